@@ -62,6 +62,11 @@ pub trait ApiComponent {
     BTreeMap::default()
   }
 
+  /// Schemas the error responses depend on besides the ones returned by [`ApiComponent::error_schemas`], by status code
+  fn error_child_schemas() -> BTreeMap<String, Vec<(String, ReferenceOr<Schema>)>> {
+    BTreeMap::default()
+  }
+
   fn responses(_content_type: Option<String>) -> Option<Responses> {
     None
   }
@@ -171,6 +176,11 @@ where
     E::schemas_by_status_code()
   }
 
+  // We expect error to be present only for response part
+  fn error_child_schemas() -> BTreeMap<String, Vec<(String, ReferenceOr<Schema>)>> {
+    E::error_child_schemas()
+  }
+
   fn responses(content_type: Option<String>) -> Option<Responses> {
     T::responses(content_type)
   }
@@ -269,6 +279,14 @@ where
     error_schemas
   }
 
+  fn error_child_schemas() -> BTreeMap<String, Vec<(String, ReferenceOr<Schema>)>> {
+    let mut error_child_schemas = E::error_child_schemas();
+    for (status, mut schemas) in T::error_child_schemas() {
+      error_child_schemas.entry(status).or_default().append(&mut schemas);
+    }
+    error_child_schemas
+  }
+
   fn responses(content_type: Option<String>) -> Option<Responses> {
     let responses = T::responses(content_type.clone());
     match responses {
@@ -332,6 +350,10 @@ where
     R::error_schemas()
   }
 
+  fn error_child_schemas() -> BTreeMap<String, Vec<(String, ReferenceOr<Schema>)>> {
+    R::error_child_schemas()
+  }
+
   fn responses(content_type: Option<String>) -> Option<Responses> {
     let mut responses = vec![];
     if let Some(response) = R::responses(content_type.clone()) {
@@ -341,62 +363,8 @@ where
           .into_iter()
           .collect::<Vec<(String, ReferenceOr<Response>)>>(),
       );
-    } else if let Some((name, schema)) = Self::schema() {
-      let ref_or = match schema {
-        r @ ReferenceOr::Reference { .. } => r,
-        ReferenceOr::Object(schema_obj) => {
-          let _ref = ReferenceOr::Reference {
-            _ref: format!("#/components/schemas/{}", name),
-          };
-          match schema_obj {
-            Schema::Object(obj) => {
-              if obj.instance_type == Some(SingleOrVec::Single(Box::new(InstanceType::Array))) {
-                ReferenceOr::Object(Schema::Object(obj))
-              } else {
-                _ref
-              }
-            }
-            Schema::Bool(_) => _ref,
-          }
-        }
-      };
-      responses.push((
-        "200".to_owned(),
-        ReferenceOr::Object(Response {
-          content: BTreeMap::from_iter(vec![(
-            content_type.unwrap_or_else(Self::content_type),
-            MediaType {
-              schema: Some(ref_or),
-              ..Default::default()
-            },
-          )]),
-          ..Default::default()
-        }),
-      ));
-    } else if let Some(schema) = Self::raw_schema() {
-      responses.push((
-        "200".to_owned(),
-        ReferenceOr::Object(Response {
-          content: BTreeMap::from_iter(vec![(
-            content_type.unwrap_or_else(Self::content_type),
-            MediaType {
-              schema: Some(schema),
-              ..Default::default()
-            },
-          )]),
-          ..Default::default()
-        }),
-      ));
-    } else if let Some(content_type) = content_type {
-      responses.push((
-        "200".to_owned(),
-        ReferenceOr::Object(Response {
-          content: BTreeMap::from_iter(vec![(content_type, MediaType::default())]),
-          ..Default::default()
-        }),
-      ));
     } else {
-      responses.push(("200".to_owned(), ReferenceOr::Object(Response::default())));
+      responses.push(("200".to_owned(), success_response::<Self>(content_type)));
     }
 
     responses.append(
@@ -412,6 +380,108 @@ where
   }
 }
 
+/// Build the responses documenting the given component: its own responses if any, a `200` response based on its schema otherwise, along with its error responses.
+pub fn component_responses<T: ApiComponent>(content_type: Option<String>) -> Responses {
+  let mut responses = match T::responses(content_type.clone()) {
+    Some(responses) => responses,
+    None => Responses {
+      responses: BTreeMap::from_iter(vec![("200".to_owned(), success_response::<T>(content_type))]),
+      ..Default::default()
+    },
+  };
+  responses.responses.extend(
+    T::error_responses()
+      .into_iter()
+      .map(|(status, schema)| (status, ReferenceOr::Object(schema))),
+  );
+  responses
+}
+
+/// Build a response documenting the given component as its body, its schema being referenced from the document components.
+pub fn component_response<T: ApiComponent>(description: String, content_type: Option<String>) -> Response {
+  Response {
+    description,
+    ..content_response::<T>(content_type)
+  }
+}
+
+fn success_response<T: ApiComponent>(content_type: Option<String>) -> ReferenceOr<Response> {
+  ReferenceOr::Object(content_response::<T>(content_type))
+}
+
+/// Build a schema matching exactly one of the given components, their schemas being referenced from the document components.
+pub fn one_of_schema(schemas: Vec<(String, ReferenceOr<Schema>)>) -> ReferenceOr<Schema> {
+  let one_of = schemas
+    .into_iter()
+    .map(|(name, schema)| match schema_reference(&name, schema) {
+      ReferenceOr::Reference { _ref } => Schema::new_ref(_ref),
+      ReferenceOr::Object(schema) => schema,
+    })
+    .collect();
+  ReferenceOr::Object(Schema::Object(SchemaObject {
+    subschemas: Some(Box::new(SubschemaValidation {
+      one_of: Some(one_of),
+      ..Default::default()
+    })),
+    ..Default::default()
+  }))
+}
+
+fn schema_reference(name: &str, schema: ReferenceOr<Schema>) -> ReferenceOr<Schema> {
+  match schema {
+    r @ ReferenceOr::Reference { .. } => r,
+    ReferenceOr::Object(schema_obj) => {
+      let _ref = ReferenceOr::Reference {
+        _ref: format!("#/components/schemas/{}", name),
+      };
+      match schema_obj {
+        Schema::Object(obj) => {
+          if obj.instance_type == Some(SingleOrVec::Single(Box::new(InstanceType::Array))) {
+            ReferenceOr::Object(Schema::Object(obj))
+          } else {
+            _ref
+          }
+        }
+        Schema::Bool(_) => _ref,
+      }
+    }
+  }
+}
+
+fn content_response<T: ApiComponent>(content_type: Option<String>) -> Response {
+  if let Some((name, schema)) = T::schema() {
+    let ref_or = schema_reference(&name, schema);
+    Response {
+      content: BTreeMap::from_iter(vec![(
+        content_type.unwrap_or_else(T::content_type),
+        MediaType {
+          schema: Some(ref_or),
+          ..Default::default()
+        },
+      )]),
+      ..Default::default()
+    }
+  } else if let Some(schema) = T::raw_schema() {
+    Response {
+      content: BTreeMap::from_iter(vec![(
+        content_type.unwrap_or_else(T::content_type),
+        MediaType {
+          schema: Some(schema),
+          ..Default::default()
+        },
+      )]),
+      ..Default::default()
+    }
+  } else if let Some(content_type) = content_type {
+    Response {
+      content: BTreeMap::from_iter(vec![(content_type, MediaType::default())]),
+      ..Default::default()
+    }
+  } else {
+    Response::default()
+  }
+}
+
 #[cfg(test)]
 mod test {
   use crate::ApiComponent;
@@ -10,10 +10,8 @@ macro_rules! simple_modifier {
         vec![]
       }
       fn raw_schema() -> Option<apistos_models::reference_or::ReferenceOr<apistos_models::Schema>> {
-        let gen = schemars::gen::SchemaSettings::openapi3().into_generator();
-
         let schema: apistos_models::reference_or::ReferenceOr<apistos_models::Schema> =
-          apistos_models::Schema::Object(gen.into_root_schema_for::<$ty>().schema).into();
+          apistos_models::Schema::Object(crate::root_schema_for::<$ty>().schema.clone()).into();
         Some(schema)
       }
       fn schema() -> Option<(
@@ -67,10 +65,8 @@ impl<T: chrono::TimeZone> ApiComponent for chrono::DateTime<T> {
   }
 
   fn raw_schema() -> Option<apistos_models::reference_or::ReferenceOr<apistos_models::Schema>> {
-    let gen = schemars::gen::SchemaSettings::openapi3().into_generator();
-
     let schema: apistos_models::reference_or::ReferenceOr<apistos_models::Schema> =
-      apistos_models::Schema::Object(gen.into_root_schema_for::<chrono::DateTime<T>>().schema).into();
+      apistos_models::Schema::Object(crate::root_schema_for::<chrono::DateTime<T>>().schema.clone()).into();
     Some(schema)
   }
 
@@ -6,6 +6,11 @@ use std::collections::BTreeMap;
 pub trait ApiErrorComponent {
   fn schemas_by_status_code() -> BTreeMap<String, (String, ReferenceOr<Schema>)>;
   fn error_responses() -> Vec<(String, Response)>;
+
+  /// Schemas the error responses depend on besides the ones returned by [`ApiErrorComponent::schemas_by_status_code`], by status code
+  fn error_child_schemas() -> BTreeMap<String, Vec<(String, ReferenceOr<Schema>)>> {
+    BTreeMap::default()
+  }
 }
 
 #[cfg(feature = "actix")]
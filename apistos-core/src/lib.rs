@@ -4,13 +4,15 @@ mod api_component;
 mod components;
 mod error_component;
 mod path_item_definition;
+mod registry;
 #[cfg(feature = "actix")]
 mod wrappers;
 
-pub use api_component::ApiComponent;
+pub use api_component::{component_response, component_responses, one_of_schema, ApiComponent};
 pub use components::*;
 pub use error_component::ApiErrorComponent;
-pub use path_item_definition::PathItemDefinition;
+pub use path_item_definition::{HandlerLink, PathItemDefinition};
+pub use registry::root_schema_for;
 #[cfg(feature = "actix")]
 pub use wrappers::{ResponderWrapper, ResponseWrapper};
 
@@ -1,5 +1,6 @@
 use apistos_models::components::Components;
 use apistos_models::paths::Operation;
+use std::any::TypeId;
 
 pub trait PathItemDefinition {
   fn is_visible() -> bool {
@@ -13,4 +14,25 @@ pub trait PathItemDefinition {
   fn components() -> Vec<Components> {
     Default::default()
   }
+
+  /// Name of the documented handler function.
+  fn handler_name() -> Option<&'static str> {
+    None
+  }
+
+  /// Links of the operation responses targeting the operation of another handler, resolved to its `operationId` once the document is built.
+  fn handler_links() -> Vec<HandlerLink> {
+    vec![]
+  }
+}
+
+/// A link declared with `#[api_operation(link(operation = handler))]`.
+#[derive(Clone, Debug)]
+pub struct HandlerLink {
+  /// Name of the link in the operation responses.
+  pub name: String,
+  /// Name of the targeted handler function.
+  pub handler: &'static str,
+  /// Type of the targeted handler function.
+  pub handler_id: TypeId,
 }
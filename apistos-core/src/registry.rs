@@ -0,0 +1,122 @@
+use once_cell::sync::Lazy;
+use schemars::gen::SchemaSettings;
+use schemars::schema::RootSchema;
+use schemars::JsonSchema;
+use std::any::type_name;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Type name and schema id of the type a root schema was generated for.
+type RootSchemaKey = (&'static str, Cow<'static, str>);
+
+/// Root schemas already generated, keyed by the type they were generated for.
+static ROOT_SCHEMAS: Lazy<RwLock<HashMap<RootSchemaKey, Arc<RootSchema>>>> = Lazy::new(Default::default);
+
+/// Root schema of **`T`** and its definitions, generated using openapi 3 settings.
+///
+/// Root schemas are memoized per type: **`T`** is only run through a schema generator on the first call, later
+/// calls from [`ApiComponent::schema`](crate::ApiComponent::schema) and [`ApiComponent::child_schemas`](crate::ApiComponent::child_schemas)
+/// share the same [`RootSchema`]. Callers still own (and therefore clone) the schemas they return.
+///
+/// Types are identified by their [type name](std::any::type_name), which includes the enclosing module or function, together
+/// with their [schema id](JsonSchema::schema_id), so that types borrowing data do not need to be `'static`.
+pub fn root_schema_for<T: JsonSchema + ?Sized>() -> Arc<RootSchema> {
+  let key = (type_name::<T>(), T::schema_id());
+  if let Some(root_schema) = ROOT_SCHEMAS.read().unwrap_or_else(PoisonError::into_inner).get(&key) {
+    return root_schema.clone();
+  }
+
+  let root_schema = Arc::new(SchemaSettings::openapi3().into_generator().into_root_schema_for::<T>());
+  ROOT_SCHEMAS
+    .write()
+    .unwrap_or_else(PoisonError::into_inner)
+    .entry(key)
+    .or_insert(root_schema)
+    .clone()
+}
+
+#[cfg(test)]
+mod test {
+  use crate::registry::root_schema_for;
+  use schemars::gen::SchemaSettings;
+  use schemars::JsonSchema;
+  use std::sync::Arc;
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Pet {
+    name: String,
+    owner: Owner,
+  }
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Owner {
+    name: String,
+  }
+
+  #[test]
+  fn root_schema_generated_once() {
+    let root_schema = root_schema_for::<Pet>();
+
+    assert!(Arc::ptr_eq(&root_schema, &root_schema_for::<Pet>()));
+    assert_eq!(
+      *root_schema,
+      SchemaSettings::openapi3()
+        .into_generator()
+        .into_root_schema_for::<Pet>()
+    );
+    assert!(root_schema.definitions.contains_key("Owner"));
+  }
+
+  mod other {
+    use schemars::JsonSchema;
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    pub(super) struct Pet {
+      id: u32,
+    }
+  }
+
+  #[test]
+  fn root_schema_keyed_by_type() {
+    let root_schema = root_schema_for::<Pet>();
+    let other_root_schema = root_schema_for::<other::Pet>();
+
+    assert!(!Arc::ptr_eq(&root_schema, &other_root_schema));
+    assert_eq!(
+      *other_root_schema,
+      SchemaSettings::openapi3()
+        .into_generator()
+        .into_root_schema_for::<other::Pet>()
+    );
+  }
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Page<'a> {
+    name: std::borrow::Cow<'a, str>,
+  }
+
+  fn page_root_schema(_page: &Page<'_>) -> Arc<schemars::schema::RootSchema> {
+    root_schema_for::<Page<'_>>()
+  }
+
+  #[test]
+  fn root_schema_for_borrowing_type() {
+    let name = String::from("pets");
+    let root_schema = page_root_schema(&Page {
+      name: std::borrow::Cow::Borrowed(&name),
+    });
+
+    assert!(Arc::ptr_eq(&root_schema, &root_schema_for::<Page<'static>>()));
+    assert_eq!(
+      *root_schema,
+      SchemaSettings::openapi3()
+        .into_generator()
+        .into_root_schema_for::<Page<'static>>()
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use crate::{ApiComponent, PathItemDefinition};
+use crate::{ApiComponent, HandlerLink, PathItemDefinition};
 use actix_web::{HttpRequest, HttpResponse, Responder};
 use apistos_models::components::Components;
 use apistos_models::paths::Operation;
@@ -54,6 +54,14 @@ where
   fn components() -> Vec<Components> {
     P::components()
   }
+
+  fn handler_name() -> Option<&'static str> {
+    P::handler_name()
+  }
+
+  fn handler_links() -> Vec<HandlerLink> {
+    P::handler_links()
+  }
 }
 
 pub struct ResponderWrapper<T>(pub T);
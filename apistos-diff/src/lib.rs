@@ -0,0 +1,667 @@
+//! Compare two [OAS 3.0](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md) documents and classify every change as breaking, non-breaking or informational.
+//!
+//! ```no_run
+//! # use apistos_models::OpenApi;
+//! # let (base, head) = (OpenApi::default(), OpenApi::default());
+//! let report = apistos_diff::diff(&base, &head);
+//! if report.is_breaking() {
+//!   println!("{}", report.to_markdown());
+//! }
+//! ```
+//!
+//! A `apistos-diff` binary is also provided to compare two json documents, e.g. to gate pull requests against the document committed on the main branch.
+
+use crate::schema::Direction;
+use apistos_models::components::Components;
+use apistos_models::paths::{MediaType, Operation, Parameter, ParameterDefinition, ParameterIn, PathItem, Response};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::security::SecurityRequirement;
+use apistos_models::OpenApi;
+use std::collections::BTreeMap;
+
+mod report;
+mod schema;
+
+pub use report::{Change, ChangeKind, Report, Severity};
+
+/// Compare the `head` document against the `base` one.
+pub fn diff(base: &OpenApi, head: &OpenApi) -> Report {
+  let empty = Components::default();
+  let mut differ = Differ {
+    base: Document::new(base, &empty),
+    head: Document::new(head, &empty),
+    visiting: vec![],
+    changes: vec![],
+  };
+  differ.diff();
+  Report {
+    changes: differ.changes,
+  }
+}
+
+/// An openapi document along with its components, used to resolve references.
+struct Document<'a> {
+  open_api: &'a OpenApi,
+  components: &'a Components,
+}
+
+impl<'a> Document<'a> {
+  fn new(open_api: &'a OpenApi, empty: &'a Components) -> Self {
+    Self {
+      open_api,
+      components: open_api.components.as_ref().unwrap_or(empty),
+    }
+  }
+}
+
+fn resolve<'a, T: Clone>(
+  mut item: &'a ReferenceOr<T>,
+  components: &'a BTreeMap<String, ReferenceOr<T>>,
+  kind: &str,
+) -> Option<&'a T> {
+  for _ in 0..=components.len() {
+    match item {
+      ReferenceOr::Object(object) => return Some(object),
+      ReferenceOr::Reference { _ref } => {
+        let name = _ref
+          .strip_prefix("#/components/")?
+          .strip_prefix(kind)?
+          .strip_prefix('/')?;
+        item = components.get(name)?;
+      }
+    }
+  }
+  None
+}
+
+struct Differ<'a> {
+  base: Document<'a>,
+  head: Document<'a>,
+  /// Pairs of schema references being compared, to stop on recursive schemas.
+  visiting: Vec<(&'a str, &'a str)>,
+  changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+  fn change(&mut self, severity: Severity, kind: ChangeKind, location: &str, description: String) {
+    self.changes.push(Change {
+      severity,
+      kind,
+      location: location.to_owned(),
+      description,
+    });
+  }
+
+  fn diff(&mut self) {
+    let (base, head) = (self.base.open_api, self.head.open_api);
+    if base.info.version != head.info.version {
+      self.change(
+        Severity::Informational,
+        ChangeKind::VersionChanged,
+        "info",
+        format!(
+          "version changed from `{}` to `{}`",
+          base.info.version, head.info.version
+        ),
+      );
+    }
+
+    for (path, base_path_item) in &base.paths.paths {
+      match head.paths.paths.get(path) {
+        None => self.change(
+          Severity::Breaking,
+          ChangeKind::PathRemoved,
+          path,
+          "path removed".to_string(),
+        ),
+        Some(head_path_item) => self.path_item(path, base_path_item, head_path_item),
+      }
+    }
+    for path in head
+      .paths
+      .paths
+      .keys()
+      .filter(|path| !base.paths.paths.contains_key(*path))
+    {
+      self.change(
+        Severity::NonBreaking,
+        ChangeKind::PathAdded,
+        path,
+        "path added".to_string(),
+      );
+    }
+  }
+
+  fn path_item(&mut self, path: &str, base: &'a PathItem, head: &'a PathItem) {
+    for (operation_type, base_operation) in &base.operations {
+      let location = format!("{} {path}", operation_type.to_string().to_uppercase());
+      match head.operations.get(operation_type) {
+        None => self.change(
+          Severity::Breaking,
+          ChangeKind::OperationRemoved,
+          &location,
+          "operation removed".to_string(),
+        ),
+        Some(head_operation) => self.operation(&location, (base, base_operation), (head, head_operation)),
+      }
+    }
+    for operation_type in head.operations.keys().filter(|o| !base.operations.contains_key(*o)) {
+      let location = format!("{} {path}", operation_type.to_string().to_uppercase());
+      self.change(
+        Severity::NonBreaking,
+        ChangeKind::OperationAdded,
+        &location,
+        "operation added".to_string(),
+      );
+    }
+  }
+
+  fn operation(
+    &mut self,
+    location: &str,
+    (base_path_item, base): (&'a PathItem, &'a Operation),
+    (head_path_item, head): (&'a PathItem, &'a Operation),
+  ) {
+    if !base.deprecated.unwrap_or_default() && head.deprecated.unwrap_or_default() {
+      self.change(
+        Severity::Informational,
+        ChangeKind::OperationDeprecated,
+        location,
+        "operation deprecated".to_string(),
+      );
+    }
+    if base.operation_id != head.operation_id {
+      self.change(
+        Severity::Informational,
+        ChangeKind::OperationIdChanged,
+        location,
+        format!(
+          "operation id changed from `{}` to `{}`",
+          base.operation_id.as_deref().unwrap_or_default(),
+          head.operation_id.as_deref().unwrap_or_default()
+        ),
+      );
+    }
+
+    self.security(location, base, head);
+    self.parameters(
+      location,
+      &parameters(base_path_item, base, self.base.components),
+      &parameters(head_path_item, head, self.head.components),
+    );
+    self.request_body(location, base, head);
+    self.responses(location, base, head);
+  }
+
+  fn security(&mut self, location: &str, base: &Operation, head: &Operation) {
+    let base_security = effective_security(base, self.base.open_api);
+    let head_security = effective_security(head, self.head.open_api);
+    // an empty requirement makes authentication optional
+    let base_optional = base_security.is_empty() || base_security.iter().any(|s| s.requirements.is_empty());
+    let head_optional = head_security.is_empty() || head_security.iter().any(|s| s.requirements.is_empty());
+
+    match (base_optional, head_optional) {
+      (true, false) => self.change(
+        Severity::Breaking,
+        ChangeKind::SecurityAdded,
+        location,
+        "authentication became required".to_string(),
+      ),
+      (false, true) => self.change(
+        Severity::NonBreaking,
+        ChangeKind::SecurityRemoved,
+        location,
+        "authentication became optional".to_string(),
+      ),
+      (false, false) => {
+        for requirement in base_security.iter().filter(|r| !head_security.contains(r)) {
+          self.change(
+            Severity::Breaking,
+            ChangeKind::SecurityRemoved,
+            location,
+            format!("security requirement {} removed", security_requirement(requirement)),
+          );
+        }
+        for requirement in head_security.iter().filter(|r| !base_security.contains(r)) {
+          self.change(
+            Severity::NonBreaking,
+            ChangeKind::SecurityAdded,
+            location,
+            format!("security requirement {} added", security_requirement(requirement)),
+          );
+        }
+      }
+      (true, true) => {}
+    }
+  }
+
+  fn parameters(
+    &mut self,
+    location: &str,
+    base: &BTreeMap<(&'static str, &'a str), &'a Parameter>,
+    head: &BTreeMap<(&'static str, &'a str), &'a Parameter>,
+  ) {
+    for ((parameter_in, name), base_parameter) in base {
+      let context = format!("{parameter_in} parameter `{name}`");
+      let Some(head_parameter) = head.get(&(*parameter_in, *name)) else {
+        self.change(
+          Severity::NonBreaking,
+          ChangeKind::ParameterRemoved,
+          location,
+          format!("{context} removed"),
+        );
+        continue;
+      };
+
+      match (
+        base_parameter.required.unwrap_or_default(),
+        head_parameter.required.unwrap_or_default(),
+      ) {
+        (false, true) => self.change(
+          Severity::Breaking,
+          ChangeKind::ParameterRequired,
+          location,
+          format!("{context} became required"),
+        ),
+        (true, false) => self.change(
+          Severity::NonBreaking,
+          ChangeKind::ParameterOptional,
+          location,
+          format!("{context} became optional"),
+        ),
+        _ => {}
+      }
+
+      match (base_parameter.definition.as_ref(), head_parameter.definition.as_ref()) {
+        (Some(ParameterDefinition::Schema(base_schema)), Some(ParameterDefinition::Schema(head_schema))) => {
+          self.reference_or_schema(location, &context, base_schema, head_schema, Direction::Request);
+        }
+        (Some(ParameterDefinition::Content(base_content)), Some(ParameterDefinition::Content(head_content))) => {
+          self.content(location, &context, base_content, head_content, Direction::Request);
+        }
+        _ => {}
+      }
+    }
+
+    for ((parameter_in, name), head_parameter) in head.iter().filter(|(key, _)| !base.contains_key(*key)) {
+      let (severity, requirement) = if head_parameter.required.unwrap_or_default() {
+        (Severity::Breaking, "required")
+      } else {
+        (Severity::NonBreaking, "optional")
+      };
+      self.change(
+        severity,
+        ChangeKind::ParameterAdded,
+        location,
+        format!("{requirement} {parameter_in} parameter `{name}` added"),
+      );
+    }
+  }
+
+  fn request_body(&mut self, location: &str, base: &'a Operation, head: &'a Operation) {
+    let base_request_body = base
+      .request_body
+      .as_ref()
+      .and_then(|r| resolve(r, &self.base.components.request_bodies, "requestBodies"));
+    let head_request_body = head
+      .request_body
+      .as_ref()
+      .and_then(|r| resolve(r, &self.head.components.request_bodies, "requestBodies"));
+
+    match (base_request_body, head_request_body) {
+      (None, None) => {}
+      (Some(_), None) => self.change(
+        Severity::Breaking,
+        ChangeKind::RequestBodyRemoved,
+        location,
+        "request body removed".to_string(),
+      ),
+      (None, Some(head_request_body)) => {
+        let (severity, requirement) = if head_request_body.required.unwrap_or_default() {
+          (Severity::Breaking, "required")
+        } else {
+          (Severity::NonBreaking, "optional")
+        };
+        self.change(
+          severity,
+          ChangeKind::RequestBodyAdded,
+          location,
+          format!("{requirement} request body added"),
+        );
+      }
+      (Some(base_request_body), Some(head_request_body)) => {
+        match (
+          base_request_body.required.unwrap_or_default(),
+          head_request_body.required.unwrap_or_default(),
+        ) {
+          (false, true) => self.change(
+            Severity::Breaking,
+            ChangeKind::RequestBodyRequired,
+            location,
+            "request body became required".to_string(),
+          ),
+          (true, false) => self.change(
+            Severity::NonBreaking,
+            ChangeKind::RequestBodyOptional,
+            location,
+            "request body became optional".to_string(),
+          ),
+          _ => {}
+        }
+        self.content(
+          location,
+          "request body",
+          &base_request_body.content,
+          &head_request_body.content,
+          Direction::Request,
+        );
+      }
+    }
+  }
+
+  fn responses(&mut self, location: &str, base: &'a Operation, head: &'a Operation) {
+    let base_responses = responses(base, self.base.components);
+    let head_responses = responses(head, self.head.components);
+
+    for (status, base_response) in &base_responses {
+      let context = format!("response `{status}`");
+      match head_responses.get(status) {
+        None => self.change(
+          Severity::Breaking,
+          ChangeKind::ResponseRemoved,
+          location,
+          format!("{context} removed"),
+        ),
+        Some(head_response) => self.content(
+          location,
+          &context,
+          &base_response.content,
+          &head_response.content,
+          Direction::Response,
+        ),
+      }
+    }
+    for status in head_responses
+      .keys()
+      .filter(|status| !base_responses.contains_key(*status))
+    {
+      self.change(
+        Severity::NonBreaking,
+        ChangeKind::ResponseAdded,
+        location,
+        format!("response `{status}` added"),
+      );
+    }
+  }
+
+  fn content(
+    &mut self,
+    location: &str,
+    context: &str,
+    base: &'a BTreeMap<String, MediaType>,
+    head: &'a BTreeMap<String, MediaType>,
+    direction: Direction,
+  ) {
+    for (media_type, base_media_type) in base {
+      let Some(head_media_type) = head.get(media_type) else {
+        self.change(
+          Severity::Breaking,
+          ChangeKind::MediaTypeRemoved,
+          location,
+          format!("{context} media type `{media_type}` removed"),
+        );
+        continue;
+      };
+      if let (Some(base_schema), Some(head_schema)) = (base_media_type.schema.as_ref(), head_media_type.schema.as_ref())
+      {
+        let context = format!("{context} ({media_type})");
+        self.reference_or_schema(location, &context, base_schema, head_schema, direction);
+      }
+    }
+    for media_type in head.keys().filter(|media_type| !base.contains_key(*media_type)) {
+      self.change(
+        Severity::NonBreaking,
+        ChangeKind::MediaTypeAdded,
+        location,
+        format!("{context} media type `{media_type}` added"),
+      );
+    }
+  }
+}
+
+fn parameter_in(parameter_in: &ParameterIn) -> &'static str {
+  match parameter_in {
+    ParameterIn::Query => "query",
+    ParameterIn::Header => "header",
+    ParameterIn::Path => "path",
+    ParameterIn::Cookie => "cookie",
+  }
+}
+
+/// Parameters of an operation, including the ones inherited from its path item, by location and name.
+fn parameters<'a>(
+  path_item: &'a PathItem,
+  operation: &'a Operation,
+  components: &'a Components,
+) -> BTreeMap<(&'static str, &'a str), &'a Parameter> {
+  path_item
+    .parameters
+    .iter()
+    .chain(&operation.parameters)
+    .filter_map(|parameter| resolve(parameter, &components.parameters, "parameters"))
+    .map(|parameter| ((parameter_in(&parameter._in), parameter.name.as_str()), parameter))
+    .collect()
+}
+
+fn responses<'a>(operation: &'a Operation, components: &'a Components) -> BTreeMap<&'a str, &'a Response> {
+  operation
+    .responses
+    .default
+    .iter()
+    .map(|response| ("default", response))
+    .chain(
+      operation
+        .responses
+        .responses
+        .iter()
+        .map(|(status, response)| (status.as_str(), response)),
+    )
+    .filter_map(|(status, response)| Some((status, resolve(response, &components.responses, "responses")?)))
+    .collect()
+}
+
+fn effective_security<'a>(operation: &'a Operation, open_api: &'a OpenApi) -> &'a [SecurityRequirement] {
+  if operation.security.is_empty() {
+    &open_api.security
+  } else {
+    &operation.security
+  }
+}
+
+fn security_requirement(requirement: &SecurityRequirement) -> String {
+  requirement
+    .requirements
+    .keys()
+    .map(|name| format!("`{name}`"))
+    .collect::<Vec<_>>()
+    .join(" + ")
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::{diff, ChangeKind, Severity};
+  use apistos_models::OpenApi;
+  use serde_json::{json, Value};
+
+  fn open_api(paths: &Value, schemas: &Value) -> OpenApi {
+    serde_json::from_value(json!({
+      "openapi": "3.0.3",
+      "info": { "title": "pets", "version": "1.0.0" },
+      "servers": [],
+      "paths": paths,
+      "components": { "schemas": schemas }
+    }))
+    .expect("Unable to parse openapi document")
+  }
+
+  fn changes(base: &OpenApi, head: &OpenApi) -> Vec<(Severity, ChangeKind, String)> {
+    diff(base, head)
+      .changes
+      .into_iter()
+      .map(|change| (change.severity, change.kind, change.description))
+      .collect()
+  }
+
+  fn pets(status: &Value, pet: &Value) -> OpenApi {
+    open_api(
+      &json!({
+        "/pets": {
+          "get": {
+            "parameters": [{ "name": "status", "in": "query", "schema": status }],
+            "responses": {
+              "200": {
+                "description": "",
+                "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } } } }
+              }
+            }
+          },
+          "post": {
+            "requestBody": {
+              "required": true,
+              "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+            },
+            "responses": { "204": { "description": "" } }
+          }
+        }
+      }),
+      &json!({ "Pet": pet }),
+    )
+  }
+
+  #[test]
+  fn identical_documents_have_no_changes() {
+    let document = pets(
+      &json!({ "type": "string" }),
+      &json!({ "type": "object", "properties": { "name": { "type": "string" } } }),
+    );
+    assert!(diff(&document, &document).changes.is_empty());
+  }
+
+  #[test]
+  fn removed_path_is_breaking() {
+    let base = pets(&json!({ "type": "string" }), &json!({ "type": "object" }));
+    let head = open_api(&json!({}), &json!({}));
+
+    let report = diff(&base, &head);
+    assert!(report.is_breaking());
+    assert_eq!(
+      changes(&base, &head),
+      vec![(Severity::Breaking, ChangeKind::PathRemoved, "path removed".to_string())]
+    );
+  }
+
+  #[test]
+  fn narrowed_enum_and_new_required_property() {
+    let base = pets(
+      &json!({ "type": "string", "enum": ["available", "sold"] }),
+      &json!({ "type": "object", "properties": { "name": { "type": "string" } } }),
+    );
+    let head = pets(
+      &json!({ "type": "string", "enum": ["available"] }),
+      &json!({
+        "type": "object",
+        "required": ["age"],
+        "properties": { "name": { "type": "string" }, "age": { "type": "integer" } }
+      }),
+    );
+
+    assert_eq!(
+      changes(&base, &head),
+      vec![
+        (
+          Severity::Breaking,
+          ChangeKind::EnumNarrowed,
+          "query parameter `status`: enum value `\"sold\"` removed".to_string()
+        ),
+        (
+          Severity::NonBreaking,
+          ChangeKind::PropertyAdded,
+          "response `200` (application/json): required property `$[].age` added".to_string()
+        ),
+        (
+          Severity::Breaking,
+          ChangeKind::PropertyAdded,
+          "request body (application/json): required property `$.age` added".to_string()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn changed_response_schema_is_breaking() {
+    let base = pets(
+      &json!({ "type": "string" }),
+      &json!({ "type": "object", "properties": { "name": { "type": "string" }, "age": { "type": "integer" } } }),
+    );
+    let head = pets(
+      &json!({ "type": "string" }),
+      &json!({ "type": "object", "properties": { "name": { "type": "integer" } } }),
+    );
+
+    assert_eq!(
+      changes(&base, &head),
+      vec![
+        (
+          Severity::Breaking,
+          ChangeKind::PropertyRemoved,
+          "response `200` (application/json): property `$[].age` removed".to_string()
+        ),
+        (
+          Severity::Breaking,
+          ChangeKind::TypeChanged,
+          "response `200` (application/json): type of `$[].name` changed from `string` to `integer`".to_string()
+        ),
+        (
+          Severity::NonBreaking,
+          ChangeKind::PropertyRemoved,
+          "request body (application/json): property `$.age` removed".to_string()
+        ),
+        (
+          Severity::Breaking,
+          ChangeKind::TypeChanged,
+          "request body (application/json): type of `$.name` changed from `string` to `integer`".to_string()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn report_rendered_as_markdown() {
+    let base = pets(&json!({ "type": "string" }), &json!({ "type": "object" }));
+    let head = open_api(
+      &json!({ "/pets": { "get": { "responses": { "200": { "description": "" } } } } }),
+      &json!({}),
+    );
+
+    assert_eq!(
+      diff(&base, &head).to_markdown(),
+      "## OpenAPI changes
+
+### Breaking changes
+
+| Location | Change |
+| --- | --- |
+| `GET /pets` | response `200` media type `application/json` removed |
+| `POST /pets` | operation removed |
+
+### Non-breaking changes
+
+| Location | Change |
+| --- | --- |
+| `GET /pets` | query parameter `status` removed |
+"
+    );
+  }
+}
@@ -0,0 +1,91 @@
+//! Compare two json openapi documents and print the changes between them.
+//!
+//! ```text
+//! apistos-diff <base.json> <head.json> [--format json|markdown] [--fail-on-breaking]
+//! ```
+//!
+//! Exits with `1` when `--fail-on-breaking` is set and breaking changes are found, `2` when the documents can't be read.
+
+use apistos_diff::diff;
+use apistos_models::OpenApi;
+use std::io::Write;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: apistos-diff <base.json> <head.json> [--format json|markdown] [--fail-on-breaking]";
+
+enum Format {
+  Json,
+  Markdown,
+}
+
+struct Args {
+  base: String,
+  head: String,
+  format: Format,
+  fail_on_breaking: bool,
+}
+
+impl Args {
+  fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+    let mut documents = vec![];
+    let mut format = Format::Markdown;
+    let mut fail_on_breaking = false;
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--format" => {
+          format = match args.next().as_deref() {
+            Some("json") => Format::Json,
+            Some("markdown") => Format::Markdown,
+            _ => return Err(USAGE.to_owned()),
+          }
+        }
+        "--fail-on-breaking" => fail_on_breaking = true,
+        "-h" | "--help" => return Err(USAGE.to_owned()),
+        _ => documents.push(arg),
+      }
+    }
+    match <[String; 2]>::try_from(documents) {
+      Ok([base, head]) => Ok(Self {
+        base,
+        head,
+        format,
+        fail_on_breaking,
+      }),
+      Err(_) => Err(USAGE.to_owned()),
+    }
+  }
+}
+
+fn read(path: &str) -> Result<OpenApi, String> {
+  let document = std::fs::read_to_string(path).map_err(|e| format!("unable to read {path}: {e}"))?;
+  serde_json::from_str(&document).map_err(|e| format!("unable to parse {path}: {e}"))
+}
+
+fn run() -> Result<ExitCode, String> {
+  let args = Args::parse(std::env::args().skip(1))?;
+  let report = diff(&read(&args.base)?, &read(&args.head)?);
+
+  let output = match args.format {
+    Format::Json => report
+      .to_json()
+      .map_err(|e| format!("unable to serialize report: {e}"))?,
+    Format::Markdown => report.to_markdown(),
+  };
+  writeln!(std::io::stdout(), "{output}").map_err(|e| format!("unable to write report: {e}"))?;
+
+  if args.fail_on_breaking && report.is_breaking() {
+    Ok(ExitCode::FAILURE)
+  } else {
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+fn main() -> ExitCode {
+  run().unwrap_or_else(|e| {
+    let _ = writeln!(std::io::stderr(), "{e}");
+    ExitCode::from(2)
+  })
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for the library.
+use serde as _;
@@ -0,0 +1,121 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Impact of a change on the consumers of the API.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+  /// Existing consumers may fail against the new document.
+  Breaking,
+  /// Existing consumers keep working against the new document.
+  NonBreaking,
+  /// Change without any effect on the API contract, e.g. a deprecation.
+  Informational,
+}
+
+impl Severity {
+  fn title(self) -> &'static str {
+    match self {
+      Severity::Breaking => "Breaking changes",
+      Severity::NonBreaking => "Non-breaking changes",
+      Severity::Informational => "Informational changes",
+    }
+  }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+  VersionChanged,
+  PathAdded,
+  PathRemoved,
+  OperationAdded,
+  OperationRemoved,
+  OperationDeprecated,
+  OperationIdChanged,
+  SecurityAdded,
+  SecurityRemoved,
+  ParameterAdded,
+  ParameterRemoved,
+  ParameterRequired,
+  ParameterOptional,
+  RequestBodyAdded,
+  RequestBodyRemoved,
+  RequestBodyRequired,
+  RequestBodyOptional,
+  MediaTypeAdded,
+  MediaTypeRemoved,
+  ResponseAdded,
+  ResponseRemoved,
+  SchemaChanged,
+  TypeChanged,
+  FormatChanged,
+  NullableChanged,
+  EnumNarrowed,
+  EnumWidened,
+  PropertyAdded,
+  PropertyRemoved,
+  PropertyRequired,
+  PropertyOptional,
+}
+
+/// A single difference between the two compared documents.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+  pub severity: Severity,
+  pub kind: ChangeKind,
+  /// Where the change happened, e.g. `GET /pets/{id}`.
+  pub location: String,
+  pub description: String,
+}
+
+/// Every change found between two documents, in the order they were found.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+  pub changes: Vec<Change>,
+}
+
+impl Report {
+  /// Whether at least one change may break existing consumers.
+  pub fn is_breaking(&self) -> bool {
+    self.changes.iter().any(|change| change.severity == Severity::Breaking)
+  }
+
+  pub fn changes_with_severity(&self, severity: Severity) -> impl Iterator<Item = &Change> {
+    self.changes.iter().filter(move |change| change.severity == severity)
+  }
+
+  pub fn to_json(&self) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(self)
+  }
+
+  /// Render the report as markdown, with a table of changes per severity, e.g. to be posted on a pull request.
+  pub fn to_markdown(&self) -> String {
+    let mut markdown = "## OpenAPI changes\n".to_string();
+    if self.changes.is_empty() {
+      markdown.push_str("\nNo changes detected.\n");
+      return markdown;
+    }
+
+    for severity in [Severity::Breaking, Severity::NonBreaking, Severity::Informational] {
+      let mut changes = self.changes_with_severity(severity).peekable();
+      if changes.peek().is_none() {
+        continue;
+      }
+      let _ = write!(
+        markdown,
+        "\n### {}\n\n| Location | Change |\n| --- | --- |\n",
+        severity.title()
+      );
+      for change in changes {
+        let _ = writeln!(
+          markdown,
+          "| `{}` | {} |",
+          change.location,
+          change.description.replace('|', "\\|")
+        );
+      }
+    }
+    markdown
+  }
+}
@@ -0,0 +1,445 @@
+use crate::report::{ChangeKind, Severity};
+use crate::{Differ, Document};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// Whether a schema describes data sent by consumers or received by them.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Direction {
+  Request,
+  Response,
+}
+
+impl Direction {
+  /// Severity of a schema now accepting less values than before.
+  fn narrowed(self) -> Severity {
+    match self {
+      Direction::Request => Severity::Breaking,
+      Direction::Response => Severity::NonBreaking,
+    }
+  }
+
+  /// Severity of a schema now accepting more values than before.
+  fn widened(self) -> Severity {
+    match self {
+      Direction::Request => Severity::NonBreaking,
+      Direction::Response => Severity::Breaking,
+    }
+  }
+}
+
+/// A schema with its references resolved, along with the first reference it was reached through.
+#[derive(Clone, Copy)]
+struct Resolved<'a> {
+  reference: Option<&'a str>,
+  schema: &'a Schema,
+}
+
+impl<'a> Document<'a> {
+  fn schema_reference(&self, reference: &str) -> Option<&'a Schema> {
+    let name = reference.strip_prefix("#/components/schemas/")?;
+    match self.components.schemas.get(name)? {
+      ReferenceOr::Object(schema) => Some(schema),
+      ReferenceOr::Reference { .. } => None,
+    }
+  }
+
+  fn resolve_schema(&self, mut schema: &'a Schema) -> Option<Resolved<'a>> {
+    let mut first_reference = None;
+    for _ in 0..=self.components.schemas.len() {
+      match schema {
+        Schema::Object(SchemaObject {
+          reference: Some(reference),
+          ..
+        }) => {
+          first_reference.get_or_insert(reference.as_str());
+          schema = self.schema_reference(reference)?;
+        }
+        schema => {
+          return Some(Resolved {
+            reference: first_reference,
+            schema,
+          })
+        }
+      }
+    }
+    None
+  }
+
+  fn resolve_reference_or_schema(&self, schema: &'a ReferenceOr<Schema>) -> Option<Resolved<'a>> {
+    match schema {
+      ReferenceOr::Object(schema) => self.resolve_schema(schema),
+      ReferenceOr::Reference { _ref } => self
+        .resolve_schema(self.schema_reference(_ref)?)
+        .map(|resolved| Resolved {
+          reference: Some(_ref.as_str()),
+          ..resolved
+        }),
+    }
+  }
+}
+
+impl<'a> Differ<'a> {
+  pub(crate) fn reference_or_schema(
+    &mut self,
+    location: &str,
+    context: &str,
+    base: &'a ReferenceOr<Schema>,
+    head: &'a ReferenceOr<Schema>,
+    direction: Direction,
+  ) {
+    match (
+      self.base.resolve_reference_or_schema(base),
+      self.head.resolve_reference_or_schema(head),
+    ) {
+      (Some(base), Some(head)) => self.resolved_schema(location, context, "$", base, head, direction),
+      _ => self.unresolved_schema(location, context, "$", base, head),
+    }
+  }
+
+  fn schema(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: &'a Schema,
+    head: &'a Schema,
+    direction: Direction,
+  ) {
+    match (self.base.resolve_schema(base), self.head.resolve_schema(head)) {
+      (Some(base), Some(head)) => self.resolved_schema(location, context, pointer, base, head, direction),
+      _ => self.unresolved_schema(location, context, pointer, base, head),
+    }
+  }
+
+  /// Schemas referencing missing components can only be compared as is.
+  fn unresolved_schema<T: PartialEq>(&mut self, location: &str, context: &str, pointer: &str, base: &T, head: &T) {
+    if base != head {
+      self.change(
+        Severity::Breaking,
+        ChangeKind::SchemaChanged,
+        location,
+        format!("{context}: schema of `{pointer}` changed"),
+      );
+    }
+  }
+
+  fn resolved_schema(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: Resolved<'a>,
+    head: Resolved<'a>,
+    direction: Direction,
+  ) {
+    let references = base.reference.zip(head.reference);
+    if let Some(references) = references {
+      if self.visiting.contains(&references) {
+        return;
+      }
+      self.visiting.push(references);
+    }
+
+    match (base.schema, head.schema) {
+      (Schema::Object(base), Schema::Object(head)) => {
+        self.schema_object(location, context, pointer, base, head, direction);
+      }
+      (base, head) => self.unresolved_schema(location, context, pointer, base, head),
+    }
+
+    if references.is_some() {
+      self.visiting.pop();
+    }
+  }
+
+  fn schema_object(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: &'a SchemaObject,
+    head: &'a SchemaObject,
+    direction: Direction,
+  ) {
+    let base_types = instance_types(base);
+    let head_types = instance_types(head);
+    if !base_types.is_empty() && !head_types.is_empty() && base_types != head_types {
+      let severity = if head_types.is_superset(&base_types) {
+        direction.widened()
+      } else if head_types.is_subset(&base_types) {
+        direction.narrowed()
+      } else {
+        Severity::Breaking
+      };
+      self.change(
+        severity,
+        ChangeKind::TypeChanged,
+        location,
+        format!(
+          "{context}: type of `{pointer}` changed from `{}` to `{}`",
+          instance_types_name(&base_types),
+          instance_types_name(&head_types)
+        ),
+      );
+    }
+
+    match (nullable(base), nullable(head)) {
+      (false, true) => self.change(
+        direction.widened(),
+        ChangeKind::NullableChanged,
+        location,
+        format!("{context}: `{pointer}` became nullable"),
+      ),
+      (true, false) => self.change(
+        direction.narrowed(),
+        ChangeKind::NullableChanged,
+        location,
+        format!("{context}: `{pointer}` is no longer nullable"),
+      ),
+      _ => {}
+    }
+
+    if base.format != head.format {
+      let severity = match (&base.format, &head.format) {
+        (None, Some(_)) => direction.narrowed(),
+        (Some(_), None) => direction.widened(),
+        _ => Severity::Breaking,
+      };
+      self.change(
+        severity,
+        ChangeKind::FormatChanged,
+        location,
+        format!(
+          "{context}: format of `{pointer}` changed from `{}` to `{}`",
+          base.format.as_deref().unwrap_or("none"),
+          head.format.as_deref().unwrap_or("none")
+        ),
+      );
+    }
+
+    self.enum_values(location, context, pointer, base, head, direction);
+    self.properties(location, context, pointer, base, head, direction);
+
+    let base_items = base.array.as_ref().and_then(|array| array.items.as_ref());
+    let head_items = head.array.as_ref().and_then(|array| array.items.as_ref());
+    if let (Some(SingleOrVec::Single(base_items)), Some(SingleOrVec::Single(head_items))) = (base_items, head_items) {
+      self.schema(
+        location,
+        context,
+        &format!("{pointer}[]"),
+        base_items,
+        head_items,
+        direction,
+      );
+    }
+
+    self.subschemas(location, context, pointer, base, head, direction);
+  }
+
+  fn enum_values(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: &SchemaObject,
+    head: &SchemaObject,
+    direction: Direction,
+  ) {
+    let at = if pointer == "$" {
+      String::new()
+    } else {
+      format!(" of `{pointer}`")
+    };
+    match (base.enum_values.as_ref(), head.enum_values.as_ref()) {
+      (Some(base_values), Some(head_values)) => {
+        for value in base_values.iter().filter(|value| !head_values.contains(value)) {
+          self.change(
+            direction.narrowed(),
+            ChangeKind::EnumNarrowed,
+            location,
+            format!("{context}: enum value `{value}`{at} removed"),
+          );
+        }
+        for value in head_values.iter().filter(|value| !base_values.contains(value)) {
+          self.change(
+            direction.widened(),
+            ChangeKind::EnumWidened,
+            location,
+            format!("{context}: enum value `{value}`{at} added"),
+          );
+        }
+      }
+      (None, Some(_)) => self.change(
+        direction.narrowed(),
+        ChangeKind::EnumNarrowed,
+        location,
+        format!("{context}: `{pointer}` restricted to an enum"),
+      ),
+      (Some(_), None) => self.change(
+        direction.widened(),
+        ChangeKind::EnumWidened,
+        location,
+        format!("{context}: `{pointer}` no longer restricted to an enum"),
+      ),
+      (None, None) => {}
+    }
+  }
+
+  fn properties(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: &'a SchemaObject,
+    head: &'a SchemaObject,
+    direction: Direction,
+  ) {
+    let (Some(base), Some(head)) = (base.object.as_ref(), head.object.as_ref()) else {
+      return;
+    };
+
+    for (name, base_property) in &base.properties {
+      let property = format!("{pointer}.{name}");
+      let Some(head_property) = head.properties.get(name) else {
+        let severity = match direction {
+          Direction::Request => Severity::NonBreaking,
+          Direction::Response => Severity::Breaking,
+        };
+        self.change(
+          severity,
+          ChangeKind::PropertyRemoved,
+          location,
+          format!("{context}: property `{property}` removed"),
+        );
+        continue;
+      };
+
+      match (base.required.contains(name), head.required.contains(name)) {
+        (false, true) => self.change(
+          direction.narrowed(),
+          ChangeKind::PropertyRequired,
+          location,
+          format!("{context}: property `{property}` became required"),
+        ),
+        (true, false) => self.change(
+          direction.widened(),
+          ChangeKind::PropertyOptional,
+          location,
+          format!("{context}: property `{property}` became optional"),
+        ),
+        _ => {}
+      }
+      self.schema(location, context, &property, base_property, head_property, direction);
+    }
+
+    for name in head
+      .properties
+      .keys()
+      .filter(|name| !base.properties.contains_key(*name))
+    {
+      let required = head.required.contains(name);
+      let severity = match direction {
+        Direction::Request if required => Severity::Breaking,
+        _ => Severity::NonBreaking,
+      };
+      self.change(
+        severity,
+        ChangeKind::PropertyAdded,
+        location,
+        format!(
+          "{context}: {}property `{pointer}.{name}` added",
+          if required { "required " } else { "" }
+        ),
+      );
+    }
+  }
+
+  fn subschemas(
+    &mut self,
+    location: &str,
+    context: &str,
+    pointer: &str,
+    base: &'a SchemaObject,
+    head: &'a SchemaObject,
+    direction: Direction,
+  ) {
+    let base_subschemas = base.subschemas.as_deref();
+    let head_subschemas = head.subschemas.as_deref();
+    let compositions = [
+      (
+        "allOf",
+        base_subschemas.and_then(|s| s.all_of.as_ref()),
+        head_subschemas.and_then(|s| s.all_of.as_ref()),
+      ),
+      (
+        "anyOf",
+        base_subschemas.and_then(|s| s.any_of.as_ref()),
+        head_subschemas.and_then(|s| s.any_of.as_ref()),
+      ),
+      (
+        "oneOf",
+        base_subschemas.and_then(|s| s.one_of.as_ref()),
+        head_subschemas.and_then(|s| s.one_of.as_ref()),
+      ),
+    ];
+
+    for (composition, base_schemas, head_schemas) in compositions {
+      match (base_schemas, head_schemas) {
+        (Some(base_schemas), Some(head_schemas)) if base_schemas.len() == head_schemas.len() => {
+          for (base_schema, head_schema) in base_schemas.iter().zip(head_schemas) {
+            self.schema(location, context, pointer, base_schema, head_schema, direction);
+          }
+        }
+        (base_schemas, head_schemas) if base_schemas != head_schemas => {
+          let base_len = base_schemas.map_or(0, Vec::len);
+          let head_len = head_schemas.map_or(0, Vec::len);
+          // adding schemas to an `allOf` adds constraints while adding schemas to an `anyOf` or `oneOf` adds alternatives
+          let severity = match (composition == "allOf", base_len.cmp(&head_len)) {
+            (_, Ordering::Equal) => Severity::Breaking,
+            (true, Ordering::Less) | (false, Ordering::Greater) => direction.narrowed(),
+            (true, Ordering::Greater) | (false, Ordering::Less) => direction.widened(),
+          };
+          self.change(
+            severity,
+            ChangeKind::SchemaChanged,
+            location,
+            format!("{context}: `{composition}` of `{pointer}` changed from {base_len} to {head_len} schemas"),
+          );
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
+fn instance_types(schema: &SchemaObject) -> BTreeSet<InstanceType> {
+  match schema.instance_type.as_ref() {
+    None => BTreeSet::new(),
+    Some(SingleOrVec::Single(instance_type)) => BTreeSet::from([**instance_type]),
+    Some(SingleOrVec::Vec(instance_types)) => instance_types.iter().copied().collect(),
+  }
+}
+
+fn instance_types_name(instance_types: &BTreeSet<InstanceType>) -> String {
+  instance_types
+    .iter()
+    .map(|instance_type| match instance_type {
+      InstanceType::Null => "null",
+      InstanceType::Boolean => "boolean",
+      InstanceType::Object => "object",
+      InstanceType::Array => "array",
+      InstanceType::Number => "number",
+      InstanceType::String => "string",
+      InstanceType::Integer => "integer",
+    })
+    .collect::<Vec<_>>()
+    .join(" | ")
+}
+
+fn nullable(schema: &SchemaObject) -> bool {
+  schema.extensions.get("nullable") == Some(&Value::Bool(true))
+}
@@ -113,6 +113,43 @@ fn api_component_derive_with_generic() {
   );
 }
 
+#[test]
+#[allow(dead_code)]
+fn api_component_derive_with_lifetime() {
+  #[derive(JsonSchema, ApiComponent)]
+  struct Page<'a> {
+    name: std::borrow::Cow<'a, str>,
+  }
+
+  fn page_schema(_page: &Page<'_>) -> Option<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)> {
+    <Page<'_> as ApiComponent>::schema()
+  }
+
+  let name = String::from("pets");
+  let page = Page {
+    name: std::borrow::Cow::Borrowed(&name),
+  };
+  let (schema_name, schema) = page_schema(&page).expect("schema should be defined");
+  assert_eq!(schema_name, "Page");
+  assert_schema(&schema.clone());
+  let json = serde_json::to_value(schema).expect("Unable to serialize as Json");
+  assert_json_eq!(
+    json,
+    json!({
+      "properties": {
+        "name": {
+          "type": "string"
+        }
+      },
+      "required": [
+        "name"
+      ],
+      "title": "Page",
+      "type": "object"
+    })
+  );
+}
+
 #[test]
 fn api_component_derive_with_flatten() {
   #[derive(JsonSchema, ApiComponent)]
@@ -1,6 +1,8 @@
 use apistos_core::ApiErrorComponent;
-use apistos_gen::ApiErrorComponent;
+use apistos_gen::{ApiComponent, ApiErrorComponent};
+use assert_json_diff::assert_json_eq;
 use schemars::JsonSchema;
+use serde_json::json;
 use std::collections::{BTreeMap, HashSet};
 
 #[test]
@@ -97,3 +99,271 @@ fn api_component_with_schema() {
     .unwrap_or_default()
     .is_empty());
 }
+
+#[test]
+#[allow(dead_code)]
+fn api_component_with_problem_details() {
+  #[derive(JsonSchema, ApiComponent)]
+  struct ConflictDetails {
+    conflicting_id: u32,
+  }
+
+  #[derive(ApiErrorComponent)]
+  #[openapi_error(
+    problem_details,
+    status(code = 404),
+    status(code = 409, description = "Already exists", extension = ConflictDetails)
+  )]
+  enum ErrorResponse {
+    NotFound,
+    Conflict(u32),
+  }
+
+  let error_schemas = <ErrorResponse as ApiErrorComponent>::schemas_by_status_code();
+  assert_eq!(
+    error_schemas
+      .iter()
+      .map(|(code, (name, _))| (code.as_str(), name.as_str()))
+      .collect::<Vec<_>>(),
+    vec![("404", "ProblemDetails"), ("409", "ConflictDetails")]
+  );
+  let error_child_schemas = <ErrorResponse as ApiErrorComponent>::error_child_schemas();
+  assert_eq!(
+    error_child_schemas
+      .iter()
+      .map(|(code, schemas)| (
+        code.as_str(),
+        schemas.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>()
+      ))
+      .collect::<Vec<_>>(),
+    vec![("404", vec!["ProblemDetails"]), ("409", vec!["ProblemDetails"])]
+  );
+
+  let error_responses = BTreeMap::from_iter(<ErrorResponse as ApiErrorComponent>::error_responses());
+  assert_json_eq!(
+    serde_json::to_value(&error_responses).expect("Unable to serialize responses"),
+    json!({
+      "404": {
+        "description": "Not Found",
+        "content": {
+          "application/problem+json": {
+            "schema": { "$ref": "#/components/schemas/ProblemDetails" }
+          }
+        }
+      },
+      "409": {
+        "description": "Already exists",
+        "content": {
+          "application/problem+json": {
+            "schema": {
+              "allOf": [
+                { "$ref": "#/components/schemas/ProblemDetails" },
+                { "$ref": "#/components/schemas/ConflictDetails" }
+              ]
+            }
+          }
+        }
+      }
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_component_with_content() {
+  #[derive(JsonSchema, ApiComponent)]
+  struct ErrorBody {
+    message: String,
+  }
+
+  #[derive(ApiErrorComponent)]
+  #[openapi_error(
+    status(code = 404, description = "Pet not found", content = ErrorBody),
+    status(code = 406, content_type = "text/plain"),
+    status(code = 409, content = ErrorBody, content_type = "application/vnd.error+json")
+  )]
+  enum ErrorResponse {
+    NotFound(String),
+    NotAcceptable,
+    Conflict(String),
+  }
+
+  let error_schemas = <ErrorResponse as ApiErrorComponent>::schemas_by_status_code();
+  assert_eq!(
+    error_schemas
+      .iter()
+      .map(|(code, (name, _))| (code.as_str(), name.as_str()))
+      .collect::<Vec<_>>(),
+    vec![("404", "ErrorBody"), ("409", "ErrorBody")]
+  );
+  assert!(<ErrorResponse as ApiErrorComponent>::error_child_schemas().is_empty());
+
+  let error_responses = BTreeMap::from_iter(<ErrorResponse as ApiErrorComponent>::error_responses());
+  assert_json_eq!(
+    serde_json::to_value(&error_responses).expect("Unable to serialize responses"),
+    json!({
+      "404": {
+        "description": "Pet not found",
+        "content": {
+          "application/json": {
+            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+          }
+        }
+      },
+      "406": {
+        "description": "Not Acceptable",
+        "content": {
+          "text/plain": {}
+        }
+      },
+      "409": {
+        "description": "Conflict",
+        "content": {
+          "application/vnd.error+json": {
+            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+          }
+        }
+      }
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_component_with_variants() {
+  #[derive(JsonSchema, ApiComponent)]
+  struct NotFoundBody {
+    id: u32,
+  }
+
+  #[derive(JsonSchema, ApiComponent)]
+  struct ErrorBody {
+    message: String,
+  }
+
+  #[derive(ApiErrorComponent)]
+  #[openapi_error(status(code = 403))]
+  enum ErrorResponse {
+    #[openapi_error(code = 404, description = "Pet not found", content = NotFoundBody)]
+    PetNotFound(u32),
+    #[openapi_error(code = 404, description = "Owner not found", content = ErrorBody)]
+    OwnerNotFound(String),
+    #[openapi_error(code = 409, description = "Already exists", content = ErrorBody)]
+    Conflict(String),
+    #[openapi_error(code = 409, description = "Already exists", content = ErrorBody)]
+    Duplicated(String),
+    Unexpected,
+  }
+
+  let error_schemas = <ErrorResponse as ApiErrorComponent>::schemas_by_status_code();
+  assert_eq!(
+    error_schemas
+      .iter()
+      .map(|(code, (name, _))| (code.as_str(), name.as_str()))
+      .collect::<Vec<_>>(),
+    vec![("409", "ErrorBody")]
+  );
+  let error_child_schemas = <ErrorResponse as ApiErrorComponent>::error_child_schemas();
+  assert_eq!(
+    error_child_schemas
+      .iter()
+      .map(|(code, schemas)| (
+        code.as_str(),
+        schemas.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>()
+      ))
+      .collect::<Vec<_>>(),
+    vec![("404", vec!["NotFoundBody", "ErrorBody"])]
+  );
+
+  let error_responses = BTreeMap::from_iter(<ErrorResponse as ApiErrorComponent>::error_responses());
+  assert_json_eq!(
+    serde_json::to_value(&error_responses).expect("Unable to serialize responses"),
+    json!({
+      "403": {
+        "description": "Forbidden"
+      },
+      "404": {
+        "description": "Pet not found\n\nOwner not found",
+        "content": {
+          "application/json": {
+            "schema": {
+              "oneOf": [
+                { "$ref": "#/components/schemas/NotFoundBody" },
+                { "$ref": "#/components/schemas/ErrorBody" }
+              ]
+            }
+          }
+        }
+      },
+      "409": {
+        "description": "Already exists",
+        "content": {
+          "application/json": {
+            "schema": { "$ref": "#/components/schemas/ErrorBody" }
+          }
+        }
+      }
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_component_with_problem_details_variants() {
+  #[derive(JsonSchema, ApiComponent)]
+  struct PetConflict {
+    pet_id: u32,
+  }
+
+  #[derive(JsonSchema, ApiComponent)]
+  struct OwnerConflict {
+    owner_id: u32,
+  }
+
+  #[derive(ApiErrorComponent)]
+  #[openapi_error(problem_details)]
+  enum ErrorResponse {
+    #[openapi_error(code = 409, extension = PetConflict)]
+    PetConflict(u32),
+    #[openapi_error(code = 409, extension = OwnerConflict)]
+    OwnerConflict(u32),
+  }
+
+  assert!(<ErrorResponse as ApiErrorComponent>::schemas_by_status_code().is_empty());
+  let error_child_schemas = <ErrorResponse as ApiErrorComponent>::error_child_schemas();
+  assert_eq!(
+    error_child_schemas
+      .iter()
+      .map(|(code, schemas)| (
+        code.as_str(),
+        schemas.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>()
+      ))
+      .collect::<Vec<_>>(),
+    vec![("409", vec!["ProblemDetails", "PetConflict", "OwnerConflict"])]
+  );
+
+  let error_responses = BTreeMap::from_iter(<ErrorResponse as ApiErrorComponent>::error_responses());
+  assert_json_eq!(
+    serde_json::to_value(&error_responses).expect("Unable to serialize responses"),
+    json!({
+      "409": {
+        "description": "Conflict",
+        "content": {
+          "application/problem+json": {
+            "schema": {
+              "allOf": [
+                { "$ref": "#/components/schemas/ProblemDetails" },
+                {
+                  "oneOf": [
+                    { "$ref": "#/components/schemas/PetConflict" },
+                    { "$ref": "#/components/schemas/OwnerConflict" }
+                  ]
+                }
+              ]
+            }
+          }
+        }
+      }
+    })
+  );
+}
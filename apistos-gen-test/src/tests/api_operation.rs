@@ -2,15 +2,19 @@ use actix_multipart::form::MultipartForm;
 use actix_web::dev::ServiceRequest;
 use actix_web::http::header::ContentType;
 use actix_web::web::Json;
-use actix_web::{Error, HttpResponse, Responder};
+use actix_web::{Error, HttpResponse, Responder, ResponseError};
 use assert_json_diff::assert_json_eq;
 use schemars::_serde_json::json;
+use schemars::JsonSchema;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
 use apistos::actix::{AcceptedJson, CreatedJson, NoContent};
+use apistos::reference_or::ReferenceOr;
 use apistos_core::PathItemDefinition;
-use apistos_gen::api_operation;
+use apistos_gen::{api_operation, ApiComponent, ApiErrorComponent};
 
 #[allow(clippy::todo)]
 mod test_models {
@@ -1546,3 +1550,328 @@ fn api_operation_actix_web_grant() {
     })
   );
 }
+
+#[test]
+#[allow(dead_code)]
+fn api_operation_callbacks() {
+  /// Subscribe to pet events
+  #[api_operation(
+    callback(
+      name = "onPetEvent",
+      expression = "{$request.body#/test}",
+      method = "post",
+      body = test_models::Test,
+      response = test_models::TestResult
+    ),
+    callback(name = "onPetEvent", expression = "{$request.body#/test}", method = "delete"),
+    callback(name = "onPetsEvent", expression = "{$request.body#/test}", body = "Option<test_models::Test>")
+  )]
+  pub(crate) async fn test(_body: Json<test_models::Test>) -> Result<NoContent, Error> {
+    Ok(NoContent)
+  }
+
+  let components = __openapi_test::components();
+  let schemas = components
+    .first()
+    .map(|c| c.schemas.keys().cloned().collect::<Vec<_>>())
+    .unwrap_or_default();
+  assert_eq!(schemas, vec!["Test".to_string(), "TestResult".to_string()]);
+
+  let operation = __openapi_test::operation();
+  let callbacks = serde_json::to_value(operation.callbacks).expect("Unable to serialize as Json");
+
+  assert_json_eq!(
+    callbacks,
+    json!({
+      "onPetEvent": {
+        "{$request.body#/test}": {
+          "post": {
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "$ref": "#/components/schemas/Test"
+                  }
+                }
+              },
+              "required": true
+            },
+            "responses": {
+              "200": {
+                "content": {
+                  "application/json": {
+                    "schema": {
+                      "$ref": "#/components/schemas/TestResult"
+                    }
+                  }
+                },
+                "description": ""
+              }
+            }
+          },
+          "delete": {
+            "responses": {
+              "200": {
+                "description": ""
+              }
+            }
+          }
+        }
+      },
+      "onPetsEvent": {
+        "{$request.body#/test}": {
+          "post": {
+            "requestBody": {
+              "content": {
+                "application/json": {
+                  "schema": {
+                    "$ref": "#/components/schemas/Test"
+                  }
+                }
+              },
+              "required": false
+            },
+            "responses": {
+              "200": {
+                "description": ""
+              }
+            }
+          }
+        }
+      }
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_operation_callbacks_schemas_overridden_by_error_schemas() {
+  mod callback_models {
+    use apistos_gen::ApiComponent;
+    use schemars::JsonSchema;
+
+    #[derive(JsonSchema, ApiComponent)]
+    pub(crate) struct ProblemDetails {
+      pub(crate) event: String,
+    }
+  }
+
+  #[derive(Debug, ApiErrorComponent)]
+  #[openapi_error(problem_details, status(code = 404))]
+  pub(crate) enum ErrorResponse {
+    NotFound,
+  }
+
+  impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{self:?}")
+    }
+  }
+
+  impl ResponseError for ErrorResponse {}
+
+  #[api_operation(callback(
+    name = "onPetEvent",
+    expression = "{$request.body#/test}",
+    body = callback_models::ProblemDetails
+  ))]
+  pub(crate) async fn test() -> Result<NoContent, ErrorResponse> {
+    Ok(NoContent)
+  }
+
+  let components = serde_json::to_value(__openapi_test::components()).expect("Unable to serialize as Json");
+  let properties = components[0]["schemas"]["ProblemDetails"]["properties"]
+    .as_object()
+    .expect("Missing ProblemDetails properties");
+  assert!(properties.contains_key("status"));
+  assert!(!properties.contains_key("event"));
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_operation_links() {
+  #[api_operation(operation_id = "get_test")]
+  pub(crate) async fn get_test() -> Result<Json<test_models::TestResult>, Error> {
+    Ok(Json(test_models::TestResult { id: 0 }))
+  }
+
+  #[api_operation(
+    link(
+      name = "GetTest",
+      operation_id = "get_test",
+      parameter(name = "id", expression = "$response.body#/id"),
+      description = "Retrieve the created test"
+    ),
+    link(name = "GetTestByHandler", operation = get_test, status = 200, request_body = "$request.body"),
+    link(name = "NotFound", operation_id = "get_test", status = 404)
+  )]
+  pub(crate) async fn test(_body: Json<test_models::Test>) -> Result<Json<test_models::TestResult>, Error> {
+    Ok(Json(test_models::TestResult { id: 0 }))
+  }
+
+  fn handler_id<H: 'static>(_handler: &H) -> std::any::TypeId {
+    std::any::TypeId::of::<H>()
+  }
+
+  let operation = __openapi_test::operation();
+  let response = operation
+    .responses
+    .responses
+    .get("200")
+    .cloned()
+    .and_then(ReferenceOr::get_object)
+    .expect("Missing 200 response");
+  let handler_link = response
+    .links
+    .get("GetTestByHandler")
+    .cloned()
+    .and_then(ReferenceOr::get_object)
+    .expect("Missing GetTestByHandler link");
+  assert_eq!(handler_link.operation_identifier, None);
+
+  assert_eq!(__openapi_get_test::handler_name(), Some("get_test"));
+  let handler_links = __openapi_test::handler_links();
+  assert_eq!(handler_links.len(), 1);
+  assert_eq!(handler_links[0].name, "GetTestByHandler");
+  assert_eq!(handler_links[0].handler, "get_test");
+  assert_eq!(handler_links[0].handler_id, handler_id(&get_test));
+
+  let links = serde_json::to_value(response.links).expect("Unable to serialize as Json");
+
+  assert_json_eq!(
+    links,
+    json!({
+      "GetTest": {
+        "operationId": "get_test",
+        "parameters": {
+          "id": "$response.body#/id"
+        },
+        "description": "Retrieve the created test"
+      },
+      "GetTestByHandler": {
+        "requestBody": "$request.body"
+      }
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_operation_error_content() {
+  #[derive(Serialize, JsonSchema, ApiComponent)]
+  pub(crate) struct ErrorBody {
+    pub(crate) message: String,
+  }
+
+  #[derive(Debug, ApiErrorComponent)]
+  #[openapi_error(status(code = 404, description = "Test not found", content = ErrorBody))]
+  pub(crate) enum ErrorResponse {
+    NotFound(String),
+  }
+
+  impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{self:?}")
+    }
+  }
+
+  impl ResponseError for ErrorResponse {}
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn test() -> Result<Json<test_models::TestResult>, ErrorResponse> {
+    Ok(Json(test_models::TestResult { id: 1 }))
+  }
+
+  let components = serde_json::to_value(__openapi_test::components()).expect("Unable to serialize as Json");
+  assert_json_eq!(
+    components[0]["schemas"]["ErrorBody"],
+    json!({
+      "properties": {
+        "message": {
+          "type": "string"
+        }
+      },
+      "required": [
+        "message"
+      ],
+      "title": "ErrorBody",
+      "type": "object"
+    })
+  );
+
+  let operation = serde_json::to_value(__openapi_test::operation()).expect("Unable to serialize as Json");
+  assert_json_eq!(
+    operation["responses"]["404"],
+    json!({
+      "content": {
+        "application/json": {
+          "schema": {
+            "$ref": "#/components/schemas/ErrorBody"
+          }
+        }
+      },
+      "description": "Test not found"
+    })
+  );
+}
+
+#[test]
+#[allow(dead_code)]
+fn api_operation_error_code_content() {
+  #[derive(Serialize, JsonSchema, ApiComponent)]
+  pub(crate) struct NotFoundBody {
+    pub(crate) id: u32,
+  }
+
+  #[derive(Serialize, JsonSchema, ApiComponent)]
+  pub(crate) struct PetConflict {
+    pub(crate) pet_id: u32,
+  }
+
+  #[derive(Serialize, JsonSchema, ApiComponent)]
+  pub(crate) struct OwnerConflict {
+    pub(crate) owner_id: u32,
+  }
+
+  #[derive(Debug, ApiErrorComponent)]
+  #[openapi_error(
+    status(code = 404, description = "Test not found", content = NotFoundBody),
+    status(code = 409, description = "Pet conflict", content = PetConflict),
+    status(code = 409, description = "Owner conflict", content = OwnerConflict)
+  )]
+  pub(crate) enum ErrorResponse {
+    NotFound(u32),
+    Conflict(u32),
+  }
+
+  impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{self:?}")
+    }
+  }
+
+  impl ResponseError for ErrorResponse {}
+
+  #[api_operation(tag = "pet", error_code = 404)]
+  pub(crate) async fn test() -> Result<Json<test_models::TestResult>, ErrorResponse> {
+    Ok(Json(test_models::TestResult { id: 1 }))
+  }
+
+  let components = serde_json::to_value(__openapi_test::components()).expect("Unable to serialize as Json");
+  let schemas = components[0]["schemas"]
+    .as_object()
+    .expect("Missing schemas")
+    .keys()
+    .cloned()
+    .collect::<Vec<_>>();
+  assert_eq!(schemas, vec!["NotFoundBody", "TestResult"]);
+
+  let operation = serde_json::to_value(__openapi_test::operation()).expect("Unable to serialize as Json");
+  let responses = operation["responses"]
+    .as_object()
+    .expect("Missing responses")
+    .keys()
+    .cloned()
+    .collect::<Vec<_>>();
+  assert_eq!(responses, vec!["200", "404"]);
+}
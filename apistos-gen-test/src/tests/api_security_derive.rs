@@ -159,3 +159,25 @@ fn api_security_derive_openid_connect() {
     })
   );
 }
+
+#[test]
+#[allow(dead_code)]
+fn api_security_derive_mutual_tls() {
+  #[derive(ApiSecurity)]
+  #[openapi_security(scheme(security_type(mutual_tls), description = "A client certificate"))]
+  struct ClientCertificate;
+
+  let securities = ClientCertificate::securities();
+  assert_eq!(securities.len(), 1);
+  let security_scheme = securities
+    .get("client_certificate")
+    .expect("Unable to find security scheme");
+  let json = serde_json::to_value(security_scheme).expect("Unable to serialize as Json");
+  assert_json_eq!(
+    json,
+    json!({
+      "description": "A client certificate",
+      "type": "mutualTLS"
+    })
+  );
+}
@@ -0,0 +1,56 @@
+use crate::operation_attr::CallbackAttr;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+pub(crate) struct Callbacks<'a> {
+  pub(crate) callbacks: &'a [CallbackAttr],
+}
+
+impl<'a> ToTokens for Callbacks<'a> {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let callbacks = self.callbacks.iter().map(|callback| {
+      let name = &callback.name;
+      let expression = &callback.expression;
+      let method = &callback.method;
+      let request_body = callback.body.as_ref().map(|body| {
+        quote!(callback_operation.request_body = <#body>::request_body().map(apistos::reference_or::ReferenceOr::Object);)
+      });
+      let responses = match &callback.response {
+        Some(response) => quote!(apistos::component_responses::<#response>(None)),
+        None => quote! {
+          apistos::paths::Responses {
+            responses: std::collections::BTreeMap::from_iter(vec![(
+              "200".to_string(),
+              apistos::reference_or::ReferenceOr::Object(apistos::paths::Response::default()),
+            )]),
+            ..Default::default()
+          }
+        },
+      };
+      quote! {
+        let mut callback_operation = apistos::paths::Operation::default();
+        #request_body
+        callback_operation.responses = #responses;
+        callbacks
+          .entry(#name.to_string())
+          .or_default()
+          .callbacks
+          .entry(#expression.to_string())
+          .or_default()
+          .operations
+          .insert(#method, callback_operation);
+      }
+    });
+
+    tokens.extend(quote! {
+      {
+        let mut callbacks: std::collections::BTreeMap<String, apistos::paths::Callback> = Default::default();
+        #(#callbacks)*
+        callbacks
+          .into_iter()
+          .map(|(name, callback)| (name, apistos::reference_or::ReferenceOr::Object(callback)))
+          .collect::<std::collections::BTreeMap<String, apistos::reference_or::ReferenceOr<apistos::paths::Callback>>>()
+      }
+    });
+  }
+}
@@ -1,3 +1,4 @@
+use crate::operation_attr::CallbackAttr;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::Type;
@@ -6,6 +7,7 @@ pub(crate) struct Components<'a> {
   pub(crate) args: &'a [Type],
   pub(crate) responder_wrapper: &'a TokenStream,
   pub(crate) error_codes: &'a [u16],
+  pub(crate) callbacks: &'a [CallbackAttr],
 }
 
 impl<'a> ToTokens for Components<'a> {
@@ -13,19 +15,32 @@ impl<'a> ToTokens for Components<'a> {
     let args = self.args;
     let responder_wrapper = self.responder_wrapper;
     let error_codes_filter = if self.error_codes.is_empty() {
-      quote!()
+      quote!(let is_documented_status = |_status: &str| true;)
     } else {
       let error_codes = self.error_codes;
       quote! {
         let available_error_codes = [#(#error_codes,)*];
-        for (status, s) in error_schemas {
-          let status = status.parse::<u16>();
-          if let Ok(status) = status {
-            if status >= 400 &&available_error_codes.contains(&status) {
-              schemas.push(s);
-            }
-          }
-        }
+        let is_documented_status = |status: &str| match status.parse::<u16>() {
+          Ok(status) if status >= 400 => available_error_codes.contains(&status),
+          _ => true,
+        };
+      }
+    };
+    let callback_types = self
+      .callbacks
+      .iter()
+      .flat_map(|callback| callback.body.iter().chain(callback.response.iter()))
+      .collect::<Vec<_>>();
+    let callback_schemas = if callback_types.is_empty() {
+      quote!()
+    } else {
+      quote! {
+        #(
+          schemas.extend(<#callback_types>::schema());
+          schemas.append(&mut <#callback_types>::child_schemas());
+          schemas.extend(<#callback_types>::error_schemas().into_values());
+          schemas.extend(<#callback_types>::error_child_schemas().into_values().flatten());
+        )*
       }
     };
     tokens.extend(quote!(
@@ -47,12 +62,26 @@ impl<'a> ToTokens for Components<'a> {
         )*
         schemas.push(<#responder_wrapper>::schema());
         let mut schemas = schemas.into_iter().flatten().collect::<Vec<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)>>();
+        // error schemas of the statuses excluded by `error_code` are not registered
+        #error_codes_filter
+        schemas.extend(
+          <#responder_wrapper>::error_schemas()
+            .into_iter()
+            .filter(|(status, _)| is_documented_status(status))
+            .map(|(_, schema)| schema),
+        );
         #(
           schemas.append(&mut <#args>::child_schemas());
         )*
         schemas.append(&mut <#responder_wrapper>::child_schemas());
-        let error_schemas = <#responder_wrapper>::error_schemas();
-        #error_codes_filter
+        #callback_schemas
+        // error child schemas come last so that they take precedence over inlined schemas, e.g. arrays, sharing their name
+        schemas.extend(
+          <#responder_wrapper>::error_child_schemas()
+            .into_iter()
+            .filter(|(status, _)| is_documented_status(status))
+            .flat_map(|(_, schemas)| schemas),
+        );
         component_builder.schemas = std::collections::BTreeMap::from_iter(schemas);
         vec![component_builder]
       }
@@ -0,0 +1,102 @@
+use crate::operation_attr::LinkAttr;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+pub(crate) struct Links<'a> {
+  pub(crate) links: &'a [LinkAttr],
+}
+
+impl<'a> ToTokens for Links<'a> {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let links = self.links.iter().map(|link| {
+      let name = &link.name;
+      let operation_identifier = match &link.operation_id {
+        Some(operation_id) => {
+          quote!(Some(apistos::paths::OperationIdentifier::OperationId(#operation_id.to_string())))
+        }
+        None => quote!(None),
+      };
+      let parameters = link.parameters.iter().map(|parameter| {
+        let name = &parameter.name;
+        let expression = &parameter.expression;
+        quote!((#name.to_string(), apistos::paths::AnyOrExpression::Expression(#expression.to_string())))
+      });
+      let request_body = match &link.request_body {
+        Some(request_body) => quote!(Some(apistos::paths::AnyOrExpression::Expression(#request_body.to_string()))),
+        None => quote!(None),
+      };
+      let description = match &link.description {
+        Some(description) => quote!(Some(#description.to_string())),
+        None => quote!(None),
+      };
+      let status_filter = match link.status {
+        Some(status) => quote!(status.parse::<u16>().ok() == Some(#status)),
+        None => quote!(status.starts_with('2')),
+      };
+      quote! {
+        let link = apistos::paths::Link {
+          operation_identifier: #operation_identifier,
+          parameters: std::collections::BTreeMap::from_iter(vec![#(#parameters,)*]),
+          request_body: #request_body,
+          description: #description,
+          ..Default::default()
+        };
+        for (status, response) in operation_builder.responses.responses.iter_mut() {
+          if let apistos::reference_or::ReferenceOr::Object(response) = response {
+            if #status_filter {
+              response
+                .links
+                .insert(#name.to_string(), apistos::reference_or::ReferenceOr::Object(link.clone()));
+            }
+          }
+        }
+      }
+    });
+
+    tokens.extend(quote!(#(#links)*));
+  }
+}
+
+/// Handlers targeted by links declared with `operation = handler`, resolved to their `operationId` once the document is built.
+pub(crate) struct HandlerLinks<'a> {
+  pub(crate) links: &'a [LinkAttr],
+}
+
+impl<'a> ToTokens for HandlerLinks<'a> {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let handler_links = self
+      .links
+      .iter()
+      .filter_map(|link| link.operation.as_ref().map(|handler| (&link.name, handler)))
+      .map(|(name, handler)| {
+        let handler_path = &handler.0;
+        let handler_name = handler_path
+          .segments
+          .last()
+          .map(|segment| segment.ident.to_string())
+          .unwrap_or_default();
+        // referencing the struct generated for the target handler fails to compile if the handler is not documented
+        let openapi_struct = handler.openapi_struct();
+        quote!({
+          let _: std::marker::PhantomData<#openapi_struct> = std::marker::PhantomData;
+          apistos::HandlerLink {
+            name: #name.to_string(),
+            handler: #handler_name,
+            handler_id: handler_id(&#handler_path),
+          }
+        })
+      })
+      .collect::<Vec<_>>();
+    if handler_links.is_empty() {
+      return;
+    }
+    tokens.extend(quote!(
+      fn handler_links() -> Vec<apistos::HandlerLink> {
+        fn handler_id<H: 'static>(_handler: &H) -> std::any::TypeId {
+          std::any::TypeId::of::<H>()
+        }
+        vec![#(#handler_links,)*]
+      }
+    ));
+  }
+}
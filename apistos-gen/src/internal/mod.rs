@@ -1,4 +1,5 @@
 use crate::internal::components::Components;
+use crate::internal::links::HandlerLinks;
 use crate::internal::operation::Operation;
 use crate::operation_attr::OperationAttr;
 use proc_macro2::{Span, TokenStream as TokenStream2};
@@ -10,7 +11,9 @@ use syn::{
   WhereClause,
 };
 
+mod callbacks;
 mod components;
+mod links;
 mod operation;
 
 pub(crate) mod schemas;
@@ -92,11 +95,19 @@ pub(crate) fn gen_open_api_impl(
       error_codes: &operation_attribute.error_codes,
       consumes: operation_attribute.consumes.as_ref(),
       produces: operation_attribute.produces.as_ref(),
+      callbacks: &operation_attribute.callbacks,
+      links: &operation_attribute.links,
     };
     let components = Components {
       args: &args,
       responder_wrapper,
       error_codes: &operation_attribute.error_codes,
+      callbacks: &operation_attribute.callbacks,
+    };
+
+    let handler_name = item_ast.sig.ident.to_string();
+    let handler_links = HandlerLinks {
+      links: &operation_attribute.links,
     };
 
     quote!(
@@ -105,6 +116,11 @@ pub(crate) fn gen_open_api_impl(
       }
       #operation
       #components
+
+      fn handler_name() -> Option<&'static str> {
+        Some(#handler_name)
+      }
+      #handler_links
     )
   };
 
@@ -1,4 +1,7 @@
+use crate::internal::callbacks::Callbacks;
+use crate::internal::links::Links;
 use crate::internal::security::Security;
+use crate::operation_attr::{CallbackAttr, LinkAttr};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use std::collections::BTreeMap;
@@ -16,6 +19,8 @@ pub(crate) struct Operation<'a> {
   pub(crate) error_codes: &'a [u16],
   pub(crate) consumes: Option<&'a String>,
   pub(crate) produces: Option<&'a String>,
+  pub(crate) callbacks: &'a [CallbackAttr],
+  pub(crate) links: &'a [LinkAttr],
 }
 
 impl<'a> ToTokens for Operation<'a> {
@@ -94,6 +99,15 @@ impl<'a> ToTokens for Operation<'a> {
     } else {
       quote!(None)
     };
+    let callbacks = if self.callbacks.is_empty() {
+      quote!()
+    } else {
+      let callbacks = Callbacks {
+        callbacks: self.callbacks,
+      };
+      quote!(operation_builder.callbacks = #callbacks;)
+    };
+    let links = Links { links: self.links };
     tokens.extend(quote!(
       fn operation() -> apistos::paths::Operation {
         use apistos::ApiComponent;
@@ -133,6 +147,8 @@ impl<'a> ToTokens for Operation<'a> {
           operation_builder.responses = responses;
         }
 
+        #links
+
         let securities = {
           #security
         };
@@ -140,6 +156,8 @@ impl<'a> ToTokens for Operation<'a> {
           operation_builder.security = securities;
         }
 
+        #callbacks
+
         operation_builder.operation_id = #operation_id;
 
         operation_builder.deprecated = #deprecated;
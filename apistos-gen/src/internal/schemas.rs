@@ -57,12 +57,10 @@ impl ToTokens for Schemas {
 
     tokens.extend(quote! {
       fn child_schemas() -> Vec<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)> {
-        let settings = schemars::gen::SchemaSettings::openapi3();
-        let mut gen = settings.into_generator();
-        let schema: apistos::RootSchema = gen.into_root_schema_for::<Self>();
+        let schema = apistos::root_schema_for::<Self>();
 
         let mut schemas: Vec<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)> = vec![];
-        for (def_name, mut def) in schema.definitions {
+        for (def_name, mut def) in schema.definitions.clone() {
           match &mut def {
             schemars::schema::Schema::Bool(_) => {}
             schemars::schema::Schema::Object(schema) => {
@@ -79,9 +77,7 @@ impl ToTokens for Schemas {
       fn schema() -> Option<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)> {
         let (name, schema) = {
           let schema_name = <Self as schemars::JsonSchema>::schema_name();
-          let settings = schemars::gen::SchemaSettings::openapi3();
-          let mut gen = settings.into_generator();
-          let mut schema: apistos::RootSchema = gen.into_root_schema_for::<Self>();
+          let mut schema: apistos::RootSchema = apistos::root_schema_for::<Self>().as_ref().clone();
           if let Some(one_of) = schema.schema.subschemas.as_mut().and_then(|s| s.one_of.as_mut()) {
             #update_one_of_title
           }
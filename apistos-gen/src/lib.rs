@@ -6,7 +6,7 @@ use crate::internal::schemas::Schemas;
 use crate::internal::utils::extract_deprecated_from_attr;
 use crate::internal::{gen_item_ast, gen_open_api_impl};
 use crate::openapi_cookie_attr::parse_openapi_cookie_attrs;
-use crate::openapi_error_attr::parse_openapi_error_attrs;
+use crate::openapi_error_attr::{parse_openapi_error_attrs, ErrorDefinition, OpenapiErrorAttribute};
 use crate::openapi_header_attr::parse_openapi_header_attrs;
 use crate::openapi_security_attr::parse_openapi_security_attrs;
 use crate::operation_attr::parse_openapi_operation_attrs;
@@ -17,7 +17,7 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::{abort, proc_macro_error, OptionExt};
 use quote::{format_ident, quote};
-use syn::{DeriveInput, GenericParam, Ident, ItemFn};
+use syn::{Data, DataEnum, DeriveInput, GenericParam, Ident, ItemFn};
 
 mod internal;
 mod openapi_cookie_attr;
@@ -181,6 +181,7 @@ pub fn derive_api_component(input: TokenStream) -> TokenStream {
 ///       - `scheme = "..."` a **required** parameter
 ///       - `bearer_format = "..."` a **required** parameter
 ///     - `open_id_connect(open_id_connect_url = "...")`
+///     - `mutual_tls`, only supported by OAS 3.1
 ///
 /// # Examples:
 ///
@@ -224,6 +225,15 @@ pub fn derive_api_component(input: TokenStream) -> TokenStream {
 /// #[openapi_security(scheme(security_type(open_id_connect(open_id_connect_url = "https://connect.com"))))]
 /// pub struct ApiKey;
 /// ```
+///
+/// ## **mutual_tls**
+/// ```rust
+/// use apistos::ApiSecurity;
+///
+/// #[derive(ApiSecurity)]
+/// #[openapi_security(scheme(security_type(mutual_tls)))]
+/// pub struct ClientCertificate;
+/// ```
 #[proc_macro_error]
 #[proc_macro_derive(ApiSecurity, attributes(openapi_security))]
 pub fn derive_api_security(input: TokenStream) -> TokenStream {
@@ -408,9 +418,84 @@ pub fn derive_api_cookie(input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// # `#[openapi_error(...)]` options:
+/// - `problem_details` an optional flag documenting an [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) `application/problem+json` body for each status, referencing the shared `ProblemDetails` schema
 /// - `status(...)` a list of possible error status with
 ///   - `code = 000` a **required** http status code
 ///   - `description = "..."` an optional description, default is the canonical reason of the given status code
+///   - `extension = Type` an optional type implementing `ApiComponent` whose fields extend the problem details of this status, only allowed along with `problem_details`
+///   - `content = Type` an optional type implementing `ApiComponent` documenting the body of this status, registered in the document components. Takes precedence over `problem_details` for this status
+///   - `content_type = "..."` an optional content type for this status, default is the content type of `content`
+///
+/// ```rust
+/// use apistos::{ApiComponent, ApiErrorComponent};
+/// use schemars::JsonSchema;
+///
+/// #[derive(JsonSchema, ApiComponent)]
+/// pub struct ErrorBody {
+///   message: String,
+/// }
+///
+/// #[derive(Clone, ApiErrorComponent)]
+/// #[openapi_error(
+///   status(code = 404, description = "Pet not found", content = ErrorBody),
+///   status(code = 406, content_type = "text/plain")
+/// )]
+/// pub enum ErrorResponse {
+///   NotFound(String),
+///   NotAcceptable,
+/// }
+/// ```
+///
+/// ```rust
+/// use apistos::{ApiComponent, ApiErrorComponent};
+/// use schemars::JsonSchema;
+///
+/// #[derive(JsonSchema, ApiComponent)]
+/// pub struct ConflictDetails {
+///   conflicting_id: u32,
+/// }
+///
+/// #[derive(Clone, ApiErrorComponent)]
+/// #[openapi_error(
+///   problem_details,
+///   status(code = 404),
+///   status(code = 409, extension = ConflictDetails)
+/// )]
+/// pub enum ErrorResponse {
+///   NotFound,
+///   Conflict(u32),
+/// }
+/// ```
+///
+/// Error enums can document their statuses on each variant instead, with `#[openapi_error(...)]` accepting the same options as `status(...)`.
+/// Variants sharing a status are documented by a single response, their descriptions being merged and their `content` referenced through a `oneOf` schema.
+/// Type level options such as `problem_details` still apply to variants statuses.
+///
+/// ```rust
+/// use apistos::{ApiComponent, ApiErrorComponent};
+/// use schemars::JsonSchema;
+///
+/// #[derive(JsonSchema, ApiComponent)]
+/// pub struct NotFoundBody {
+///   id: u32,
+/// }
+///
+/// #[derive(JsonSchema, ApiComponent)]
+/// pub struct ErrorBody {
+///   message: String,
+/// }
+///
+/// #[derive(Clone, ApiErrorComponent)]
+/// pub enum ErrorResponse {
+///   #[openapi_error(code = 404, description = "Pet not found", content = NotFoundBody)]
+///   PetNotFound(u32),
+///   #[openapi_error(code = 404, description = "Owner not found", content = ErrorBody)]
+///   OwnerNotFound(String),
+///   #[openapi_error(code = 409)]
+///   Conflict,
+///   Unexpected,
+/// }
+/// ```
 #[proc_macro_error]
 #[proc_macro_derive(ApiErrorComponent, attributes(openapi_error))]
 pub fn derive_api_error(input: TokenStream) -> TokenStream {
@@ -418,14 +503,24 @@ pub fn derive_api_error(input: TokenStream) -> TokenStream {
   let DeriveInput {
     attrs,
     ident,
-    data: _data,
+    data,
     generics,
     vis: _vis,
   } = input;
 
-  let openapi_error_attributes = parse_openapi_error_attrs(&attrs).expect_or_abort(
-    "expected #[openapi_error(...)] attribute to be present when used with ApiErrorComponent derive trait",
-  );
+  let variants_statuses = match data {
+    Data::Enum(DataEnum { variants, .. }) => variants
+      .iter()
+      .filter_map(|variant| parse_openapi_error_attrs::<ErrorDefinition>(&variant.attrs))
+      .collect::<Vec<_>>(),
+    _ => vec![],
+  };
+  let mut openapi_error_attributes = parse_openapi_error_attrs::<OpenapiErrorAttribute>(&attrs)
+    .or_else(|| (!variants_statuses.is_empty()).then(OpenapiErrorAttribute::default))
+    .expect_or_abort(
+      "expected #[openapi_error(...)] attribute to be present on the type or its variants when used with ApiErrorComponent derive trait",
+    );
+  openapi_error_attributes.status.extend(variants_statuses);
 
   let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
   quote!(
@@ -506,6 +601,21 @@ pub fn derive_api_error(input: TokenStream) -> TokenStream {
 ///   - `error_code = 00` an optional list of error codes to document only theses
 ///   - `consumes = "..."` allow to override body content type
 ///   - `produces = "..."` allow to override response content type
+///   - `callback(...)` an optional list of out-of-band requests the operation may initiate with
+///       - `name = "..."` a **required** name identifying the callback
+///       - `expression = "..."` a **required** runtime expression identifying the callback url, for example `{$request.body#/callbackUrl}`
+///       - `method = "..."` the http method of the callback request, default to `post`
+///       - `body = Type` an optional type implementing `ApiComponent` describing the callback request body
+///       - `response = Type` an optional type implementing `ApiComponent` describing the response expected from the callback. Types with generics should be provided as a string, for example `response = "Vec<Ack>"`
+///   - `link(...)` an optional list of links from this operation responses to other operations with
+///       - `name = "..."` a **required** name identifying the link
+///       - `operation_id = "..."` the operation id of the target operation
+///       - `operation = handler` a path to the target handler, which must be decorated with `#[api_operation]`. The link
+///  is resolved to the target operation id once the documentation is built, it is removed and reported according to the document validation level if the handler is not registered or is registered by several operations, e.g. at several paths. Exactly one of `operation_id` or `operation` is required
+///       - `parameter(...)` an optional list of parameters passed to the target operation, each with a `name = "..."` and a runtime `expression = "..."`, for example `$response.body#/id`
+///       - `request_body = "..."` an optional runtime expression used as the target operation request body
+///       - `description = "..."` an optional description
+///       - `status = 000` the response status the link is attached to, default to every documented 2xx response
 ///
 /// If `summary` or `description` are not provided, a default value will be extracted from the comments. The first line will be used as summary while the rest will be part of the description.
 ///
@@ -1,3 +1,4 @@
+use crate::operation_attr::TypeAttr;
 use actix_web::http::StatusCode;
 use darling::FromMeta;
 use proc_macro2::{Span, TokenStream};
@@ -5,12 +6,12 @@ use proc_macro_error::abort;
 use quote::{quote, ToTokens};
 use syn::Attribute;
 
-pub(crate) fn parse_openapi_error_attrs(attrs: &[Attribute]) -> Option<OpenapiErrorAttribute> {
+pub(crate) fn parse_openapi_error_attrs<T: FromMeta + Clone>(attrs: &[Attribute]) -> Option<T> {
   let error_attribute = attrs
     .iter()
     .filter(|attribute| attribute.path().is_ident("openapi_error"))
-    .map(|attribute| OpenapiErrorAttribute::from_meta(&attribute.meta))
-    .collect::<darling::Result<Vec<OpenapiErrorAttribute>>>();
+    .map(|attribute| T::from_meta(&attribute.meta))
+    .collect::<darling::Result<Vec<T>>>();
 
   match error_attribute {
     Ok(error_attributes) if error_attributes.len() > 1 => {
@@ -21,15 +22,52 @@ pub(crate) fn parse_openapi_error_attrs(attrs: &[Attribute]) -> Option<OpenapiEr
   }
 }
 
-#[derive(FromMeta, Clone)]
+#[derive(FromMeta, Clone, Default)]
 pub(crate) struct OpenapiErrorAttribute {
+  #[darling(default)]
+  pub(crate) problem_details: bool,
   #[darling(multiple)]
   pub(crate) status: Vec<ErrorDefinition>,
 }
 
 impl ToTokens for OpenapiErrorAttribute {
   fn to_tokens(&self, tokens: &mut TokenStream) {
-    let defs = &self.status;
+    let mut codes: Vec<u16> = vec![];
+    for definition in &self.status {
+      if !codes.contains(&definition.code) {
+        codes.push(definition.code);
+      }
+    }
+    let defs = codes
+      .into_iter()
+      .map(|code| ErrorResponse {
+        code,
+        definitions: self
+          .status
+          .iter()
+          .filter(|definition| definition.code == code)
+          .collect(),
+        problem_details: self.problem_details,
+      })
+      .collect::<Vec<_>>();
+    let has_child_schemas = self.problem_details
+      || self
+        .status
+        .iter()
+        .any(|definition| definition.content.is_some() || definition.extension.is_some());
+    let error_child_schemas = if has_child_schemas {
+      let child_schemas = defs.iter().map(|def| def.child_schemas()).collect::<Vec<_>>();
+      quote! {
+        fn error_child_schemas() -> std::collections::BTreeMap<String, Vec<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)>> {
+          [#(#child_schemas,)*]
+            .into_iter()
+            .filter(|(_, schemas)| !schemas.is_empty())
+            .collect()
+        }
+      }
+    } else {
+      quote!()
+    };
     tokens.extend(quote! {
       fn error_responses() -> Vec<(String, apistos::paths::Response)> {
         let responses: Vec<((String, apistos::paths::Response), Option<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)>)> = vec![#(#defs,)*];
@@ -45,6 +83,8 @@ impl ToTokens for OpenapiErrorAttribute {
         }
         schemas
       }
+
+      #error_child_schemas
     })
   }
 }
@@ -53,21 +93,213 @@ impl ToTokens for OpenapiErrorAttribute {
 pub(crate) struct ErrorDefinition {
   pub(crate) code: u16,
   pub(crate) description: Option<String>,
+  pub(crate) extension: Option<TypeAttr>,
+  pub(crate) content: Option<TypeAttr>,
+  pub(crate) content_type: Option<String>,
+}
+
+/// Error definitions sharing the same status code, documented as a single response.
+struct ErrorResponse<'a> {
+  code: u16,
+  definitions: Vec<&'a ErrorDefinition>,
+  problem_details: bool,
+}
+
+impl<'a> ErrorResponse<'a> {
+  fn contents(&self) -> Vec<&'a TypeAttr> {
+    distinct_types(
+      self
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.content.as_ref()),
+    )
+  }
+
+  fn extensions(&self) -> Vec<&'a TypeAttr> {
+    distinct_types(
+      self
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.extension.as_ref()),
+    )
+  }
+
+  /// Types documented through a `oneOf` schema, which are not registered by `schemas_by_status_code`
+  fn one_of_types(&self) -> Vec<&'a TypeAttr> {
+    let contents = self.contents();
+    let extensions = self.extensions();
+    if contents.len() > 1 {
+      contents
+    } else if extensions.len() > 1 {
+      extensions
+    } else {
+      vec![]
+    }
+  }
+}
+
+impl<'a> ErrorResponse<'a> {
+  /// Schemas this response depends on besides the one returned by `schemas_by_status_code`, along with its status code
+  fn child_schemas(&self) -> TokenStream {
+    let code = self.code;
+    let problem_details_schema = if self.problem_details {
+      quote!(schemas.extend(<apistos::problem::ProblemDetails as apistos::ApiComponent>::schema());)
+    } else {
+      quote!()
+    };
+    let one_of_types = self.one_of_types();
+    let types = distinct_types(
+      self
+        .definitions
+        .iter()
+        .filter_map(|definition| definition.content.as_ref().or(definition.extension.as_ref())),
+    );
+    quote! {
+      {
+        let mut schemas: Vec<(String, apistos::reference_or::ReferenceOr<apistos::Schema>)> = vec![];
+        #problem_details_schema
+        #(
+          schemas.extend(<#one_of_types as apistos::ApiComponent>::schema());
+        )*
+        #(
+          schemas.append(&mut <#types as apistos::ApiComponent>::child_schemas());
+        )*
+        (#code.to_string(), schemas)
+      }
+    }
+  }
+}
+
+fn distinct_types<'a>(types: impl Iterator<Item = &'a TypeAttr>) -> Vec<&'a TypeAttr> {
+  let mut distinct: Vec<&'a TypeAttr> = vec![];
+  for ty in types {
+    if !distinct
+      .iter()
+      .any(|existing| existing.to_token_stream().to_string() == ty.to_token_stream().to_string())
+    {
+      distinct.push(ty);
+    }
+  }
+  distinct
 }
 
-impl ToTokens for ErrorDefinition {
+impl<'a> ToTokens for ErrorResponse<'a> {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     let code = self.code;
     let default_description = match StatusCode::from_u16(code) {
       Ok(status_code) => status_code.canonical_reason().unwrap_or_default(),
       Err(e) => abort!(Span::call_site(), format!("{e}")),
     };
-    let description = self.description.as_deref().unwrap_or(default_description);
-    tokens.extend(quote! {
-      ((#code.to_string(), apistos::paths::Response {
-        description: #description.to_string(),
-        ..Default::default()
-      }), None)
-    });
+    let mut descriptions: Vec<&str> = vec![];
+    for description in self
+      .definitions
+      .iter()
+      .filter_map(|definition| definition.description.as_deref())
+    {
+      if !descriptions.contains(&description) {
+        descriptions.push(description);
+      }
+    }
+    let description = if descriptions.is_empty() {
+      default_description.to_string()
+    } else {
+      descriptions.join("\n\n")
+    };
+    let mut content_types: Vec<&str> = vec![];
+    for content_type in self
+      .definitions
+      .iter()
+      .filter_map(|definition| definition.content_type.as_deref())
+    {
+      if !content_types.contains(&content_type) {
+        content_types.push(content_type);
+      }
+    }
+    if content_types.len() > 1 {
+      abort!(
+        Span::call_site(),
+        format!("Conflicting `content_type` for status {code} in #[openapi_error]")
+      )
+    }
+    let content_type = match content_types.first() {
+      Some(content_type) => quote!(Some(#content_type.to_string())),
+      None => quote!(None),
+    };
+    let contents = self.contents();
+    let extensions = self.extensions();
+    match (contents.as_slice(), extensions.as_slice(), self.problem_details) {
+      ([_, ..], [_, ..], _) => abort!(
+        Span::call_site(),
+        "`content` and `extension` can't be used together in #[openapi_error] status"
+      ),
+      ([content], [], _) => tokens.extend(quote! {
+        (
+          (#code.to_string(), apistos::component_response::<#content>(#description.to_string(), #content_type)),
+          <#content as apistos::ApiComponent>::schema()
+        )
+      }),
+      ([first, ..], [], _) => tokens.extend(quote! {
+        {
+          let schemas = [#(<#contents as apistos::ApiComponent>::schema(),)*].into_iter().flatten().collect();
+          let content_type: Option<String> = #content_type;
+          ((#code.to_string(), apistos::paths::Response {
+            description: #description.to_string(),
+            content: std::collections::BTreeMap::from_iter([(
+              content_type.unwrap_or_else(<#first as apistos::ApiComponent>::content_type),
+              apistos::paths::MediaType {
+                schema: Some(apistos::one_of_schema(schemas)),
+                ..Default::default()
+              },
+            )]),
+            ..Default::default()
+          }), None)
+        }
+      }),
+      ([], [], false) => tokens.extend(quote! {
+        ((#code.to_string(), apistos::paths::Response {
+          description: #description.to_string(),
+          content: #content_type
+            .into_iter()
+            .map(|content_type: String| (content_type, apistos::paths::MediaType::default()))
+            .collect(),
+          ..Default::default()
+        }), None)
+      }),
+      ([], _, true) if !content_types.is_empty() => abort!(
+        Span::call_site(),
+        "`content_type` can't be used with `problem_details` unless `content` is set"
+      ),
+      ([], [], true) => tokens.extend(quote! {
+        (
+          (#code.to_string(), apistos::problem::problem_response(#description.to_string(), None)),
+          <apistos::problem::ProblemDetails as apistos::ApiComponent>::schema()
+        )
+      }),
+      ([], [extension], true) => tokens.extend(quote! {
+        {
+          let extension = <#extension as apistos::ApiComponent>::schema();
+          let extension_reference = extension.as_ref().map(|(name, _)| apistos::reference_or::ReferenceOr::Reference {
+            _ref: format!("#/components/schemas/{}", name),
+          });
+          (
+            (#code.to_string(), apistos::problem::problem_response(#description.to_string(), extension_reference)),
+            extension
+          )
+        }
+      }),
+      ([], [_, ..], true) => tokens.extend(quote! {
+        {
+          let extensions = [#(<#extensions as apistos::ApiComponent>::schema(),)*].into_iter().flatten().collect();
+          (
+            (#code.to_string(), apistos::problem::problem_response(#description.to_string(), Some(apistos::one_of_schema(extensions)))),
+            None
+          )
+        }
+      }),
+      ([], [_, ..], false) => abort!(
+        Span::call_site(),
+        "`extension` requires `problem_details` to be set on #[openapi_error]"
+      ),
+    }
   }
 }
@@ -88,6 +88,8 @@ pub(crate) enum SecurityType {
   ApiKey(ApiKey),
   Http(Http),
   OpenIdConnect(OpenIdConnect),
+  #[darling(rename = "mutual_tls")]
+  MutualTls,
 }
 
 impl ToTokens for SecurityType {
@@ -100,6 +102,7 @@ impl ToTokens for SecurityType {
       SecurityType::ApiKey(v) => quote!(ApiKey(#v)),
       SecurityType::Http(v) => quote!(Http(#v)),
       SecurityType::OpenIdConnect(v) => quote!(OpenIdConnect(#v)),
+      SecurityType::MutualTls => quote!(MutualTLS),
     };
     tokens.extend(quote! {
       apistos::security::SecurityType::#scheme_tokens
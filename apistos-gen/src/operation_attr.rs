@@ -1,8 +1,11 @@
+use crate::OPENAPI_STRUCT_PREFIX;
 use darling::ast::NestedMeta;
 use darling::FromMeta;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use proc_macro_error::abort;
+use quote::{quote, ToTokens};
 use std::collections::BTreeMap;
+use syn::{Expr, Path, Type};
 
 pub(crate) fn parse_openapi_operation_attrs(attrs: &[NestedMeta]) -> OperationAttr {
   match OperationAttrInternal::from_list(attrs) {
@@ -30,6 +33,10 @@ struct OperationAttrInternal {
   produces: Option<String>,
   #[darling(multiple)]
   skip_args: Vec<Ident>,
+  #[darling(multiple, rename = "callback")]
+  callbacks: Vec<CallbackAttr>,
+  #[darling(multiple, rename = "link")]
+  links: Vec<LinkAttr>,
 }
 
 #[derive(FromMeta, Clone)]
@@ -51,6 +58,136 @@ pub(crate) struct OperationAttr {
   pub(crate) consumes: Option<String>,
   pub(crate) produces: Option<String>,
   pub(crate) skip_args: Vec<Ident>,
+  pub(crate) callbacks: Vec<CallbackAttr>,
+  pub(crate) links: Vec<LinkAttr>,
+}
+
+#[derive(FromMeta, Clone)]
+pub(crate) struct CallbackAttr {
+  pub(crate) name: String,
+  pub(crate) expression: String,
+  #[darling(default)]
+  pub(crate) method: CallbackMethod,
+  pub(crate) body: Option<TypeAttr>,
+  pub(crate) response: Option<TypeAttr>,
+}
+
+#[derive(FromMeta, Clone, Default)]
+#[darling(rename_all = "lowercase")]
+pub(crate) enum CallbackMethod {
+  Get,
+  Put,
+  #[default]
+  Post,
+  Delete,
+  Options,
+  Head,
+  Patch,
+  Trace,
+}
+
+impl ToTokens for CallbackMethod {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let operation_type = match self {
+      CallbackMethod::Get => quote!(Get),
+      CallbackMethod::Put => quote!(Put),
+      CallbackMethod::Post => quote!(Post),
+      CallbackMethod::Delete => quote!(Delete),
+      CallbackMethod::Options => quote!(Options),
+      CallbackMethod::Head => quote!(Head),
+      CallbackMethod::Patch => quote!(Patch),
+      CallbackMethod::Trace => quote!(Trace),
+    };
+    tokens.extend(quote!(apistos::paths::OperationType::#operation_type))
+  }
+}
+
+#[derive(FromMeta, Clone)]
+#[darling(and_then = "Self::validate")]
+pub(crate) struct LinkAttr {
+  pub(crate) name: String,
+  pub(crate) operation_id: Option<String>,
+  pub(crate) operation: Option<HandlerAttr>,
+  pub(crate) status: Option<u16>,
+  pub(crate) description: Option<String>,
+  pub(crate) request_body: Option<String>,
+  #[darling(multiple, rename = "parameter")]
+  pub(crate) parameters: Vec<LinkParameterAttr>,
+}
+
+impl LinkAttr {
+  fn validate(self) -> darling::Result<Self> {
+    match (&self.operation_id, &self.operation) {
+      (Some(_), None) | (None, Some(_)) => Ok(self),
+      _ => Err(darling::Error::custom(format!(
+        "link {} expects exactly one of `operation_id` or `operation`",
+        self.name
+      ))),
+    }
+  }
+}
+
+#[derive(FromMeta, Clone)]
+pub(crate) struct LinkParameterAttr {
+  pub(crate) name: String,
+  pub(crate) expression: String,
+}
+
+/// A path to a handler documented with `#[api_operation]` (`operation = get_order` or `operation = orders::get_order`).
+#[derive(Clone)]
+pub(crate) struct HandlerAttr(pub(crate) Path);
+
+impl HandlerAttr {
+  /// Path to the struct generated by `#[api_operation]` for this handler.
+  pub(crate) fn openapi_struct(&self) -> Path {
+    let mut path = self.0.clone();
+    if let Some(segment) = path.segments.last_mut() {
+      segment.ident = Ident::new(
+        &format!("{OPENAPI_STRUCT_PREFIX}{}", segment.ident),
+        segment.ident.span(),
+      );
+    }
+    path
+  }
+}
+
+impl FromMeta for HandlerAttr {
+  fn from_expr(expr: &Expr) -> darling::Result<Self> {
+    match expr {
+      Expr::Path(path) => Ok(HandlerAttr(path.path.clone())),
+      Expr::Lit(lit) => Self::from_value(&lit.lit),
+      expr => Err(darling::Error::unexpected_expr_type(expr)),
+    }
+  }
+
+  fn from_string(value: &str) -> darling::Result<Self> {
+    syn::parse_str(value).map(HandlerAttr).map_err(darling::Error::custom)
+  }
+}
+
+/// A type provided either as a path (`body = Event`) or as a string for types with generics (`body = "Vec<Event>"`).
+#[derive(Clone)]
+pub(crate) struct TypeAttr(pub(crate) Type);
+
+impl FromMeta for TypeAttr {
+  fn from_expr(expr: &Expr) -> darling::Result<Self> {
+    match expr {
+      Expr::Lit(lit) => Self::from_value(&lit.lit),
+      expr => syn::parse2(expr.to_token_stream())
+        .map(TypeAttr)
+        .map_err(|e| darling::Error::custom(e).with_span(expr)),
+    }
+  }
+
+  fn from_string(value: &str) -> darling::Result<Self> {
+    syn::parse_str(value).map(TypeAttr).map_err(darling::Error::custom)
+  }
+}
+
+impl ToTokens for TypeAttr {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    self.0.to_tokens(tokens)
+  }
 }
 
 impl From<OperationAttrInternal> for OperationAttr {
@@ -71,6 +208,8 @@ impl From<OperationAttrInternal> for OperationAttr {
       consumes: value.consumes,
       produces: value.produces,
       skip_args: value.skip_args,
+      callbacks: value.callbacks,
+      links: value.links,
     }
   }
 }
@@ -9,6 +9,9 @@ use serde_json::Value;
 pub struct Info {
   /// The title of the API
   pub title: String,
+  /// A short summary of the API. Only supported by OAS 3.1.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub summary: Option<String>,
   /// A short description of the API. [CommonMark](https://spec.commonmark.org/) syntax MAY be used for rich text representation.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub description: Option<String>,
@@ -54,6 +57,9 @@ pub struct Contact {
 pub struct License {
   /// The license name used for the API.
   pub name: String,
+  /// An [SPDX](https://spdx.org/licenses/) license expression for the API. The `identifier` field is mutually exclusive of the `url` field. Only supported by OAS 3.1.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub identifier: Option<String>,
   /// A URL to the license used for the API. MUST be in the format of a URL.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub url: Option<String>,
@@ -7,21 +7,25 @@
 
 use crate::components::Components;
 use crate::info::Info;
-use crate::paths::{ExternalDocumentation, Paths};
+use crate::paths::{ExternalDocumentation, PathItem, Paths};
+use crate::reference_or::ReferenceOr;
 use crate::security::SecurityRequirement;
 use crate::server::Server;
 use crate::tag::Tag;
 use indexmap::IndexMap;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 pub mod components;
 pub mod info;
+pub mod merge;
 pub mod paths;
 pub mod reference_or;
 pub mod security;
 pub mod server;
 pub mod tag;
+pub mod visit;
 
 pub use schemars::schema::*;
 
@@ -30,6 +34,8 @@ pub use schemars::schema::*;
 pub enum OpenApiVersion {
   #[serde(rename = "3.0.3")]
   OAS3_0,
+  #[serde(rename = "3.1.0")]
+  OAS3_1,
 }
 
 impl Default for OpenApiVersion {
@@ -47,10 +53,16 @@ pub struct OpenApi {
   pub openapi: OpenApiVersion,
   /// Provides metadata about the API. The metadata MAY be used by tooling as required.
   pub info: Info,
+  /// The default value for the `$schema` keyword within [Schema Objects](https://spec.openapis.org/oas/v3.1.0#schema-object) contained within this OAS document. This MUST be in the form of a URI. Only supported by OAS 3.1.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub json_schema_dialect: Option<String>,
   /// An array of Server Objects, which provide connectivity information to a target server. If the `servers` property is not provided, or is an empty array, the default value would be a [Server Object](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#server-object) with a [url](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#serverUrl) value of `/`.
   pub servers: Vec<Server>,
   /// The available paths and operations for the API.
   pub paths: Paths,
+  /// The incoming webhooks that MAY be received as part of this API and that the API consumer MAY choose to implement. The key name is a unique string to refer to each webhook, while the (optionally referenced) Path Item Object describes a request that may be initiated by the API provider and the expected responses. Only supported by OAS 3.1.
+  #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+  pub webhooks: BTreeMap<String, ReferenceOr<PathItem>>,
   /// An element to hold various schemas for the specification.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub components: Option<Components>,
@@ -152,4 +164,15 @@ mod test {
     let oas_json = serde_json::to_string_pretty(&oas).expect("Error generating json for oas");
     assert_eq!(oas_json, include_str!("../test-assets/openapi.json"));
   }
+
+  #[test]
+  fn extension_operation_type_properly_serialized() {
+    let operation_types = vec![OperationType::Trace, OperationType::Extension("connect".to_string())];
+
+    let json = serde_json::to_value(&operation_types).expect("Error generating json for operation types");
+    assert_eq!(json, serde_json::json!(["trace", "x-connect"]));
+
+    let deserialized: Vec<OperationType> = serde_json::from_value(json).expect("Error parsing operation types");
+    assert_eq!(deserialized, operation_types);
+  }
 }
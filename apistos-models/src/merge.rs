@@ -0,0 +1,894 @@
+//! Utilities to merge the [`OpenApi`] documents of several services into a single one, e.g. to expose them behind a gateway.
+//!
+//! Paths of each source are prefixed by the source's path prefix. Components declared by several documents are deduplicated when they are identical, conflicting ones are either renamed or reported depending on the [`MergePolicy`]. Tags and security schemes are unioned.
+
+use crate::components::Components;
+use crate::paths::{
+  Callback, Encoding, Example, Examples, Header, Link, MediaType, Operation, OperationIdentifier, OperationType,
+  Parameter, ParameterDefinition, PathItem, RequestBody, Response,
+};
+use crate::reference_or::ReferenceOr;
+use crate::security::{SecurityRequirement, SecurityScheme};
+use crate::OpenApi;
+use schemars::schema::{Schema, SchemaObject};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// A document to merge, along with the prefix under which its paths are exposed.
+#[derive(Clone, Debug)]
+pub struct MergeSource {
+  /// Name of the source, used to rename conflicting components and operation ids as well as to report conflicts.
+  pub name: String,
+  /// Prefix prepended to every path of the source document, e.g. `/users`.
+  pub path_prefix: String,
+  pub open_api: OpenApi,
+}
+
+impl MergeSource {
+  pub fn new(name: impl Into<String>, path_prefix: impl Into<String>, open_api: OpenApi) -> Self {
+    Self {
+      name: name.into(),
+      path_prefix: path_prefix.into(),
+      open_api,
+    }
+  }
+}
+
+/// What to do when two documents declare different items under the same name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+  /// Abort the merge with a [`MergeError`].
+  #[default]
+  Fail,
+  /// Rename the item of the merged source to `{source name}_{name}`, updating every reference to it.
+  Rename,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MergePolicy {
+  /// Policy applied to conflicting components (schemas, responses, security schemes, ...) and webhooks.
+  pub components: ConflictPolicy,
+  /// Policy applied to conflicting operation ids.
+  pub operation_ids: ConflictPolicy,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergeError {
+  /// The source declares a component which differs from an already merged one with the same name.
+  ComponentConflict {
+    source: String,
+    kind: &'static str,
+    name: String,
+  },
+  /// The source declares a webhook which differs from an already merged one with the same name.
+  WebhookConflict { source: String, name: String },
+  /// The source declares an operation id already used by a merged operation.
+  OperationIdConflict { source: String, operation_id: String },
+  /// The source declares an operation already declared for the same path and method. This conflict can't be solved by renaming.
+  PathConflict {
+    source: String,
+    path: String,
+    operation_type: OperationType,
+  },
+}
+
+impl Display for MergeError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      MergeError::ComponentConflict { source, kind, name } => {
+        write!(f, "{source}: conflicting component `#/components/{kind}/{name}`")
+      }
+      MergeError::WebhookConflict { source, name } => write!(f, "{source}: conflicting webhook `{name}`"),
+      MergeError::OperationIdConflict { source, operation_id } => {
+        write!(f, "{source}: conflicting operation id `{operation_id}`")
+      }
+      MergeError::PathConflict {
+        source,
+        path,
+        operation_type,
+      } => write!(f, "{source}: operation `{operation_type} {path}` is already declared"),
+    }
+  }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Merge the given sources into the base document.
+///
+/// The base document provides the merged document info, servers, external docs and extensions. Its paths, components, tags and security are kept as is.
+pub fn merge(
+  base: OpenApi,
+  sources: impl IntoIterator<Item = MergeSource>,
+  policy: MergePolicy,
+) -> Result<OpenApi, MergeError> {
+  sources
+    .into_iter()
+    .try_fold(base, |merged, source| merge_source(merged, source, policy))
+}
+
+fn merge_source(mut merged: OpenApi, source: MergeSource, policy: MergePolicy) -> Result<OpenApi, MergeError> {
+  let MergeSource {
+    name,
+    path_prefix,
+    mut open_api,
+  } = source;
+  let path_prefix = path_prefix.trim_end_matches('/');
+
+  let mut renames = Renames {
+    operation_ref_prefix: (!path_prefix.is_empty()).then(|| path_prefix.replace('~', "~0").replace('/', "~1")),
+    ..Default::default()
+  };
+  if let Some(source_components) = open_api.components.as_ref() {
+    let components = merged.components.get_or_insert_with(Default::default);
+    plan_component_renames(&name, components, source_components, policy.components, &mut renames)?;
+  }
+  plan_operation_id_renames(&name, &merged, &open_api, policy.operation_ids, &mut renames)?;
+
+  open_api.rename_references(&renames);
+
+  if !open_api.security.is_empty() || !merged.security.is_empty() {
+    // operations inheriting the source global security would otherwise inherit the merged document one
+    let security = if open_api.security.is_empty() {
+      vec![SecurityRequirement::default()]
+    } else {
+      open_api.security
+    };
+    let operations = open_api
+      .paths
+      .paths
+      .values_mut()
+      .chain(open_api.webhooks.values_mut().filter_map(ReferenceOr::get_object_mut))
+      .flat_map(|path_item| path_item.operations.values_mut());
+    for operation in operations.filter(|operation| operation.security.is_empty()) {
+      operation.security.clone_from(&security);
+    }
+  }
+
+  if let Some(source_components) = open_api.components {
+    let components = merged.components.get_or_insert_with(Default::default);
+    merge_components(components, source_components, &renames);
+  }
+
+  for (path, mut path_item) in open_api.paths.paths {
+    let path = format!("{path_prefix}{path}");
+    match merged.paths.paths.get_mut(&path) {
+      None => {
+        merged.paths.paths.insert(path, path_item);
+      }
+      Some(merged_path_item) => {
+        if let Some(operation_type) = path_item
+          .operations
+          .keys()
+          .find(|operation_type| merged_path_item.operations.contains_key(*operation_type))
+        {
+          return Err(MergeError::PathConflict {
+            source: name,
+            path,
+            operation_type: operation_type.clone(),
+          });
+        }
+        if !same(&merged_path_item.parameters, &path_item.parameters) {
+          inline_path_parameters(merged_path_item);
+          inline_path_parameters(&mut path_item);
+        }
+        merged_path_item.operations.extend(path_item.operations);
+      }
+    }
+  }
+
+  for (webhook_name, webhook) in open_api.webhooks {
+    match merged.webhooks.get(&webhook_name) {
+      None => {
+        merged.webhooks.insert(webhook_name, webhook);
+      }
+      Some(merged_webhook) if same(merged_webhook, &webhook) => {}
+      Some(_) => {
+        let renamed = format!("{name}_{webhook_name}");
+        if policy.components == ConflictPolicy::Fail || merged.webhooks.contains_key(&renamed) {
+          return Err(MergeError::WebhookConflict {
+            source: name,
+            name: webhook_name,
+          });
+        }
+        merged.webhooks.insert(renamed, webhook);
+      }
+    }
+  }
+
+  for tag in open_api.tags {
+    if !merged.tags.iter().any(|merged_tag| merged_tag.name == tag.name) {
+      merged.tags.push(tag);
+    }
+  }
+
+  Ok(merged)
+}
+
+/// Renames to apply to the source document before merging it.
+#[derive(Default)]
+struct Renames {
+  /// Component references, e.g. `#/components/schemas/Pet` to `#/components/schemas/pets_Pet`.
+  references: BTreeMap<String, String>,
+  security_schemes: BTreeMap<String, String>,
+  operation_ids: BTreeMap<String, String>,
+  /// Path prefix as a json pointer token, used to rewrite local operation references.
+  operation_ref_prefix: Option<String>,
+}
+
+impl Renames {
+  fn rename(&self, reference: &mut String) {
+    if let Some(renamed) = self.references.get(reference) {
+      reference.clone_from(renamed);
+    }
+  }
+}
+
+fn plan_component_renames(
+  source: &str,
+  merged: &Components,
+  components: &Components,
+  policy: ConflictPolicy,
+  renames: &mut Renames,
+) -> Result<(), MergeError> {
+  // renaming a component changes the components referencing it, which may then conflict in turn
+  loop {
+    let mut renamed = false;
+    renamed |= plan_renames(source, "schemas", &merged.schemas, &components.schemas, policy, renames)?;
+    renamed |= plan_renames(
+      source,
+      "responses",
+      &merged.responses,
+      &components.responses,
+      policy,
+      renames,
+    )?;
+    renamed |= plan_renames(
+      source,
+      "parameters",
+      &merged.parameters,
+      &components.parameters,
+      policy,
+      renames,
+    )?;
+    renamed |= plan_renames(
+      source,
+      "examples",
+      &merged.examples,
+      &components.examples,
+      policy,
+      renames,
+    )?;
+    renamed |= plan_renames(
+      source,
+      "requestBodies",
+      &merged.request_bodies,
+      &components.request_bodies,
+      policy,
+      renames,
+    )?;
+    renamed |= plan_renames(source, "headers", &merged.headers, &components.headers, policy, renames)?;
+    renamed |= plan_renames(
+      source,
+      "securitySchemes",
+      &merged.security_schemes,
+      &components.security_schemes,
+      policy,
+      renames,
+    )?;
+    renamed |= plan_renames(source, "links", &merged.links, &components.links, policy, renames)?;
+    renamed |= plan_renames(
+      source,
+      "callbacks",
+      &merged.callbacks,
+      &components.callbacks,
+      policy,
+      renames,
+    )?;
+    if !renamed {
+      return Ok(());
+    }
+  }
+}
+
+fn plan_renames<T: Clone + Serialize + References>(
+  source: &str,
+  kind: &'static str,
+  merged: &BTreeMap<String, ReferenceOr<T>>,
+  components: &BTreeMap<String, ReferenceOr<T>>,
+  policy: ConflictPolicy,
+  renames: &mut Renames,
+) -> Result<bool, MergeError> {
+  let mut renamed = false;
+  for (name, component) in components {
+    let reference = format!("#/components/{kind}/{name}");
+    if renames.references.contains_key(&reference) {
+      continue;
+    }
+    let Some(merged_component) = merged.get(name) else {
+      continue;
+    };
+    let mut component = component.clone();
+    component.rename_references(renames);
+    if same(merged_component, &component) {
+      continue;
+    }
+
+    let new_name = format!("{source}_{name}");
+    if policy == ConflictPolicy::Fail || merged.contains_key(&new_name) || components.contains_key(&new_name) {
+      return Err(MergeError::ComponentConflict {
+        source: source.to_owned(),
+        kind,
+        name: name.clone(),
+      });
+    }
+    if kind == "securitySchemes" {
+      renames.security_schemes.insert(name.clone(), new_name.clone());
+    }
+    renames
+      .references
+      .insert(reference, format!("#/components/{kind}/{new_name}"));
+    renamed = true;
+  }
+  Ok(renamed)
+}
+
+fn plan_operation_id_renames(
+  source: &str,
+  merged: &OpenApi,
+  open_api: &OpenApi,
+  policy: ConflictPolicy,
+  renames: &mut Renames,
+) -> Result<(), MergeError> {
+  let merged_operation_ids: BTreeSet<&String> = operation_ids(merged).collect();
+  let operation_ids: BTreeSet<&String> = operation_ids(open_api).collect();
+  for operation_id in operation_ids.iter().filter(|id| merged_operation_ids.contains(*id)) {
+    let new_operation_id = format!("{source}_{operation_id}");
+    if policy == ConflictPolicy::Fail
+      || merged_operation_ids.contains(&new_operation_id)
+      || operation_ids.contains(&new_operation_id)
+    {
+      return Err(MergeError::OperationIdConflict {
+        source: source.to_owned(),
+        operation_id: (*operation_id).clone(),
+      });
+    }
+    renames.operation_ids.insert((*operation_id).clone(), new_operation_id);
+  }
+  Ok(())
+}
+
+fn operation_ids(open_api: &OpenApi) -> impl Iterator<Item = &String> {
+  open_api
+    .paths
+    .paths
+    .values()
+    .chain(open_api.webhooks.values().filter_map(|webhook| match webhook {
+      ReferenceOr::Object(path_item) => Some(path_item),
+      ReferenceOr::Reference { .. } => None,
+    }))
+    .flat_map(|path_item| path_item.operations.values())
+    .filter_map(|operation| operation.operation_id.as_ref())
+}
+
+fn merge_components(merged: &mut Components, components: Components, renames: &Renames) {
+  merge_components_of("schemas", &mut merged.schemas, components.schemas, renames);
+  merge_components_of("responses", &mut merged.responses, components.responses, renames);
+  merge_components_of("parameters", &mut merged.parameters, components.parameters, renames);
+  merge_components_of("examples", &mut merged.examples, components.examples, renames);
+  merge_components_of(
+    "requestBodies",
+    &mut merged.request_bodies,
+    components.request_bodies,
+    renames,
+  );
+  merge_components_of("headers", &mut merged.headers, components.headers, renames);
+  merge_components_of(
+    "securitySchemes",
+    &mut merged.security_schemes,
+    components.security_schemes,
+    renames,
+  );
+  merge_components_of("links", &mut merged.links, components.links, renames);
+  merge_components_of("callbacks", &mut merged.callbacks, components.callbacks, renames);
+}
+
+fn merge_components_of<T: Clone>(
+  kind: &str,
+  merged: &mut BTreeMap<String, ReferenceOr<T>>,
+  components: BTreeMap<String, ReferenceOr<T>>,
+  renames: &Renames,
+) {
+  let prefix = format!("#/components/{kind}/");
+  for (name, component) in components {
+    let renamed = renames
+      .references
+      .get(&format!("{prefix}{name}"))
+      .and_then(|reference| reference.strip_prefix(&prefix));
+    match renamed {
+      Some(renamed) => {
+        merged.insert(renamed.to_owned(), component);
+      }
+      // identical components are only kept once
+      None => {
+        merged.entry(name).or_insert(component);
+      }
+    }
+  }
+}
+
+/// Move path level parameters to the path item operations, so that operations merged from another document are not affected by them.
+fn inline_path_parameters(path_item: &mut PathItem) {
+  let parameters = std::mem::take(&mut path_item.parameters);
+  for operation in path_item.operations.values_mut() {
+    let overridden = |parameter: &&ReferenceOr<Parameter>| {
+      match parameter {
+      ReferenceOr::Object(parameter) => operation.parameters.iter().any(|operation_parameter| {
+        matches!(operation_parameter, ReferenceOr::Object(p) if p.name == parameter.name && p._in == parameter._in)
+      }),
+      ReferenceOr::Reference { .. } => operation.parameters.iter().any(|p| same(p, *parameter)),
+    }
+    };
+    let mut inherited: Vec<ReferenceOr<Parameter>> = parameters.iter().filter(|p| !overridden(p)).cloned().collect();
+    inherited.append(&mut operation.parameters);
+    operation.parameters = inherited;
+  }
+}
+
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+  matches!((serde_json::to_value(a), serde_json::to_value(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Rewrite references to renamed components and operations.
+trait References {
+  fn rename_references(&mut self, renames: &Renames);
+}
+
+impl<T: Clone + References> References for ReferenceOr<T> {
+  fn rename_references(&mut self, renames: &Renames) {
+    match self {
+      ReferenceOr::Object(object) => object.rename_references(renames),
+      ReferenceOr::Reference { _ref } => renames.rename(_ref),
+    }
+  }
+}
+
+impl<T: References> References for Option<T> {
+  fn rename_references(&mut self, renames: &Renames) {
+    if let Some(object) = self {
+      object.rename_references(renames);
+    }
+  }
+}
+
+impl<T: References> References for Vec<T> {
+  fn rename_references(&mut self, renames: &Renames) {
+    for object in self {
+      object.rename_references(renames);
+    }
+  }
+}
+
+impl<T: References> References for BTreeMap<String, T> {
+  fn rename_references(&mut self, renames: &Renames) {
+    for object in self.values_mut() {
+      object.rename_references(renames);
+    }
+  }
+}
+
+struct SchemaReferences<'a>(&'a Renames);
+
+impl Visitor for SchemaReferences<'_> {
+  fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+    if let Some(reference) = schema.reference.as_mut() {
+      self.0.rename(reference);
+    }
+    visit_schema_object(self, schema);
+  }
+}
+
+impl References for Schema {
+  fn rename_references(&mut self, renames: &Renames) {
+    SchemaReferences(renames).visit_schema(self);
+  }
+}
+
+impl References for OpenApi {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.components.rename_references(renames);
+    for path_item in self.paths.paths.values_mut() {
+      path_item.rename_references(renames);
+    }
+    self.webhooks.rename_references(renames);
+    self.security.rename_references(renames);
+  }
+}
+
+impl References for Components {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.schemas.rename_references(renames);
+    self.responses.rename_references(renames);
+    self.parameters.rename_references(renames);
+    self.examples.rename_references(renames);
+    self.request_bodies.rename_references(renames);
+    self.headers.rename_references(renames);
+    self.security_schemes.rename_references(renames);
+    self.links.rename_references(renames);
+    self.callbacks.rename_references(renames);
+  }
+}
+
+impl References for PathItem {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.parameters.rename_references(renames);
+    for operation in self.operations.values_mut() {
+      operation.rename_references(renames);
+    }
+  }
+}
+
+impl References for Operation {
+  fn rename_references(&mut self, renames: &Renames) {
+    if let Some(renamed) = self
+      .operation_id
+      .as_ref()
+      .and_then(|operation_id| renames.operation_ids.get(operation_id))
+    {
+      self.operation_id = Some(renamed.clone());
+    }
+    self.parameters.rename_references(renames);
+    self.request_body.rename_references(renames);
+    self.responses.default.rename_references(renames);
+    self.responses.responses.rename_references(renames);
+    self.callbacks.rename_references(renames);
+    self.security.rename_references(renames);
+  }
+}
+
+impl References for SecurityRequirement {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.requirements = std::mem::take(&mut self.requirements)
+      .into_iter()
+      .map(|(name, scopes)| (renames.security_schemes.get(&name).cloned().unwrap_or(name), scopes))
+      .collect();
+  }
+}
+
+impl References for Parameter {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.definition.rename_references(renames);
+    self.example.rename_references(renames);
+  }
+}
+
+impl References for Header {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.definition.rename_references(renames);
+  }
+}
+
+impl References for ParameterDefinition {
+  fn rename_references(&mut self, renames: &Renames) {
+    match self {
+      ParameterDefinition::Schema(schema) => schema.rename_references(renames),
+      ParameterDefinition::Content(content) => content.rename_references(renames),
+    }
+  }
+}
+
+impl References for MediaType {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.schema.rename_references(renames);
+    self.example.rename_references(renames);
+    self.encoding.rename_references(renames);
+  }
+}
+
+impl References for Encoding {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.headers.rename_references(renames);
+  }
+}
+
+impl References for Examples {
+  fn rename_references(&mut self, renames: &Renames) {
+    if let Examples::Examples(examples) = self {
+      examples.rename_references(renames);
+    }
+  }
+}
+
+impl References for RequestBody {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.content.rename_references(renames);
+  }
+}
+
+impl References for Response {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.headers.rename_references(renames);
+    self.content.rename_references(renames);
+    self.links.rename_references(renames);
+  }
+}
+
+impl References for Link {
+  fn rename_references(&mut self, renames: &Renames) {
+    match self.operation_identifier.as_mut() {
+      Some(OperationIdentifier::OperationId(operation_id)) => {
+        if let Some(renamed) = renames.operation_ids.get(operation_id) {
+          operation_id.clone_from(renamed);
+        }
+      }
+      Some(OperationIdentifier::OperationRef(operation_ref)) => {
+        if let (Some(path), Some(prefix)) = (
+          operation_ref.strip_prefix("#/paths/"),
+          renames.operation_ref_prefix.as_ref(),
+        ) {
+          *operation_ref = format!("#/paths/{prefix}{path}");
+        }
+      }
+      None => {}
+    }
+  }
+}
+
+impl References for Callback {
+  fn rename_references(&mut self, renames: &Renames) {
+    self.callbacks.rename_references(renames);
+  }
+}
+
+impl References for Example {
+  fn rename_references(&mut self, _renames: &Renames) {}
+}
+
+impl References for SecurityScheme {
+  fn rename_references(&mut self, _renames: &Renames) {}
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::merge::{merge, ConflictPolicy, MergeError, MergePolicy, MergeSource};
+  use crate::paths::OperationType;
+  use crate::OpenApi;
+  use serde_json::{json, Value};
+
+  fn open_api(value: Value) -> OpenApi {
+    serde_json::from_value(value).expect("Unable to parse openapi document")
+  }
+
+  fn pets() -> OpenApi {
+    open_api(json!({
+      "openapi": "3.0.3",
+      "info": { "title": "pets", "version": "1.0.0" },
+      "servers": [],
+      "paths": {
+        "/": {
+          "get": {
+            "operationId": "list",
+            "tags": ["pet"],
+            "responses": {
+              "200": {
+                "description": "",
+                "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } },
+                "links": { "first": { "operationId": "get" } }
+              }
+            }
+          }
+        },
+        "/{id}": {
+          "get": {
+            "operationId": "get",
+            "responses": {
+              "200": { "$ref": "#/components/responses/Item" },
+              "default": { "$ref": "#/components/responses/Error" }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Item": { "type": "object", "properties": { "name": { "type": "string" } } },
+          "Error": { "type": "object", "properties": { "message": { "type": "string" } } }
+        },
+        "responses": {
+          "Item": { "description": "", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } },
+          "Error": { "description": "", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+        },
+        "securitySchemes": {
+          "api_key": { "type": "apiKey", "name": "x-api-key", "in": "header" }
+        }
+      },
+      "security": [{ "api_key": [] }],
+      "tags": [{ "name": "pet" }]
+    }))
+  }
+
+  fn stores() -> OpenApi {
+    open_api(json!({
+      "openapi": "3.0.3",
+      "info": { "title": "stores", "version": "1.0.0" },
+      "servers": [],
+      "paths": {
+        "/": {
+          "get": {
+            "operationId": "list",
+            "tags": ["store"],
+            "responses": {
+              "200": {
+                "description": "",
+                "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } }
+              },
+              "default": { "$ref": "#/components/responses/Error" }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "Item": { "type": "object", "properties": { "address": { "type": "string" } } },
+          "Error": { "type": "object", "properties": { "message": { "type": "string" } } }
+        },
+        "responses": {
+          "Error": { "description": "", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } } }
+        },
+        "securitySchemes": {
+          "api_key": { "type": "apiKey", "name": "x-store-key", "in": "header" }
+        }
+      },
+      "security": [{ "api_key": [] }],
+      "tags": [{ "name": "pet" }, { "name": "store" }]
+    }))
+  }
+
+  fn base() -> OpenApi {
+    open_api(json!({
+      "openapi": "3.0.3",
+      "info": { "title": "portal", "version": "1.0.0" },
+      "servers": [{ "url": "https://api.example.com" }],
+      "paths": {}
+    }))
+  }
+
+  #[test]
+  fn merge_renaming_conflicts() {
+    let merged = merge(
+      base(),
+      vec![
+        MergeSource::new("pets", "/pets/", pets()),
+        MergeSource::new("stores", "/stores", stores()),
+      ],
+      MergePolicy {
+        components: ConflictPolicy::Rename,
+        operation_ids: ConflictPolicy::Rename,
+      },
+    )
+    .expect("Unable to merge documents");
+
+    let json = serde_json::to_value(&merged).expect("Unable to serialize merged document");
+    assert_eq!(json["info"]["title"], json!("portal"));
+    assert_eq!(json["servers"], json!([{ "url": "https://api.example.com" }]));
+    assert_eq!(
+      json["paths"]
+        .as_object()
+        .expect("missing paths")
+        .keys()
+        .collect::<Vec<_>>(),
+      vec!["/pets/", "/pets/{id}", "/stores/"]
+    );
+
+    let pets_list = &json["paths"]["/pets/"]["get"];
+    assert_eq!(pets_list["operationId"], json!("list"));
+    assert_eq!(pets_list["security"], json!([{ "api_key": [] }]));
+    assert_eq!(
+      pets_list["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+      json!("#/components/schemas/Item")
+    );
+    assert_eq!(
+      pets_list["responses"]["200"]["links"]["first"]["operationId"],
+      json!("get")
+    );
+
+    let stores_list = &json["paths"]["/stores/"]["get"];
+    assert_eq!(stores_list["operationId"], json!("stores_list"));
+    assert_eq!(stores_list["security"], json!([{ "stores_api_key": [] }]));
+    assert_eq!(
+      stores_list["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+      json!("#/components/schemas/stores_Item")
+    );
+    assert_eq!(
+      stores_list["responses"]["default"]["$ref"],
+      json!("#/components/responses/Error")
+    );
+
+    let components = &json["components"];
+    assert_eq!(
+      components["schemas"]
+        .as_object()
+        .expect("missing schemas")
+        .keys()
+        .collect::<Vec<_>>(),
+      vec!["Error", "Item", "stores_Item"]
+    );
+    assert_eq!(
+      components["responses"]
+        .as_object()
+        .expect("missing responses")
+        .keys()
+        .collect::<Vec<_>>(),
+      vec!["Error", "Item"]
+    );
+    assert_eq!(
+      components["securitySchemes"]["stores_api_key"]["name"],
+      json!("x-store-key")
+    );
+    assert_eq!(json["tags"], json!([{ "name": "pet" }, { "name": "store" }]));
+  }
+
+  #[test]
+  fn merge_failing_on_conflicts() {
+    let merged = merge(
+      base(),
+      vec![
+        MergeSource::new("pets", "/pets", pets()),
+        MergeSource::new("stores", "/stores", stores()),
+      ],
+      MergePolicy::default(),
+    );
+    assert_eq!(
+      merged.err(),
+      Some(MergeError::ComponentConflict {
+        source: "stores".to_string(),
+        kind: "schemas",
+        name: "Item".to_string(),
+      })
+    );
+
+    let merged = merge(
+      base(),
+      vec![
+        MergeSource::new("pets", "/pets", pets()),
+        MergeSource::new("stores", "/stores", stores()),
+      ],
+      MergePolicy {
+        components: ConflictPolicy::Rename,
+        operation_ids: ConflictPolicy::Fail,
+      },
+    );
+    assert_eq!(
+      merged.err(),
+      Some(MergeError::OperationIdConflict {
+        source: "stores".to_string(),
+        operation_id: "list".to_string(),
+      })
+    );
+  }
+
+  #[test]
+  fn merge_failing_on_path_conflicts() {
+    let merged = merge(
+      base(),
+      vec![
+        MergeSource::new("pets", "/", pets()),
+        MergeSource::new("stores", "/", stores()),
+      ],
+      MergePolicy {
+        components: ConflictPolicy::Rename,
+        operation_ids: ConflictPolicy::Rename,
+      },
+    );
+    assert_eq!(
+      merged.err(),
+      Some(MergeError::PathConflict {
+        source: "stores".to_string(),
+        path: "/".to_string(),
+        operation_type: OperationType::Get,
+      })
+    );
+  }
+}
@@ -3,9 +3,10 @@ use crate::security::SecurityRequirement;
 use crate::server::Server;
 use indexmap::IndexMap;
 use schemars::schema::Schema;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 
 #[derive(Serialize, Clone, Debug, Default)]
 #[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
@@ -42,9 +43,7 @@ pub struct PathItem {
   pub extensions: IndexMap<String, Value>,
 }
 
-#[derive(Serialize, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
-#[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize))]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum OperationType {
   /// A definition of a GET operation on this path.
   Get,
@@ -62,6 +61,69 @@ pub enum OperationType {
   Patch,
   /// A definition of a TRACE operation on this path.
   Trace,
+  /// A definition of an operation using a method the specification has no field for, like CONNECT, serialized as an `x-<method>` [specification extension](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#specification-extensions). Holds the lowercase method name.
+  Extension(String),
+}
+
+impl Display for OperationType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      OperationType::Get => write!(f, "get"),
+      OperationType::Put => write!(f, "put"),
+      OperationType::Post => write!(f, "post"),
+      OperationType::Delete => write!(f, "delete"),
+      OperationType::Options => write!(f, "options"),
+      OperationType::Head => write!(f, "head"),
+      OperationType::Patch => write!(f, "patch"),
+      OperationType::Trace => write!(f, "trace"),
+      OperationType::Extension(method) => write!(f, "{method}"),
+    }
+  }
+}
+
+impl Serialize for OperationType {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      OperationType::Extension(method) => serializer.serialize_str(&format!("x-{method}")),
+      operation_type => serializer.serialize_str(&operation_type.to_string()),
+    }
+  }
+}
+
+#[cfg(any(test, feature = "deserialize"))]
+impl<'de> serde::Deserialize<'de> for OperationType {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let operation_type = String::deserialize(deserializer)?;
+    Ok(match operation_type.as_str() {
+      "get" => OperationType::Get,
+      "put" => OperationType::Put,
+      "post" => OperationType::Post,
+      "delete" => OperationType::Delete,
+      "options" => OperationType::Options,
+      "head" => OperationType::Head,
+      "patch" => OperationType::Patch,
+      "trace" => OperationType::Trace,
+      other => match other.strip_prefix("x-") {
+        Some(method) => OperationType::Extension(method.to_owned()),
+        None => {
+          return Err(serde::de::Error::unknown_variant(
+            other,
+            &[
+              "get",
+              "put",
+              "post",
+              "delete",
+              "options",
+              "head",
+              "patch",
+              "trace",
+              "x-<method>",
+            ],
+          ))
+        }
+      },
+    })
+  }
 }
 
 /// Describes a single API operation on a path.
@@ -22,7 +22,7 @@ pub struct SecurityRequirement {
 #[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
 #[serde(rename_all = "camelCase")]
 pub struct SecurityScheme {
-  /// The type of the security scheme. Valid values are `"apiKey"`, `"http"`, `"oauth2"`, `"openIdConnect"`.
+  /// The type of the security scheme. Valid values are `"apiKey"`, `"http"`, `"mutualTLS"`, `"oauth2"`, `"openIdConnect"`.
   #[serde(flatten)]
   pub _type: SecurityType,
   /// A short description for security scheme. [CommonMark syntax](https://spec.commonmark.org/) MAY be used for rich text representation.
@@ -42,6 +42,9 @@ pub enum SecurityType {
   #[serde(rename = "oauth2")]
   OAuth2(OAuth2),
   OpenIdConnect(OpenIdConnect),
+  /// Mutual TLS authentication. Only supported by OAS 3.1.
+  #[serde(rename = "mutualTLS")]
+  MutualTLS,
 }
 
 #[derive(Serialize, Clone, Debug)]
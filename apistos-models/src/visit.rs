@@ -0,0 +1,254 @@
+//! Utilities to apply a [`Visitor`] to every schema of an [`OpenApi`] document, as well as visitors converting schemas between OAS versions.
+
+use crate::components::Components;
+use crate::paths::{
+  Callback, Header, MediaType, Operation, Parameter, ParameterDefinition, PathItem, RequestBody, Response,
+};
+use crate::reference_or::ReferenceOr;
+use crate::OpenApi;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec, SubschemaValidation};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::mem;
+
+/// Apply the given visitor to every schema of the document, whether it is declared in components or inlined in operations and webhooks.
+pub fn visit_open_api<V: Visitor + ?Sized>(visitor: &mut V, open_api: &mut OpenApi) {
+  if let Some(components) = open_api.components.as_mut() {
+    visit_components(visitor, components);
+  }
+  for path_item in open_api.paths.paths.values_mut() {
+    visit_path_item(visitor, path_item);
+  }
+  for path_item in open_api.webhooks.values_mut().filter_map(object_mut) {
+    visit_path_item(visitor, path_item);
+  }
+}
+
+/// Apply the given visitor to every schema of the given components.
+pub fn visit_components<V: Visitor + ?Sized>(visitor: &mut V, components: &mut Components) {
+  for schema in components.schemas.values_mut() {
+    visit_reference_or_schema(visitor, schema);
+  }
+  for response in components.responses.values_mut().filter_map(object_mut) {
+    visit_response(visitor, response);
+  }
+  for parameter in components.parameters.values_mut().filter_map(object_mut) {
+    visit_parameter(visitor, parameter);
+  }
+  for request_body in components.request_bodies.values_mut().filter_map(object_mut) {
+    visit_content(visitor, &mut request_body.content);
+  }
+  for header in components.headers.values_mut().filter_map(object_mut) {
+    visit_header(visitor, header);
+  }
+  for callback in components.callbacks.values_mut().filter_map(object_mut) {
+    visit_callback(visitor, callback);
+  }
+}
+
+/// Apply the given visitor to every schema of the given path item operations.
+pub fn visit_path_item<V: Visitor + ?Sized>(visitor: &mut V, path_item: &mut PathItem) {
+  for parameter in path_item.parameters.iter_mut().filter_map(object_mut) {
+    visit_parameter(visitor, parameter);
+  }
+  for operation in path_item.operations.values_mut() {
+    visit_operation(visitor, operation);
+  }
+}
+
+fn visit_operation<V: Visitor + ?Sized>(visitor: &mut V, operation: &mut Operation) {
+  for parameter in operation.parameters.iter_mut().filter_map(object_mut) {
+    visit_parameter(visitor, parameter);
+  }
+  if let Some(RequestBody { content, .. }) = operation.request_body.as_mut().and_then(object_mut) {
+    visit_content(visitor, content);
+  }
+  let responses = operation
+    .responses
+    .default
+    .iter_mut()
+    .chain(operation.responses.responses.values_mut());
+  for response in responses.filter_map(object_mut) {
+    visit_response(visitor, response);
+  }
+  for callback in operation.callbacks.values_mut().filter_map(object_mut) {
+    visit_callback(visitor, callback);
+  }
+}
+
+fn visit_callback<V: Visitor + ?Sized>(visitor: &mut V, callback: &mut Callback) {
+  for path_item in callback.callbacks.values_mut() {
+    visit_path_item(visitor, path_item);
+  }
+}
+
+fn visit_response<V: Visitor + ?Sized>(visitor: &mut V, response: &mut Response) {
+  for header in response.headers.values_mut().filter_map(object_mut) {
+    visit_header(visitor, header);
+  }
+  visit_content(visitor, &mut response.content);
+}
+
+fn visit_parameter<V: Visitor + ?Sized>(visitor: &mut V, parameter: &mut Parameter) {
+  if let Some(definition) = parameter.definition.as_mut() {
+    visit_parameter_definition(visitor, definition);
+  }
+}
+
+fn visit_header<V: Visitor + ?Sized>(visitor: &mut V, header: &mut Header) {
+  if let Some(definition) = header.definition.as_mut() {
+    visit_parameter_definition(visitor, definition);
+  }
+}
+
+fn visit_parameter_definition<V: Visitor + ?Sized>(visitor: &mut V, definition: &mut ParameterDefinition) {
+  match definition {
+    ParameterDefinition::Schema(schema) => visit_reference_or_schema(visitor, schema),
+    ParameterDefinition::Content(content) => visit_content(visitor, content),
+  }
+}
+
+fn visit_content<V: Visitor + ?Sized>(visitor: &mut V, content: &mut BTreeMap<String, MediaType>) {
+  for media_type in content.values_mut() {
+    if let Some(schema) = media_type.schema.as_mut() {
+      visit_reference_or_schema(visitor, schema);
+    }
+  }
+}
+
+fn visit_reference_or_schema<V: Visitor + ?Sized>(visitor: &mut V, schema: &mut ReferenceOr<Schema>) {
+  if let ReferenceOr::Object(schema) = schema {
+    visitor.visit_schema(schema);
+  }
+}
+
+fn object_mut<T: Clone>(reference_or: &mut ReferenceOr<T>) -> Option<&mut T> {
+  match reference_or {
+    ReferenceOr::Object(object) => Some(object),
+    ReferenceOr::Reference { .. } => None,
+  }
+}
+
+/// Converts schemas generated following OAS 3.0 into [JSON Schema 2020-12](https://json-schema.org/draft/2020-12/release-notes) ones, as expected by OAS 3.1:
+/// - `nullable` is replaced by a `null` type, or by an `anyOf` including a `null` schema when the schema has no type (e.g. a reference)
+/// - `example` is replaced by `examples`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Oas31SchemaVisitor;
+
+impl Visitor for Oas31SchemaVisitor {
+  fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+    if let Some(example) = schema.extensions.remove("example") {
+      let metadata = schema.metadata();
+      if metadata.examples.is_empty() {
+        metadata.examples.push(example);
+      }
+    }
+
+    if schema.extensions.remove("nullable") == Some(Value::Bool(true)) {
+      match schema.instance_type.as_mut() {
+        Some(SingleOrVec::Single(instance_type)) if **instance_type != InstanceType::Null => {
+          schema.instance_type = Some(SingleOrVec::Vec(vec![**instance_type, InstanceType::Null]));
+        }
+        Some(SingleOrVec::Vec(instance_types)) if !instance_types.contains(&InstanceType::Null) => {
+          instance_types.push(InstanceType::Null);
+        }
+        Some(_) => {}
+        None => {
+          let mut inner = mem::take(schema);
+          schema.metadata = inner.metadata.take();
+          schema.subschemas = Some(Box::new(SubschemaValidation {
+            any_of: Some(vec![
+              Schema::Object(inner),
+              Schema::Object(SchemaObject {
+                instance_type: Some(InstanceType::Null.into()),
+                ..Default::default()
+              }),
+            ]),
+            ..Default::default()
+          }));
+        }
+      }
+      if let Some(enum_values) = schema.enum_values.as_mut() {
+        if !enum_values.contains(&Value::Null) {
+          enum_values.push(Value::Null);
+        }
+      }
+    }
+
+    visit_schema_object(self, schema);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::visit::Oas31SchemaVisitor;
+  use schemars::gen::SchemaSettings;
+  use schemars::visit::Visitor;
+  use schemars::JsonSchema;
+  use serde_json::json;
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Pet {
+    name: Option<String>,
+    /// The pet owner
+    owner: Option<Owner>,
+    #[schemars(example = "example_age")]
+    age: u8,
+  }
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Owner {
+    name: String,
+  }
+
+  fn example_age() -> u8 {
+    3
+  }
+
+  #[test]
+  fn schema_converted_to_oas3_1() {
+    let mut schema = SchemaSettings::openapi3()
+      .into_generator()
+      .into_root_schema_for::<Pet>()
+      .schema;
+
+    Oas31SchemaVisitor.visit_schema_object(&mut schema);
+
+    let json = serde_json::to_value(&schema).expect("Unable to serialize schema");
+    assert_eq!(
+      json,
+      json!({
+        "title": "Pet",
+        "type": "object",
+        "required": ["age"],
+        "properties": {
+          "name": {
+            "type": ["string", "null"]
+          },
+          "owner": {
+            "description": "The pet owner",
+            "anyOf": [
+              {
+                "allOf": [{ "$ref": "#/components/schemas/Owner" }]
+              },
+              {
+                "type": "null"
+              }
+            ]
+          },
+          "age": {
+            "type": "integer",
+            "format": "uint8",
+            "minimum": 0.0,
+            "examples": [3]
+          }
+        }
+      })
+    );
+  }
+}
@@ -1,24 +1,35 @@
-use crate::internal::actix::handler::OASHandler;
-use crate::internal::actix::route::{Route, RouteWrapper};
+use crate::internal::actix::handler::{OASHandler, SpecFormat};
+use crate::internal::actix::route::{any_method, document_any_method_operations, Route, RouteWrapper};
+use crate::internal::actix::transform::{DocumentedTransform, TransformDocumentation};
+use crate::internal::cache::{
+  cache_open_api, cached_handler, cached_open_api, memoize_handler_definitions, CachedOpenApi, DocumentedRoutes,
+};
 use crate::internal::definition_holder::DefinitionHolder;
-use crate::spec::{DefaultParameters, Spec};
+use crate::internal::links::{resolve_links, OperationHandler, OperationHandlers};
+use crate::internal::references::retain_referenced_components;
+use crate::operation_id::{HashedOperationId, OperationIdStrategy};
+use crate::spec::{DefaultParameters, Spec, Transformer};
+use crate::validation::{validate, BuildError, ValidationError, ValidationLevel};
 use crate::web::ServiceConfig;
 use actix_service::{IntoServiceFactory, ServiceFactory, Transform};
 use actix_web::body::MessageBody;
 use actix_web::dev::{HttpServiceFactory, ServiceRequest, ServiceResponse};
 use actix_web::web::{get, resource};
 use actix_web::Error;
-use apistos_models::paths::{OperationType, Parameter};
+use apistos_models::components::Components;
+use apistos_models::paths::{OperationType, Parameter, PathItem};
 use apistos_models::reference_or::ReferenceOr;
-use apistos_models::OpenApi;
+use apistos_models::visit::{visit_open_api, Oas31SchemaVisitor};
+use apistos_models::{OpenApi, OpenApiVersion};
 use apistos_plugins::ui::{UIPluginConfig, UIPluginWrapper};
 use indexmap::IndexMap;
+use log::warn;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use schemars::schema::Schema;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
 use std::{fmt, mem};
 
 pub trait OpenApiWrapper<T> {
@@ -29,10 +40,30 @@ pub trait OpenApiWrapper<T> {
 
 /// Wrapper for [actix_web::App](https://docs.rs/actix-web/latest/actix_web/struct.App.html) with openapi specification
 pub struct App<T> {
-  open_api_spec: Arc<RwLock<OpenApi>>,
+  document: Document,
+  named_documents: IndexMap<String, Document>,
   inner: Option<actix_web::App<T>>,
+}
+
+/// An openapi document being populated along with the [`Spec`] options applying to its operations.
+struct Document {
+  open_api: OpenApi,
   default_tags: Vec<String>,
   default_parameters: Vec<DefaultParameters>,
+  validation: ValidationLevel,
+  operation_id_strategy: Arc<dyn OperationIdStrategy>,
+  transforms: Vec<TransformDocumentation>,
+  collapse_any_method_routes: bool,
+  transformers: Vec<Transformer>,
+  /// Whether operations were assigned to this document using [`Scope::document`](crate::web::Scope::document), its components then being restricted to the ones its operations reference.
+  scoped: bool,
+  cache_key: Option<String>,
+  /// Specification cached by an app already built with the same cache key, no longer generated by this document.
+  cached: Option<Arc<CachedOpenApi>>,
+  /// Paths and methods documented by this document, checked against the ones of the cached specification.
+  routes: DocumentedRoutes,
+  /// Handlers of the document operations, by path and method.
+  handlers: OperationHandlers,
 }
 
 /// Build config to pass to `build_with` function,
@@ -54,12 +85,20 @@ pub struct App<T> {
 ///       .with(RapidocConfig::new(&"/rapidoc")) // with rapidoc feature enable
 ///       .with(RedocConfig::new(&"/redoc")) // with redoc feature enable
 ///       .with(Scalar::new(&"/scalar")) // with scalar feature enable
-///       .with(SwaggerUIConfig::new(&"/swagger")), // with swagger-ui feature enable
+///       .with(SwaggerUIConfig::new(&"/swagger")) // with swagger-ui feature enable
+///       .with_yaml("/openapi.yaml"), // with yaml feature enable
 ///   );
 /// ```
 #[derive(Default)]
 pub struct BuildConfig {
   ui_plugin_configs: Vec<Box<dyn UIPluginConfig>>,
+  #[cfg(feature = "yaml")]
+  yaml_path: Option<String>,
+  #[cfg(feature = "yaml")]
+  content_negotiation: bool,
+  #[cfg(feature = "compression")]
+  compression: bool,
+  documents: Vec<(String, String, BuildConfig)>,
 }
 
 impl BuildConfig {
@@ -67,28 +106,66 @@ impl BuildConfig {
     self.ui_plugin_configs.push(Box::new(plugin));
     self
   }
+
+  /// Also expose the generated openapi specification as `application/yaml` at **`yaml_path`**.
+  #[cfg(feature = "yaml")]
+  pub fn with_yaml(mut self, yaml_path: &str) -> Self {
+    self.yaml_path = Some(yaml_path.to_owned());
+    self
+  }
+
+  /// Serve the generated openapi specification as `application/yaml` on the openapi path when the request `Accept` header asks for yaml, JSON being served otherwise.
+  #[cfg(feature = "yaml")]
+  pub fn with_content_negotiation(mut self) -> Self {
+    self.content_negotiation = true;
+    self
+  }
+
+  /// Also serve the generated openapi specification gzip or brotli encoded to clients accepting it, bodies being compressed once when building the app.
+  #[cfg(feature = "compression")]
+  pub fn with_compression(mut self) -> Self {
+    self.compression = true;
+    self
+  }
+
+  /// Expose the document declared with [`App::named_document`] under **`name`** at **`openapi_path`**, using **`config`** to expose it through UIs.
+  ///
+  /// ```rust,ignore
+  /// use actix_web::App;
+  /// use apistos::app::{BuildConfig, OpenApiWrapper};
+  /// use apistos::web::scope;
+  /// use apistos::SwaggerUIConfig;
+  ///
+  /// App::new()
+  ///   .document(todo!())
+  ///   .named_document("v2", todo!())
+  ///   .service(scope("/v1").service(todo!()))
+  ///   .service(scope("/v2").document("v2").service(todo!()))
+  ///   .build_with(
+  ///     "/openapi/v1.json",
+  ///     BuildConfig::default()
+  ///       .with(SwaggerUIConfig::new(&"/swagger/v1"))
+  ///       .with_document(
+  ///         "v2",
+  ///         "/openapi/v2.json",
+  ///         BuildConfig::default().with(SwaggerUIConfig::new(&"/swagger/v2")),
+  ///       ),
+  ///   );
+  /// ```
+  pub fn with_document(mut self, name: &str, openapi_path: &str, config: BuildConfig) -> Self {
+    self.documents.push((name.to_owned(), openapi_path.to_owned(), config));
+    self
+  }
 }
 
 impl<T> OpenApiWrapper<T> for actix_web::App<T> {
   type Wrapper = App<T>;
 
   fn document(self, spec: Spec) -> Self::Wrapper {
-    let mut open_api_spec = OpenApi {
-      info: spec.info,
-      ..Default::default()
-    };
-    if !spec.tags.is_empty() {
-      open_api_spec.tags = spec.tags;
-    }
-    open_api_spec.external_docs = spec.external_docs;
-    if !spec.servers.is_empty() {
-      open_api_spec.servers = spec.servers;
-    }
     App {
-      open_api_spec: Arc::new(RwLock::new(open_api_spec)),
+      document: Document::from(spec),
+      named_documents: IndexMap::new(),
       inner: Some(self),
-      default_tags: spec.default_tags,
-      default_parameters: spec.default_parameters,
     }
   }
 }
@@ -97,6 +174,20 @@ impl<T> App<T>
 where
   T: ServiceFactory<ServiceRequest, Config = (), Error = Error, InitError = ()>,
 {
+  /// Declare an additional openapi document named **`name`**, built from its own `Spec`.
+  ///
+  /// Operations of scopes assigned to this document using [`Scope::document`](crate::web::Scope::document) are only documented in it,
+  /// every other operation being documented in the default document.
+  /// Named documents are exposed using [`BuildConfig::with_document`].
+  pub fn named_document(mut self, name: &str, spec: Spec) -> Self {
+    let mut document = Document::from(spec);
+    for transform in &self.document.transforms {
+      document.add_transform(transform.clone());
+    }
+    self.named_documents.insert(name.to_owned(), document);
+    self
+  }
+
   /// Drop in for [`actix_web::App::app_data`](https://docs.rs/actix-web/*/actix_web/struct.App.html#method.app_data)
   pub fn app_data<U: 'static>(mut self, ext: U) -> Self {
     self.inner = self.inner.take().map(|app| app.app_data(ext));
@@ -170,6 +261,9 @@ where
   }
 
   /// Drop in for [`actix_web::App::wrap`](https://docs.rs/actix-web/*/actix_web/struct.App.html#method.wrap)
+  ///
+  /// **NOTE:** This doesn't affect spec generation, even if the middleware implements [`DocumentedTransform`]: its parameters, responses,
+  /// security requirements and tags are only documented when it is registered using [`App::wrap_documented`].
   pub fn wrap<M, B>(
     mut self,
     mw: M,
@@ -179,13 +273,33 @@ where
     B: MessageBody,
   {
     App {
-      open_api_spec: self.open_api_spec,
+      document: self.document,
+      named_documents: self.named_documents,
       inner: self.inner.take().map(|app| app.wrap(mw)),
-      default_tags: self.default_tags,
-      default_parameters: self.default_parameters,
     }
   }
 
+  /// Drop in for [`actix_web::App::wrap`](https://docs.rs/actix-web/*/actix_web/struct.App.html#method.wrap)
+  /// documenting what the middleware adds to every operation of the app using its [`DocumentedTransform`] implementation.
+  pub fn wrap_documented<M, B>(
+    self,
+    mw: M,
+  ) -> App<impl ServiceFactory<ServiceRequest, Config = (), Response = ServiceResponse<B>, Error = Error, InitError = ()>>
+  where
+    M: Transform<T::Service, ServiceRequest, Response = ServiceResponse<B>, Error = Error, InitError = ()>
+      + DocumentedTransform
+      + 'static,
+    B: MessageBody,
+  {
+    let documentation = TransformDocumentation::new(&mw);
+    let mut app = self.wrap(mw);
+    app.document.add_transform(documentation.clone());
+    for document in app.named_documents.values_mut() {
+      document.add_transform(documentation.clone());
+    }
+    app
+  }
+
   /// Drop in for [`actix_web::App::wrap_fn`](https://docs.rs/actix-web/*/actix_web/struct.App.html#method.wrap_fn)
   pub fn wrap_fn<F, R, B>(
     mut self,
@@ -197,25 +311,80 @@ where
     B: MessageBody,
   {
     App {
-      open_api_spec: self.open_api_spec,
+      document: self.document,
+      named_documents: self.named_documents,
       inner: self.inner.take().map(|app| app.wrap_fn(mw)),
-      default_tags: self.default_tags,
-      default_parameters: self.default_parameters,
     }
   }
 
+  /// Document a webhook named **`name`**, i.e. a request the API sends to its consumers, using the operation and components of the given route.
+  ///
+  /// The route is only used to generate documentation and is not registered on the app. Webhooks are only supported by OAS 3.1.
+  ///
+  /// ```rust,ignore
+  /// use actix_web::App;
+  /// use apistos::app::OpenApiWrapper;
+  /// use apistos::web::post;
+  ///
+  /// #[api_operation(summary = "A new pet is available")]
+  /// async fn new_pet(body: Json<Pet>) -> Result<NoContent, Error> {
+  ///   unimplemented!()
+  /// }
+  ///
+  /// App::new()
+  ///   .document(todo!())
+  ///   .webhook("newPet", post().to(new_pet))
+  ///   .build("/openapi.json");
+  /// ```
+  pub fn webhook(mut self, name: &str, route: Route) -> Self {
+    self.document.add_webhook(name, RouteWrapper::new("", route));
+    self
+  }
+
+  /// Return the openapi specification generated from every documented service, resource and route registered so far.
+  ///
+  /// The [`Spec::transformers`] are not applied and the specification is not validated, see [`App::built_open_api`] to get the specification as served.
+  pub fn open_api(&self) -> OpenApi {
+    self.document.open_api()
+  }
+
+  /// Return the openapi specification of the document declared with [`App::named_document`] under **`name`**, if any.
+  pub fn named_open_api(&self, name: &str) -> Option<OpenApi> {
+    self.named_documents.get(name).map(Document::open_api)
+  }
+
+  /// Return the openapi specification exactly as it is served once the app is built, i.e. transformed by the [`Spec::transformers`] and validated.
+  ///
+  /// This allows generating the specification without serving it, see [`export`](crate::export) to write it to disk.
+  /// Errors are the ones [`App::try_build`] would return.
+  pub fn built_open_api(&self) -> Result<Arc<OpenApi>, BuildError> {
+    self.document.built_open_api(None)
+  }
+
+  /// Same as [`App::built_open_api`] for the document declared with [`App::named_document`] under **`name`**, if any.
+  pub fn named_built_open_api(&self, name: &str) -> Option<Result<Arc<OpenApi>, BuildError>> {
+    self
+      .named_documents
+      .get(name)
+      .map(|document| document.built_open_api(Some(name)))
+  }
+
   /// Add a new resource at **`openapi_path`** to expose the generated openapi schema and return an [actix_web::App](https://docs.rs/actix-web/latest/actix_web/struct.App.html)
-  #[allow(clippy::unwrap_used, clippy::expect_used)]
+  ///
+  /// Panics if the document fails validation with [`ValidationLevel::Error`] or one of its transformers fails, see [`App::try_build`].
   pub fn build(self, openapi_path: &str) -> actix_web::App<T> {
-    let open_api_spec = self.open_api_spec.read().unwrap().clone();
-    self
-      .inner
-      .expect("Missing app")
-      .service(resource(openapi_path).route(get().to(OASHandler::new(open_api_spec))))
+    self.build_with(openapi_path, BuildConfig::default())
+  }
+
+  /// Same as [`App::build`], returning an error if the document cannot be generated.
+  pub fn try_build(self, openapi_path: &str) -> Result<actix_web::App<T>, BuildError> {
+    self.try_build_with(openapi_path, BuildConfig::default())
   }
 
   /// Add a new resource at **`openapi_path`** to expose the generated openapi schema optionnaly exposing it through UIs and return an [actix_web::App](https://docs.rs/actix-web/latest/actix_web/struct.App.html)
   ///
+  /// Panics if a document fails validation with [`ValidationLevel::Error`] or one of its transformers fails, see [`App::try_build_with`].
+  ///
   /// ```rust,ignore
   /// use actix_web::App;
   /// use apistos::app::{BuildConfig, OpenApiWrapper};
@@ -231,65 +400,327 @@ where
   ///       .with(RapidocConfig::new(&"/rapidoc")) // with rapidoc feature enable
   ///       .with(RedocConfig::new(&"/redoc")) // with redoc feature enable
   ///       .with(ScalarConfig::new(&"/scalar")) // with scalar feature enable
-  ///       .with(SwaggerUIConfig::new(&"/swagger")), // with swagger-ui feature enable
+  ///       .with(SwaggerUIConfig::new(&"/swagger")) // with swagger-ui feature enable
+  ///       .with_yaml("/openapi.yaml"), // with yaml feature enable
   ///   );
   /// ```
-  #[allow(clippy::unwrap_used, clippy::expect_used)]
+  #[allow(clippy::panic)]
   pub fn build_with(self, openapi_path: &str, config: BuildConfig) -> actix_web::App<T> {
-    let open_api_spec = self.open_api_spec.read().unwrap().clone();
+    match self.try_build_with(openapi_path, config) {
+      Ok(actix_app) => actix_app,
+      Err(e) => panic!("{e}"),
+    }
+  }
 
-    let mut actix_app = self.inner.expect("Missing app");
+  /// Same as [`App::build_with`], returning an error if a document cannot be generated.
+  #[allow(clippy::expect_used)]
+  pub fn try_build_with(mut self, openapi_path: &str, config: BuildConfig) -> Result<actix_web::App<T>, BuildError> {
+    let mut actix_app = self.inner.take().expect("Missing app");
+
+    let mut documents = vec![(&self.document, None, openapi_path.to_owned(), config)];
+    let mut exposed = vec![];
+    while let Some((document, name, openapi_path, mut config)) = documents.pop() {
+      for (name, document_path, document_config) in mem::take(&mut config.documents) {
+        match self.named_documents.get(&name) {
+          Some(named_document) => documents.push((named_document, Some(name), document_path, document_config)),
+          None => warn!("Unknown openapi document {name}, it will not be exposed at {document_path}"),
+        }
+      }
+      exposed.push((
+        document.built_open_api(name.as_deref())?,
+        document.cache_key.as_deref(),
+        openapi_path,
+        config,
+      ));
+    }
+    for (open_api_spec, cache_key, openapi_path, config) in exposed {
+      actix_app = expose(actix_app, &open_api_spec, cache_key, &openapi_path, config);
+    }
+    Ok(actix_app)
+  }
+
+  /// Updates the document the given definition holder belongs to with its definitions and operations.
+  fn update_from_def_holder<D: DefinitionHolder>(&mut self, definition_holder: &mut D) {
+    let document = match definition_holder.document_name().map(ToOwned::to_owned) {
+      None => &mut self.document,
+      Some(name) => match self.named_documents.get_mut(&name) {
+        Some(document) => {
+          document.scoped = true;
+          document
+        }
+        None => {
+          warn!("Unknown openapi document {name}, operations will be documented in the default document");
+          &mut self.document
+        }
+      },
+    };
+    document.update_from_def_holder(definition_holder);
+  }
+}
+
+/// Mount the resources exposing the given openapi specification, as well as the UIs and formats requested by the build config.
+fn expose<T>(
+  mut actix_app: actix_web::App<T>,
+  open_api_spec: &OpenApi,
+  cache_key: Option<&str>,
+  openapi_path: &str,
+  config: BuildConfig,
+) -> actix_web::App<T>
+where
+  T: ServiceFactory<ServiceRequest, Config = (), Error = Error, InitError = ()>,
+{
+  #[cfg(feature = "compression")]
+  let compression = config.compression;
+  #[cfg(not(feature = "compression"))]
+  let compression = false;
+  #[cfg(feature = "yaml")]
+  let format = if config.content_negotiation {
+    SpecFormat::Negotiated
+  } else {
+    SpecFormat::Json
+  };
+  #[cfg(not(feature = "yaml"))]
+  let format = SpecFormat::Json;
+  let handler = |path: &str, format: SpecFormat| match cache_key {
+    Some(key) => cached_handler(key, path, format, compression, || {
+      OASHandler::new(open_api_spec, format, compression)
+    }),
+    None => OASHandler::new(open_api_spec, format, compression),
+  };
+
+  #[cfg(feature = "yaml")]
+  if let Some(yaml_path) = &config.yaml_path {
+    actix_app = actix_app.service(resource(yaml_path).route(get().to(handler(yaml_path, SpecFormat::Yaml))));
+  }
+
+  for plugin in config.ui_plugin_configs {
+    actix_app = actix_app.service(UIPluginWrapper::from(plugin.build(openapi_path)))
+  }
 
-    for plugin in config.ui_plugin_configs {
-      actix_app = actix_app.service(UIPluginWrapper::from(plugin.build(openapi_path)))
+  actix_app.service(resource(openapi_path).route(get().to(handler(openapi_path, format))))
+}
+
+impl From<Spec> for Document {
+  fn from(spec: Spec) -> Self {
+    if spec.cache_key.is_some() {
+      memoize_handler_definitions();
+    }
+    let mut open_api = OpenApi {
+      openapi: spec.openapi_version,
+      info: spec.info,
+      json_schema_dialect: spec.json_schema_dialect,
+      ..Default::default()
+    };
+    if !spec.tags.is_empty() {
+      open_api.tags = spec.tags;
+    }
+    open_api.external_docs = spec.external_docs;
+    if !spec.servers.is_empty() {
+      open_api.servers = spec.servers;
+    }
+    if !spec.default_security.is_empty() {
+      let mut security_schemes = BTreeMap::new();
+      for default_security in spec.default_security {
+        open_api.security.push(default_security.requirement);
+        security_schemes.extend(
+          default_security
+            .security_schemes
+            .into_iter()
+            .map(|(name, scheme)| (name, ReferenceOr::Object(scheme))),
+        );
+      }
+      if !security_schemes.is_empty() {
+        open_api.components = Some(Components {
+          security_schemes,
+          ..Default::default()
+        });
+      }
+    }
+    Self {
+      open_api,
+      default_tags: spec.default_tags,
+      default_parameters: spec.default_parameters,
+      validation: spec.validation,
+      operation_id_strategy: spec
+        .operation_id_strategy
+        .unwrap_or_else(|| Arc::new(HashedOperationId)),
+      transforms: vec![],
+      collapse_any_method_routes: spec.collapse_any_method_routes,
+      transformers: spec.transformers,
+      scoped: false,
+      cached: spec.cache_key.as_deref().and_then(cached_open_api),
+      cache_key: spec.cache_key,
+      routes: Default::default(),
+      handlers: Default::default(),
+    }
+  }
+}
+
+impl Document {
+  /// The document openapi specification, applying documented middlewares and resolving links between its operations, only keeping the components referenced by its operations
+  /// if it was built using [`Scope::document`](crate::web::Scope::document) and converting schemas to the document OAS version.
+  fn open_api(&self) -> OpenApi {
+    self.resolved_open_api().0
+  }
+
+  /// Same as [`Document::open_api`], along with the problems found resolving links.
+  fn resolved_open_api(&self) -> (OpenApi, Vec<ValidationError>) {
+    if let Some(cached) = &self.cached {
+      return (cached.open_api.as_ref().clone(), vec![]);
+    }
+    let mut open_api = self.open_api.clone();
+    for transform in &self.transforms {
+      open_api
+        .paths
+        .paths
+        .values_mut()
+        .flat_map(|pi| pi.operations.values_mut())
+        .for_each(|op| transform.apply(op));
+    }
+    let link_errors = resolve_links(&mut open_api, &self.handlers);
+    if self.scoped {
+      retain_referenced_components(&mut open_api);
+    }
+    if matches!(open_api.openapi, OpenApiVersion::OAS3_1) {
+      visit_open_api(&mut Oas31SchemaVisitor, &mut open_api);
+    }
+    (open_api, link_errors)
+  }
+
+  /// The document openapi specification to serve, shared with the apps built with the same cache key if any.
+  ///
+  /// Fails if the specification cached under the document cache key was generated from other paths or methods.
+  fn built_open_api(&self, name: Option<&str>) -> Result<Arc<OpenApi>, BuildError> {
+    let (key, cached) = match (&self.cache_key, &self.cached) {
+      (None, _) => return Ok(Arc::new(self.validated_open_api(name)?)),
+      (Some(key), Some(cached)) => (key, cached.clone()),
+      (Some(key), None) => (
+        key,
+        cache_open_api(key, self.validated_open_api(name)?, self.routes.clone()),
+      ),
+    };
+    if cached.routes != self.routes {
+      return Err(BuildError::CacheConflict {
+        document: name.map(ToOwned::to_owned),
+        cache_key: key.clone(),
+      });
     }
+    Ok(cached.open_api.clone())
+  }
 
-    actix_app.service(resource(openapi_path).route(get().to(OASHandler::new(open_api_spec))))
+  /// The document openapi specification transformed by the document transformers, problems found validating it being reported according to the document validation level.
+  #[allow(clippy::panic)]
+  fn validated_open_api(&self, name: Option<&str>) -> Result<OpenApi, BuildError> {
+    let (mut open_api, mut errors) = self.resolved_open_api();
+    for transformer in &self.transformers {
+      transformer(&mut open_api).map_err(|error| BuildError::Transformer {
+        document: name.map(ToOwned::to_owned),
+        error,
+      })?;
+    }
+    if self.validation == ValidationLevel::Ignore {
+      return Ok(open_api);
+    }
+    errors.extend(validate(&open_api));
+    if errors.is_empty() {
+      return Ok(open_api);
+    }
+    if self.validation == ValidationLevel::Log {
+      for e in &errors {
+        warn!("Invalid openapi document, {e}");
+      }
+      return Ok(open_api);
+    }
+    let error = BuildError::Invalid {
+      document: name.map(ToOwned::to_owned),
+      errors,
+    };
+    match self.validation {
+      ValidationLevel::Panic => panic!("{error}"),
+      _ => Err(error),
+    }
   }
 
-  /// Updates the underlying spec with definitions and operations from the given definition holder.
-  #[allow(clippy::unwrap_used)]
+  /// Updates the document with definitions and operations from the given definition holder.
   fn update_from_def_holder<D: DefinitionHolder>(&mut self, definition_holder: &mut D) {
-    let mut open_api_spec = self.open_api_spec.write().unwrap();
-    let mut components = definition_holder.components().into_iter().reduce(|mut acc, component| {
-      acc.schemas.extend(component.schemas);
-      acc.responses.extend(component.responses);
-      acc.security_schemes.extend(component.security_schemes);
-      acc
-    });
-    definition_holder.update_path_items(&mut open_api_spec.paths.paths);
+    let mut path_items = IndexMap::new();
+    definition_holder.update_path_items(&mut path_items);
+    let mut handlers: BTreeMap<(String, OperationType), OperationHandler> = definition_holder
+      .handlers()
+      .into_iter()
+      .map(|(path, method, handler)| ((path, method), handler))
+      .collect();
+    for (path, item) in &mut path_items {
+      let methods = document_any_method_operations(&mut item.operations, self.collapse_any_method_routes);
+      if let Some(handler) = handlers.remove(&(path.clone(), any_method())) {
+        handlers.extend(
+          methods
+            .into_iter()
+            .map(|method| ((path.clone(), method), handler.clone())),
+        );
+      }
+    }
+    for (path, item) in &path_items {
+      self
+        .routes
+        .extend(item.operations.keys().map(|op_type| (path.clone(), op_type.clone())));
+    }
+    if self.cached.is_some() {
+      return;
+    }
+    self.add_components(definition_holder.components());
     let mut paths = IndexMap::new();
-    for (path, mut item) in mem::take(&mut open_api_spec.paths.paths) {
-      let path = if path.starts_with('/') {
-        path
+    let mut operation_ids: BTreeSet<String> = self
+      .open_api
+      .paths
+      .paths
+      .values()
+      .chain(path_items.values())
+      .flat_map(|pi| pi.operations.values())
+      .filter_map(|op| op.operation_id.clone())
+      .collect();
+    for (holder_path, mut item) in path_items {
+      let path = if holder_path.starts_with('/') {
+        holder_path.clone()
       } else {
-        "/".to_owned() + &path
+        "/".to_owned() + &holder_path
       };
 
-      item.operations.iter_mut().for_each(|(op_type, op)| {
-        let operation_id = build_operation_id(&path, op_type);
-        op.operation_id = op.operation_id.clone().or(Some(operation_id));
-      });
+      for (op_type, op) in item.operations.iter_mut().filter(|(_, op)| op.operation_id.is_none()) {
+        let handler_name = handlers
+          .get(&(holder_path.clone(), op_type.clone()))
+          .and_then(|handler| handler.name);
+        let operation_id = self
+          .operation_id_strategy
+          .operation_id(&path, op_type, op, handler_name);
+        op.operation_id = Some(unique_operation_id(operation_id, &operation_ids));
+        operation_ids.extend(op.operation_id.clone());
+      }
 
       let sanitized_path = sanitize_patterned_path_parameter(&path);
+      for op_type in item.operations.keys() {
+        if let Some(handler) = handlers.remove(&(holder_path.clone(), op_type.clone())) {
+          self.handlers.push((sanitized_path.clone(), op_type.clone(), handler));
+        }
+      }
       paths.insert(sanitized_path, item);
     }
 
     if !self.default_parameters.is_empty() {
-      let mut parameter_components: BTreeMap<String, ReferenceOr<Parameter>> = self
+      let parameter_components: BTreeMap<String, ReferenceOr<Parameter>> = self
         .default_parameters
         .iter()
         .flat_map(|p| &p.parameters)
         .map(|p| (p.name.clone(), ReferenceOr::Object(p.clone())))
         .collect();
 
-      let mut schema_components: BTreeMap<String, ReferenceOr<Schema>> = self
+      let schema_components: BTreeMap<String, ReferenceOr<Schema>> = self
         .default_parameters
         .iter()
         .flat_map(|p| p.components.clone())
         .collect();
 
-      let mut parameter_refs = self
+      let parameter_refs: Vec<ReferenceOr<Parameter>> = self
         .default_parameters
         .iter()
         .flat_map(|p| &p.parameters)
@@ -301,12 +732,13 @@ where
       paths
         .values_mut()
         .flat_map(|pi| pi.operations.values_mut())
-        .for_each(|op| op.parameters.append(&mut parameter_refs));
+        .for_each(|op| op.parameters.extend(parameter_refs.iter().cloned()));
 
-      if let Some(c) = components.as_mut() {
-        c.parameters.append(&mut parameter_components);
-        c.schemas.append(&mut schema_components);
-      }
+      self.add_components(vec![Components {
+        parameters: parameter_components,
+        schemas: schema_components,
+        ..Default::default()
+      }]);
     }
 
     if !self.default_tags.is_empty() {
@@ -316,11 +748,63 @@ where
         .for_each(|op| op.tags.append(&mut self.default_tags.clone()))
     }
 
-    open_api_spec.paths.paths = paths;
-    open_api_spec.components = components;
+    for (path, item) in paths {
+      let path_item = self.open_api.paths.paths.entry(path).or_default();
+      path_item.operations.extend(item.operations);
+    }
+  }
+
+  /// Adds a webhook to the document from the operations and components of the given route.
+  fn add_webhook(&mut self, name: &str, mut webhook: RouteWrapper) {
+    if self.cached.is_some() {
+      return;
+    }
+    if !matches!(self.open_api.openapi, OpenApiVersion::OAS3_1) {
+      warn!("Webhooks are only supported by OAS 3.1, webhook {name} will be ignored by most OAS 3.0 consumers");
+    }
+    self.add_components(webhook.components());
+    let mut operations = webhook.operations();
+    document_any_method_operations(&mut operations, self.collapse_any_method_routes);
+    match self
+      .open_api
+      .webhooks
+      .entry(name.to_owned())
+      .or_insert_with(|| ReferenceOr::Object(PathItem::default()))
+    {
+      ReferenceOr::Object(path_item) => path_item.operations.extend(operations),
+      ReferenceOr::Reference { .. } => warn!("Webhook {name} is a reference, its operations will not be documented"),
+    }
+  }
+
+  /// Document a middleware wrapping every operation of the document.
+  fn add_transform(&mut self, transform: TransformDocumentation) {
+    self.add_components(transform.components());
+    self.transforms.push(transform);
+  }
+
+  fn add_components(&mut self, components: Vec<Components>) {
+    for component in components {
+      match self.open_api.components.as_mut() {
+        Some(document_components) => merge_components(document_components, component),
+        None => self.open_api.components = Some(component),
+      }
+    }
   }
 }
 
+fn merge_components(components: &mut Components, other: Components) {
+  components.schemas.extend(other.schemas);
+  components.responses.extend(other.responses);
+  components.parameters.extend(other.parameters);
+  components.examples.extend(other.examples);
+  components.request_bodies.extend(other.request_bodies);
+  components.headers.extend(other.headers);
+  components.security_schemes.extend(other.security_schemes);
+  components.links.extend(other.links);
+  components.callbacks.extend(other.callbacks);
+  components.extensions.extend(other.extensions);
+}
+
 #[allow(clippy::expect_used)]
 static PATH_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{(?<name>\S+):(.*)\}").expect("path name regex"));
 
@@ -333,35 +817,31 @@ fn sanitize_patterned_path_parameter(path: &str) -> String {
   path_parts.join("/")
 }
 
-#[allow(clippy::expect_used)]
-static PATH_RESOURCE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/(.*?)/\{(.*?)\}").expect("path template regex"));
-
-fn build_operation_id(path: &str, operation_type: &OperationType) -> String {
-  let resource = PATH_RESOURCE_REGEX
-    .captures(path)
-    .and_then(|c| c.get(1))
-    .map(|_match| _match.as_str())
-    .unwrap_or(path)
-    .trim_matches('/');
-  format!(
-    "{:?}_{}-{:x}",
-    operation_type,
-    resource.replace('/', "-"),
-    md5::compute(path)
-  )
-  .to_lowercase()
+/// Suffix the given operation id with a number if it is already used.
+fn unique_operation_id(operation_id: String, operation_ids: &BTreeSet<String>) -> String {
+  if !operation_ids.contains(&operation_id) {
+    return operation_id;
+  }
+  let mut n = 2;
+  while operation_ids.contains(&format!("{operation_id}_{n}")) {
+    n += 1;
+  }
+  format!("{operation_id}_{n}")
 }
 
 #[cfg(test)]
 mod test {
   #![allow(clippy::expect_used)]
 
-  use crate::app::{build_operation_id, BuildConfig, OpenApiWrapper};
+  use crate::app::{BuildConfig, OpenApiWrapper};
   use crate::spec::Spec;
-  use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
+  #[cfg(feature = "yaml")]
+  use actix_web::http::header::{ACCEPT, CONTENT_TYPE};
+  use actix_web::http::header::{ETAG, IF_NONE_MATCH};
+  use actix_web::http::StatusCode;
+  use actix_web::test::{call_service, init_service, read_body, try_read_body_json, TestRequest};
   use actix_web::App;
   use apistos_models::info::Info;
-  use apistos_models::paths::OperationType;
   use apistos_models::tag::Tag;
   use apistos_models::OpenApi;
   use apistos_rapidoc::RapidocConfig;
@@ -463,6 +943,195 @@ mod test {
     assert!(resp.status().is_success());
   }
 
+  #[cfg(feature = "yaml")]
+  #[actix_web::test]
+  async fn open_api_available_as_yaml() {
+    let openapi_path = "/test.json";
+    let yaml_path = "/test.yaml";
+
+    let app = App::new()
+      .document(Spec::default())
+      .build_with(openapi_path, BuildConfig::default().with_yaml(yaml_path));
+    let app = init_service(app).await;
+
+    let req = TestRequest::get().uri(openapi_path).to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+
+    let req = TestRequest::get().uri(yaml_path).to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+      resp.headers().get(CONTENT_TYPE).and_then(|h| h.to_str().ok()),
+      Some("application/yaml")
+    );
+
+    let body = read_body(resp).await;
+    let body: OpenApi = serde_norway::from_slice(&body).expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+  }
+
+  #[cfg(feature = "yaml")]
+  #[actix_web::test]
+  async fn open_api_format_negotiated() {
+    let openapi_path = "/test.json";
+
+    let app = App::new()
+      .document(Spec::default())
+      .build_with(openapi_path, BuildConfig::default().with_content_negotiation());
+    let app = init_service(app).await;
+
+    let req = TestRequest::get().uri(openapi_path).to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+      resp.headers().get(CONTENT_TYPE).and_then(|h| h.to_str().ok()),
+      Some("application/json")
+    );
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((ACCEPT, "application/yaml"))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+      resp.headers().get(CONTENT_TYPE).and_then(|h| h.to_str().ok()),
+      Some("application/yaml")
+    );
+
+    let body = read_body(resp).await;
+    let body: OpenApi = serde_norway::from_slice(&body).expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+
+    for accept in [
+      "application/yaml;q=0, application/json",
+      "application/yaml;q=0",
+      "application/yaml;q=0.5, application/json",
+      "*/*",
+    ] {
+      let req = TestRequest::get()
+        .uri(openapi_path)
+        .insert_header((ACCEPT, accept))
+        .to_request();
+      let resp = call_service(&app, req).await;
+      assert_eq!(
+        resp.headers().get(CONTENT_TYPE).and_then(|h| h.to_str().ok()),
+        Some("application/json"),
+        "{accept}"
+      );
+    }
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((ACCEPT, "application/json;q=0.5, text/yaml"))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert_eq!(
+      resp.headers().get(CONTENT_TYPE).and_then(|h| h.to_str().ok()),
+      Some("application/yaml")
+    );
+  }
+
+  #[actix_web::test]
+  async fn open_api_not_modified() {
+    let openapi_path = "/test.json";
+
+    let app = App::new().document(Spec::default()).build(openapi_path);
+    let app = init_service(app).await;
+
+    let req = TestRequest::get().uri(openapi_path).to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let etag = resp.headers().get(ETAG).cloned().expect("Missing etag");
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((IF_NONE_MATCH, etag.clone()))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers().get(ETAG), Some(&etag));
+    assert!(read_body(resp).await.is_empty());
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((IF_NONE_MATCH, "\"outdated\""))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+  }
+
+  #[cfg(feature = "compression")]
+  #[actix_web::test]
+  async fn open_api_available_compressed() {
+    use actix_web::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, VARY};
+    use std::io::Read;
+
+    let openapi_path = "/test.json";
+
+    let app = App::new()
+      .document(Spec::default())
+      .build_with(openapi_path, BuildConfig::default().with_compression());
+    let app = init_service(app).await;
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((ACCEPT_ENCODING, "gzip"))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+      resp.headers().get(CONTENT_ENCODING).and_then(|h| h.to_str().ok()),
+      Some("gzip")
+    );
+    assert_eq!(
+      resp.headers().get(VARY).and_then(|h| h.to_str().ok()),
+      Some("Accept-Encoding")
+    );
+    let gzip_etag = resp.headers().get(ETAG).cloned().expect("Missing etag");
+
+    let body = read_body(resp).await;
+    let mut decoded = vec![];
+    flate2::read::GzDecoder::new(body.as_ref())
+      .read_to_end(&mut decoded)
+      .expect("Unable to decode gzip body");
+    let body: OpenApi = serde_json::from_slice(&decoded).expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+
+    let req = TestRequest::get()
+      .uri(openapi_path)
+      .insert_header((ACCEPT_ENCODING, "gzip, br"))
+      .to_request();
+    let resp = call_service(&app, req).await;
+    assert_eq!(
+      resp.headers().get(CONTENT_ENCODING).and_then(|h| h.to_str().ok()),
+      Some("br")
+    );
+    assert_ne!(resp.headers().get(ETAG), Some(&gzip_etag));
+
+    let body = read_body(resp).await;
+    let mut decoded = vec![];
+    brotli::Decompressor::new(body.as_ref(), 4096)
+      .read_to_end(&mut decoded)
+      .expect("Unable to decode brotli body");
+    let body: OpenApi = serde_json::from_slice(&decoded).expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+
+    let req = TestRequest::get().uri(openapi_path).to_request();
+    let resp = call_service(&app, req).await;
+    assert!(resp.headers().get(CONTENT_ENCODING).is_none());
+
+    let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+    assert_eq!(body, OpenApi::default());
+  }
+
   #[actix_web::test]
   async fn multiple_open_api_available() {
     let openapi_path = "/test.json";
@@ -581,16 +1250,4 @@ mod test {
     assert_eq!(body.info, second_info);
     assert_eq!(body.tags, second_tags);
   }
-
-  #[test]
-  fn test_build_operation_id() {
-    let operation_id = build_operation_id("/api/v1/plop/", &OperationType::Get);
-    assert_eq!(operation_id, "get_api-v1-plop-89654e0732d51aafdc164076a57fd663");
-
-    let operation_id = build_operation_id("/api/v1/plap/{test_id}", &OperationType::Get);
-    assert_eq!(operation_id, "get_api-v1-plap-97ba4631a55f77d23b996bf558be60da");
-
-    let operation_id = build_operation_id("/api/v1/plip/{test_id}/test/", &OperationType::Get);
-    assert_eq!(operation_id, "get_api-v1-plip-f5c9e39d7a1acb928c72745f3893bce8")
-  }
 }
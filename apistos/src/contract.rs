@@ -0,0 +1,615 @@
+//! Runtime checks of the documented contract.
+//!
+//! Both middlewares are opt-in and built from the openapi specification of the app:
+//! - [`RequestValidation`] rejects requests whose path parameters, query parameters or json body do not match the schemas of their operation
+//! - [`ResponseContract`] checks responses against the operation documented for their request, e.g. when running in staging or in integration tests
+//!
+//! ```rust,ignore
+//! use actix_web::App;
+//! use apistos::app::OpenApiWrapper;
+//! use apistos::contract::{RequestValidation, ResponseContract};
+//!
+//! let app = App::new().document(todo!()).service(todo!());
+//! let open_api = app.built_open_api().expect("Unable to generate openapi specification");
+//! let contract = ResponseContract::new(open_api.clone()).on_violation(|violation| todo!());
+//! let app = app
+//!   .build("/openapi.json")
+//!   .wrap(RequestValidation::new(open_api))
+//!   .wrap(contract);
+//! ```
+
+use crate::internal::json_schema::{child_pointer, SchemaValidator};
+use crate::internal::operations::{MatchedOperation, OperationMatcher};
+use crate::internal::references::parse_component_reference;
+use actix_service::{Service, Transform};
+use actix_web::body::{to_bytes, BodySize, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::PayloadError;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::{Method, StatusCode};
+use actix_web::web::{self, Bytes, Query};
+use actix_web::{Error, HttpResponse, ResponseError};
+use apistos_models::components::Components;
+use apistos_models::paths::{MediaType, Parameter, ParameterDefinition, ParameterIn, Response, Responses};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::OpenApi;
+use log::warn;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A json value not matching its schema.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SchemaViolation {
+  /// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) to the invalid value, empty for the whole document.
+  pub pointer: String,
+  pub message: String,
+}
+
+impl Display for SchemaViolation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if self.pointer.is_empty() {
+      write!(f, "{}", self.message)
+    } else {
+      write!(f, "{}: {}", self.pointer, self.message)
+    }
+  }
+}
+
+/// Error responded by the [`RequestValidation`] middleware as a `400 Bad Request`, e.g.
+///
+/// ```json
+/// {
+///   "errors": [
+///     { "pointer": "/path/id", "message": "expected integer, found string" },
+///     { "pointer": "/body/name", "message": "string is longer than 8 characters" }
+///   ]
+/// }
+/// ```
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RequestValidationError {
+  /// Invalid values, their pointer being prefixed by the part of the request they were found in: `/path`, `/query` or `/body`.
+  pub errors: Vec<SchemaViolation>,
+}
+
+impl Display for RequestValidationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "request does not match its documented operation:")?;
+    for error in &self.errors {
+      write!(f, "\n  - {error}")?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for RequestValidationError {}
+
+impl ResponseError for RequestValidationError {
+  fn status_code(&self) -> StatusCode {
+    StatusCode::BAD_REQUEST
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    HttpResponse::build(self.status_code()).json(self)
+  }
+}
+
+/// Default limit of the json bodies validated by [`RequestValidation`] and [`ResponseContract`], the default limit of [`JsonConfig`](actix_web::web::JsonConfig).
+const DEFAULT_BODY_LIMIT: usize = 2_097_152;
+
+/// Middleware validating requests against the schemas of their documented operation.
+///
+/// Requests are matched to their operation by method and path, requests without documented operation being passed through.
+/// Path and query parameters are converted to the type of their schema before being validated, json bodies being buffered and validated against the documented schema.
+///
+/// Bodies sent with a content type not documented by the operation are reported as invalid when the body is required,
+/// bodies of documented non-json content types being passed through without validation.
+///
+/// Invalid requests are not passed to the wrapped service, a [`RequestValidationError`] listing every invalid value being responded instead.
+/// Json bodies larger than the [body limit](RequestValidation::with_body_limit) are rejected with a `413 Payload Too Large`.
+#[derive(Clone)]
+pub struct RequestValidation {
+  open_api: Arc<OpenApi>,
+  matcher: Arc<OperationMatcher>,
+  body_limit: usize,
+}
+
+impl RequestValidation {
+  /// Validate requests against the given openapi specification, usually [`App::built_open_api`](crate::app::App::built_open_api).
+  pub fn new(open_api: impl Into<Arc<OpenApi>>) -> Self {
+    let open_api = open_api.into();
+    Self {
+      matcher: Arc::new(OperationMatcher::new(&open_api)),
+      open_api,
+      body_limit: DEFAULT_BODY_LIMIT,
+    }
+  }
+
+  /// Maximum size in bytes of the json bodies buffered to be validated, defaults to 2MiB like [`JsonConfig`](actix_web::web::JsonConfig).
+  ///
+  /// It should match the limit of the `JsonConfig` of the app when it is customized, so that the middleware does not reject bodies its handlers accept.
+  pub fn with_body_limit(mut self, limit: usize) -> Self {
+    self.body_limit = limit;
+    self
+  }
+
+  async fn validate(&self, mut req: ServiceRequest) -> Result<ServiceRequest, (ServiceRequest, Error)> {
+    let path = req.path().to_owned();
+    let Some(operation) = self.matcher.find(&self.open_api, req.method(), &path) else {
+      return Ok(req);
+    };
+    let components = self.open_api.components.as_ref();
+    let validator = SchemaValidator::new(components);
+    let mut errors = parameter_violations(&validator, components, &operation, req.query_string());
+
+    let request_body =
+      operation.operation.request_body.as_ref().and_then(|request_body| {
+        resolve_component(components, request_body, "requestBodies", |c| &c.request_bodies, 0)
+      });
+    if let Some(request_body) = request_body {
+      let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(essence);
+      let media_type = content_type
+        .as_deref()
+        .and_then(|content_type| documented_media_type(&request_body.content, content_type));
+      let schema = content_type
+        .as_deref()
+        .filter(|content_type| is_json(content_type))
+        .and(media_type)
+        .and_then(|media_type| media_type.schema.as_ref());
+      if let Some(schema) = schema {
+        let body = match read_body(&mut req, self.body_limit).await {
+          Ok(body) => body,
+          Err(e) => return Err((req, e)),
+        };
+        let violations = match serde_json::from_slice::<Value>(&body) {
+          Ok(value) => validator.validate(schema, &value),
+          Err(_) if body.is_empty() && request_body.required != Some(true) => vec![],
+          Err(e) => vec![SchemaViolation {
+            pointer: String::new(),
+            message: format!("invalid json: {e}"),
+          }],
+        };
+        errors.extend(violations.into_iter().map(|violation| prefixed(violation, "/body")));
+        req.set_payload(Payload::from(body));
+      } else if request_body.required == Some(true) {
+        match (&content_type, media_type) {
+          (None, _) => errors.push(SchemaViolation {
+            pointer: "/body".to_string(),
+            message: "required body is missing".to_string(),
+          }),
+          (Some(content_type), None) => errors.push(SchemaViolation {
+            pointer: "/body".to_string(),
+            message: format!("content type {content_type} is not documented"),
+          }),
+          (Some(_), Some(_)) => {}
+        }
+      }
+    }
+
+    if errors.is_empty() {
+      Ok(req)
+    } else {
+      Err((req, RequestValidationError { errors }.into()))
+    }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestValidation
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Transform = RequestValidationMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(RequestValidationMiddleware {
+      service: Rc::new(service),
+      validation: self.clone(),
+    }))
+  }
+}
+
+/// Service produced by the [`RequestValidation`] middleware.
+pub struct RequestValidationMiddleware<S> {
+  service: Rc<S>,
+  validation: RequestValidation,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestValidationMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let service = self.service.clone();
+    let validation = self.validation.clone();
+    Box::pin(async move {
+      match validation.validate(req).await {
+        Ok(req) => Ok(service.call(req).await?.map_into_left_body()),
+        Err((req, e)) => Ok(req.error_response(e).map_into_right_body()),
+      }
+    })
+  }
+}
+
+/// A response not matching the documented operation of its request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractViolation {
+  pub method: Method,
+  /// Path of the request.
+  pub path: String,
+  /// Templated path of the documented operation, e.g. `/pets/{id}`.
+  pub operation: String,
+  pub status: StatusCode,
+  pub kind: ContractViolationKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContractViolationKind {
+  /// The status code is documented neither explicitly, nor through a range or a default response.
+  UndocumentedStatus,
+  /// The content type of the response is not documented for its status.
+  UndocumentedContentType(String),
+  /// The json body does not match the documented schema.
+  InvalidBody(Vec<SchemaViolation>),
+}
+
+impl Display for ContractViolation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} {} ({}): ", self.method, self.path, self.operation)?;
+    match &self.kind {
+      ContractViolationKind::UndocumentedStatus => write!(f, "status {} is not documented", self.status),
+      ContractViolationKind::UndocumentedContentType(content_type) => {
+        write!(
+          f,
+          "content type {content_type} is not documented for status {}",
+          self.status
+        )
+      }
+      ContractViolationKind::InvalidBody(violations) => {
+        write!(
+          f,
+          "body does not match the schema documented for status {}:",
+          self.status
+        )?;
+        for violation in violations {
+          write!(f, "\n  - {violation}")?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+type ViolationHandler = Arc<dyn Fn(&ContractViolation) + Send + Sync>;
+
+/// Middleware checking responses against the documented responses of their operation.
+///
+/// Requests are matched to their operation by method and path, requests without documented operation being ignored.
+/// The status of the response must be documented, either explicitly, through a range (e.g. `5XX`) or a default response, as well as its content type if the response documents content.
+/// Json bodies are buffered and validated against the documented schema, unless they are streamed or larger than the
+/// [body limit](ResponseContract::with_body_limit), in which case their schema is not checked.
+///
+/// Every violation is logged as a warning, counted, and passed to the handlers registered with [`ResponseContract::on_violation`]. Responses are never altered.
+#[derive(Clone)]
+pub struct ResponseContract {
+  open_api: Arc<OpenApi>,
+  matcher: Arc<OperationMatcher>,
+  violations: Arc<AtomicU64>,
+  handlers: Vec<ViolationHandler>,
+  body_limit: usize,
+}
+
+impl ResponseContract {
+  /// Check responses against the given openapi specification, usually [`App::built_open_api`](crate::app::App::built_open_api).
+  pub fn new(open_api: impl Into<Arc<OpenApi>>) -> Self {
+    let open_api = open_api.into();
+    Self {
+      matcher: Arc::new(OperationMatcher::new(&open_api)),
+      open_api,
+      violations: Default::default(),
+      handlers: vec![],
+      body_limit: DEFAULT_BODY_LIMIT,
+    }
+  }
+
+  /// Maximum size in bytes of the json bodies to validate, defaults to 2MiB. Larger bodies are passed through without being validated.
+  #[must_use]
+  pub fn with_body_limit(mut self, limit: usize) -> Self {
+    self.body_limit = limit;
+    self
+  }
+
+  /// Call **`handler`** for every violation, in addition to logging it.
+  #[must_use]
+  pub fn on_violation<F>(mut self, handler: F) -> Self
+  where
+    F: Fn(&ContractViolation) + Send + Sync + 'static,
+  {
+    self.handlers.push(Arc::new(handler));
+    self
+  }
+
+  /// Number of violations found so far, shared between the clones of this middleware.
+  pub fn violations(&self) -> u64 {
+    self.violations.load(Ordering::Relaxed)
+  }
+
+  fn report(&self, violation: &ContractViolation) {
+    warn!("{violation}");
+    self.violations.fetch_add(1, Ordering::Relaxed);
+    for handler in &self.handlers {
+      handler(violation);
+    }
+  }
+
+  async fn check(&self, method: Method, path: String, res: ServiceResponse) -> Result<ServiceResponse, Error> {
+    let Some(operation) = self.matcher.find(&self.open_api, &method, &path) else {
+      return Ok(res);
+    };
+    let status = res.status();
+    let violation = |kind| ContractViolation {
+      method: method.clone(),
+      path: path.clone(),
+      operation: operation.path.to_owned(),
+      status,
+      kind,
+    };
+
+    let Some(response) = documented_response(&self.open_api, &operation.operation.responses, status) else {
+      self.report(&violation(ContractViolationKind::UndocumentedStatus));
+      return Ok(res);
+    };
+    let content_type = res
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|content_type| content_type.to_str().ok())
+      .map(essence);
+    let Some(content_type) = content_type.filter(|_| !response.content.is_empty() && method != Method::HEAD) else {
+      return Ok(res);
+    };
+    let Some(media_type) = documented_media_type(&response.content, &content_type) else {
+      self.report(&violation(ContractViolationKind::UndocumentedContentType(content_type)));
+      return Ok(res);
+    };
+    let Some(schema) = media_type.schema.as_ref().filter(|_| is_json(&content_type)) else {
+      return Ok(res);
+    };
+    match res.response().body().size() {
+      BodySize::None => {}
+      BodySize::Sized(size) if size <= self.body_limit as u64 => {}
+      BodySize::Sized(_) | BodySize::Stream => return Ok(res),
+    }
+
+    let (req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let body = to_bytes(body)
+      .await
+      .map_err(actix_web::error::ErrorInternalServerError)?;
+    let violations = match serde_json::from_slice::<Value>(&body) {
+      Ok(value) => SchemaValidator::new(self.open_api.components.as_ref()).validate(schema, &value),
+      Err(e) => vec![SchemaViolation {
+        pointer: String::new(),
+        message: format!("invalid json: {e}"),
+      }],
+    };
+    if !violations.is_empty() {
+      self.report(&violation(ContractViolationKind::InvalidBody(violations)));
+    }
+    Ok(ServiceResponse::new(req, res.set_body(BoxBody::new(body))))
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ResponseContract
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse;
+  type Error = Error;
+  type Transform = ResponseContractMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(ResponseContractMiddleware {
+      service,
+      contract: self.clone(),
+    }))
+  }
+}
+
+/// Service produced by the [`ResponseContract`] middleware.
+pub struct ResponseContractMiddleware<S> {
+  service: S,
+  contract: ResponseContract,
+}
+
+impl<S, B> Service<ServiceRequest> for ResponseContractMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse;
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let method = req.method().clone();
+    let path = req.path().to_owned();
+    let contract = self.contract.clone();
+    let fut = self.service.call(req);
+    Box::pin(async move {
+      let res = fut.await?;
+      contract.check(method, path, res.map_into_boxed_body()).await
+    })
+  }
+}
+
+/// Violations of the path and query parameters of the operation, operation parameters overriding path item parameters with the same name and location.
+fn parameter_violations(
+  validator: &SchemaValidator<'_>,
+  components: Option<&Components>,
+  operation: &MatchedOperation<'_>,
+  query_string: &str,
+) -> Vec<SchemaViolation> {
+  let query = Query::<Vec<(String, String)>>::from_query(query_string)
+    .map(Query::into_inner)
+    .unwrap_or_default();
+  let resolve = |parameter| resolve_component(components, parameter, "parameters", |c| &c.parameters, 0);
+  let operation_parameters: Vec<&Parameter> = operation.operation.parameters.iter().filter_map(resolve).collect();
+  let path_item_parameters = operation
+    .path_item
+    .parameters
+    .iter()
+    .filter_map(resolve)
+    .filter(|parameter| {
+      !operation_parameters
+        .iter()
+        .any(|overriding| overriding.name == parameter.name && overriding._in == parameter._in)
+    });
+
+  let mut violations = vec![];
+  for parameter in operation_parameters.iter().copied().chain(path_item_parameters) {
+    let Some(ParameterDefinition::Schema(schema)) = parameter.definition.as_ref() else {
+      continue;
+    };
+    let (part, values, delimited) = match parameter._in {
+      ParameterIn::Path => (
+        "/path",
+        operation
+          .path_parameters
+          .iter()
+          .filter(|(name, _)| *name == parameter.name)
+          .map(|(_, value)| value.clone())
+          .collect::<Vec<_>>(),
+        true,
+      ),
+      ParameterIn::Query => (
+        "/query",
+        query
+          .iter()
+          .filter(|(name, _)| *name == parameter.name)
+          .map(|(_, value)| value.clone())
+          .collect(),
+        parameter.explode == Some(false),
+      ),
+      ParameterIn::Header | ParameterIn::Cookie => continue,
+    };
+    let pointer = child_pointer(part, &parameter.name);
+    if values.is_empty() {
+      if parameter.required == Some(true) {
+        violations.push(SchemaViolation {
+          pointer,
+          message: "required parameter is missing".to_string(),
+        });
+      }
+      continue;
+    }
+    let value = validator.parameter_value(schema, &values, delimited);
+    violations.extend(
+      validator
+        .validate(schema, &value)
+        .into_iter()
+        .map(|violation| prefixed(violation, &pointer)),
+    );
+  }
+  violations
+}
+
+fn prefixed(violation: SchemaViolation, pointer: &str) -> SchemaViolation {
+  SchemaViolation {
+    pointer: format!("{pointer}{}", violation.pointer),
+    message: violation.message,
+  }
+}
+
+/// Response documented for the given status, looking for the exact status first, then its range (e.g. `5XX`) and finally the default response.
+fn documented_response<'a>(
+  open_api: &'a OpenApi,
+  responses: &'a Responses,
+  status: StatusCode,
+) -> Option<&'a Response> {
+  let code = status.as_str();
+  let range = format!("{}XX", status.as_u16() / 100);
+  let response = responses
+    .responses
+    .get(code)
+    .or_else(|| responses.responses.get(&range))
+    .or_else(|| responses.responses.get(&range.to_lowercase()))
+    .or(responses.default.as_ref())?;
+  resolve_component(open_api.components.as_ref(), response, "responses", |c| &c.responses, 0)
+}
+
+/// Resolve a `#/components/<kind>/<name>` reference, following references to references.
+fn resolve_component<'a, T: Clone>(
+  components: Option<&'a Components>,
+  value: &'a ReferenceOr<T>,
+  kind: &str,
+  declared: fn(&Components) -> &BTreeMap<String, ReferenceOr<T>>,
+  depth: usize,
+) -> Option<&'a T> {
+  match value {
+    ReferenceOr::Object(value) => Some(value),
+    ReferenceOr::Reference { _ref } if depth < 8 => {
+      let (reference_kind, name) = parse_component_reference(_ref)?;
+      if reference_kind != kind {
+        return None;
+      }
+      let value = declared(components?).get(&name)?;
+      resolve_component(components, value, kind, declared, depth + 1)
+    }
+    ReferenceOr::Reference { .. } => None,
+  }
+}
+
+/// Media type documented for the given content type, wildcards such as `*/*` or `image/*` being matched last.
+fn documented_media_type<'a>(content: &'a BTreeMap<String, MediaType>, content_type: &str) -> Option<&'a MediaType> {
+  let documented = || content.iter().map(|(key, media_type)| (essence(key), media_type));
+  let main_type = content_type.split('/').next().unwrap_or_default();
+  documented()
+    .find(|(key, _)| key == content_type)
+    .or_else(|| documented().find(|(key, _)| key.strip_suffix("/*") == Some(main_type)))
+    .or_else(|| documented().find(|(key, _)| key == "*/*"))
+    .map(|(_, media_type)| media_type)
+}
+
+/// Media type without its parameters, e.g. `application/json` for `application/json; charset=utf-8`.
+fn essence(content_type: &str) -> String {
+  content_type.split(';').next().unwrap_or_default().trim().to_lowercase()
+}
+
+/// Buffer the request payload, failing with a `413 Payload Too Large` if it is larger than **`limit`**.
+async fn read_body(req: &mut ServiceRequest, limit: usize) -> Result<Bytes, Error> {
+  let payload = req.extract::<web::Payload>().await?;
+  match payload.to_bytes_limited(limit).await {
+    Ok(body) => body,
+    Err(_) => Err(PayloadError::Overflow.into()),
+  }
+}
+
+fn is_json(content_type: &str) -> bool {
+  content_type == "application/json" || content_type.ends_with("+json")
+}
@@ -0,0 +1,165 @@
+//! Utilities to write a generated openapi specification to disk without starting a server.
+//!
+//! ```rust,ignore
+//! use actix_web::App;
+//! use apistos::app::OpenApiWrapper;
+//! use apistos::export::{to_file, ExportFormat};
+//!
+//! let open_api = App::new()
+//!   .document(todo!())
+//!   .service(todo!())
+//!   .built_open_api()
+//!   .expect("Unable to generate openapi specification");
+//! to_file(&open_api, "openapi.json", ExportFormat::Json).expect("Unable to export openapi specification");
+//! ```
+
+use apistos_models::OpenApi;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::{fs, io};
+
+/// Output format of an exported openapi specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+  Json,
+  #[cfg(feature = "yaml")]
+  Yaml,
+}
+
+impl ExportFormat {
+  /// Guess the export format from the extension of the given path.
+  pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+    let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+      "json" => Some(ExportFormat::Json),
+      #[cfg(feature = "yaml")]
+      "yaml" | "yml" => Some(ExportFormat::Yaml),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+  Io(io::Error),
+  Json(serde_json::Error),
+  #[cfg(feature = "yaml")]
+  Yaml(serde_norway::Error),
+}
+
+impl Display for ExportError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ExportError::Io(e) => write!(f, "unable to write openapi specification: {e}"),
+      ExportError::Json(e) => write!(f, "unable to serialize openapi specification as json: {e}"),
+      #[cfg(feature = "yaml")]
+      ExportError::Yaml(e) => write!(f, "unable to serialize openapi specification as yaml: {e}"),
+    }
+  }
+}
+
+impl std::error::Error for ExportError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      ExportError::Io(e) => Some(e),
+      ExportError::Json(e) => Some(e),
+      #[cfg(feature = "yaml")]
+      ExportError::Yaml(e) => Some(e),
+    }
+  }
+}
+
+impl From<io::Error> for ExportError {
+  fn from(value: io::Error) -> Self {
+    Self::Io(value)
+  }
+}
+
+impl From<serde_json::Error> for ExportError {
+  fn from(value: serde_json::Error) -> Self {
+    Self::Json(value)
+  }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_norway::Error> for ExportError {
+  fn from(value: serde_norway::Error) -> Self {
+    Self::Yaml(value)
+  }
+}
+
+/// Serialize the given openapi specification in the requested format.
+pub fn to_string(open_api: &OpenApi, format: ExportFormat) -> Result<String, ExportError> {
+  match format {
+    ExportFormat::Json => Ok(serde_json::to_string_pretty(open_api)?),
+    #[cfg(feature = "yaml")]
+    ExportFormat::Yaml => Ok(serde_norway::to_string(open_api)?),
+  }
+}
+
+/// Write the given openapi specification at **`path`** in the requested format, creating or truncating the file.
+pub fn to_file<P: AsRef<Path>>(open_api: &OpenApi, path: P, format: ExportFormat) -> Result<(), ExportError> {
+  let content = to_string(open_api, format)?;
+  fs::write(path, content)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::export::{to_file, to_string, ExportFormat};
+  use apistos_models::info::Info;
+  use apistos_models::OpenApi;
+  use std::fs;
+
+  fn open_api() -> OpenApi {
+    OpenApi {
+      info: Info {
+        title: "An exported API".to_string(),
+        version: "1.0.0".to_string(),
+        ..Default::default()
+      },
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn export_format_from_path() {
+    assert_eq!(ExportFormat::from_path("openapi.json"), Some(ExportFormat::Json));
+    assert_eq!(ExportFormat::from_path("openapi.JSON"), Some(ExportFormat::Json));
+    assert_eq!(ExportFormat::from_path("openapi.txt"), None);
+    assert_eq!(ExportFormat::from_path("openapi"), None);
+    #[cfg(feature = "yaml")]
+    assert_eq!(ExportFormat::from_path("openapi.yml"), Some(ExportFormat::Yaml));
+  }
+
+  #[test]
+  fn export_json_file() {
+    let path = std::env::temp_dir().join("apistos_export_json_file.json");
+
+    to_file(&open_api(), &path, ExportFormat::Json).expect("Unable to export openapi");
+
+    let content = fs::read_to_string(&path).expect("Unable to read exported file");
+    let exported: OpenApi = serde_json::from_str(&content).expect("Unable to parse exported file");
+    assert_eq!(exported, open_api());
+    fs::remove_file(path).expect("Unable to remove exported file");
+  }
+
+  #[cfg(feature = "yaml")]
+  #[test]
+  fn export_yaml() {
+    let content = to_string(&open_api(), ExportFormat::Yaml).expect("Unable to export openapi");
+
+    let exported: OpenApi = serde_norway::from_str(&content).expect("Unable to parse exported yaml");
+    assert_eq!(exported, open_api());
+  }
+
+  #[test]
+  fn export_json() {
+    let content = to_string(&open_api(), ExportFormat::Json).expect("Unable to export openapi");
+
+    let exported: OpenApi = serde_json::from_str(&content).expect("Unable to parse exported json");
+    assert_eq!(exported, open_api());
+  }
+}
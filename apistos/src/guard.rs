@@ -0,0 +1,222 @@
+//! Guards documenting the constraints they put on requests, to be registered using `guard_documented` on a [`Route`](crate::web::Route),
+//! a [`Resource`](crate::web::Resource) or a [`Scope`](crate::web::Scope).
+//!
+//! ```rust,ignore
+//! use apistos::guard;
+//! use apistos::web::{get, resource};
+//!
+//! resource("/pets")
+//!   .guard_documented(guard::header("X-Api-Version", "2"))
+//!   .route(get().to(get_pets));
+//! ```
+
+use crate::internal::actix::transform::add_parameters;
+use actix_web::guard::{Guard, GuardContext};
+use actix_web::http::header::CONTENT_TYPE;
+use apistos_models::paths::{Operation, Parameter, ParameterDefinition, ParameterIn};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::server::Server;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Describes the constraints a guard puts on requests.
+pub trait DocumentedGuard {
+  /// Parameters required by the guard, added to every guarded operation unless the operation already declares a parameter with the same name and location.
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![]
+  }
+
+  /// Content type request bodies of guarded operations are restricted to.
+  ///
+  /// Only the media type documented for this content type is kept. Request bodies which do not document it are left
+  /// without content, which is reported as [`ValidationErrorKind::MissingRequestBodyContent`](crate::validation::ValidationErrorKind::MissingRequestBodyContent).
+  fn content_type(&self) -> Option<String> {
+    None
+  }
+
+  /// Servers guarded operations are restricted to.
+  ///
+  /// As guards are combined, operations guarded by several guards only keep the servers allowed by every one of them.
+  fn servers(&self) -> Vec<Server> {
+    vec![]
+  }
+}
+
+/// Guard matching requests with the given header value, documented as a required header parameter.
+pub struct HeaderGuard {
+  name: &'static str,
+  value: &'static str,
+}
+
+/// Documented [`actix_web::guard::Header`](https://docs.rs/actix-web/*/actix_web/guard/fn.Header.html).
+pub fn header(name: &'static str, value: &'static str) -> HeaderGuard {
+  HeaderGuard { name, value }
+}
+
+impl Guard for HeaderGuard {
+  fn check(&self, ctx: &GuardContext<'_>) -> bool {
+    ctx
+      .head()
+      .headers()
+      .get(self.name)
+      .is_some_and(|value| value.as_bytes() == self.value.as_bytes())
+  }
+}
+
+impl DocumentedGuard for HeaderGuard {
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![constant_parameter(self.name, ParameterIn::Header, self.value)]
+  }
+}
+
+/// Guard matching requests with the given query parameter value, documented as a required query parameter.
+pub struct QueryGuard {
+  name: String,
+  value: String,
+}
+
+/// Guard matching requests whose query string contains **`name`** with the given **`value`**.
+pub fn query(name: &str, value: &str) -> QueryGuard {
+  QueryGuard {
+    name: name.to_owned(),
+    value: value.to_owned(),
+  }
+}
+
+impl Guard for QueryGuard {
+  fn check(&self, ctx: &GuardContext<'_>) -> bool {
+    let query = ctx.head().uri.query().unwrap_or_default();
+    actix_web::web::Query::<BTreeMap<String, String>>::from_query(query)
+      .map(|params| params.get(&self.name) == Some(&self.value))
+      .unwrap_or_default()
+  }
+}
+
+impl DocumentedGuard for QueryGuard {
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![constant_parameter(&self.name, ParameterIn::Query, &self.value)]
+  }
+}
+
+/// Guard matching requests with the given content type, documented by restricting request bodies to this content type.
+pub struct ContentTypeGuard {
+  content_type: String,
+}
+
+/// Guard matching requests whose `Content-Type` header essence is **`content_type`**, for example `application/json`.
+pub fn content_type(content_type: &str) -> ContentTypeGuard {
+  ContentTypeGuard {
+    content_type: content_type.to_owned(),
+  }
+}
+
+impl Guard for ContentTypeGuard {
+  fn check(&self, ctx: &GuardContext<'_>) -> bool {
+    ctx
+      .head()
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|h| h.to_str().ok())
+      .and_then(|h| h.split(';').next())
+      .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(&self.content_type))
+  }
+}
+
+impl DocumentedGuard for ContentTypeGuard {
+  fn content_type(&self) -> Option<String> {
+    Some(self.content_type.clone())
+  }
+}
+
+/// Guard matching requests for the given host, documented as a server.
+pub struct HostGuard {
+  host: String,
+  scheme: Option<String>,
+  inner: actix_web::guard::HostGuard,
+}
+
+/// Documented [`actix_web::guard::Host`](https://docs.rs/actix-web/*/actix_web/guard/fn.Host.html).
+pub fn host(host: &str) -> HostGuard {
+  HostGuard {
+    host: host.to_owned(),
+    scheme: None,
+    inner: actix_web::guard::Host(host),
+  }
+}
+
+impl HostGuard {
+  /// Also match the request scheme, documented servers using `https` otherwise.
+  pub fn scheme(mut self, scheme: &str) -> Self {
+    self.scheme = Some(scheme.to_owned());
+    self.inner = self.inner.scheme(scheme);
+    self
+  }
+}
+
+impl Guard for HostGuard {
+  fn check(&self, ctx: &GuardContext<'_>) -> bool {
+    self.inner.check(ctx)
+  }
+}
+
+impl DocumentedGuard for HostGuard {
+  fn servers(&self) -> Vec<Server> {
+    vec![Server {
+      url: format!("{}://{}", self.scheme.as_deref().unwrap_or("https"), self.host),
+      ..Default::default()
+    }]
+  }
+}
+
+fn constant_parameter(name: &str, _in: ParameterIn, value: &str) -> Parameter {
+  Parameter {
+    name: name.to_owned(),
+    _in,
+    required: Some(true),
+    definition: Some(ParameterDefinition::Schema(ReferenceOr::Object(Schema::Object(
+      SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(vec![Value::String(value.to_owned())]),
+        ..Default::default()
+      },
+    )))),
+    ..Default::default()
+  }
+}
+
+/// Snapshot of the documentation of a guard implementing [`DocumentedGuard`].
+#[derive(Clone, Default)]
+pub(crate) struct GuardDocumentation {
+  parameters: Vec<Parameter>,
+  content_type: Option<String>,
+  servers: Vec<Server>,
+}
+
+impl GuardDocumentation {
+  pub(crate) fn new<G: DocumentedGuard>(guard: &G) -> Self {
+    Self {
+      parameters: guard.parameters(),
+      content_type: guard.content_type(),
+      servers: guard.servers(),
+    }
+  }
+
+  pub(crate) fn apply(&self, operation: &mut Operation) {
+    add_parameters(operation, &self.parameters);
+    if let Some(content_type) = &self.content_type {
+      if let Some(ReferenceOr::Object(request_body)) = operation.request_body.as_mut() {
+        request_body.content.retain(|media_type, _| media_type == content_type);
+      }
+    }
+    if !self.servers.is_empty() {
+      if operation.servers.is_empty() {
+        operation.servers = self.servers.clone();
+      } else {
+        operation
+          .servers
+          .retain(|server| self.servers.iter().any(|s| s.url == server.url));
+      }
+    }
+  }
+}
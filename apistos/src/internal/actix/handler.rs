@@ -1,21 +1,236 @@
-use actix_web::{Error, HttpResponse};
+#[cfg(feature = "yaml")]
+use actix_web::http::header::{Accept, Quality};
+#[cfg(feature = "compression")]
+use actix_web::http::header::{AcceptEncoding, ContentEncoding, Encoding, CONTENT_ENCODING};
+use actix_web::http::header::{EntityTag, IfNoneMatch, CONTENT_TYPE, ETAG, VARY};
+use actix_web::web::Bytes;
+use actix_web::{Error, HttpMessage, HttpRequest, HttpResponse};
 use apistos_models::OpenApi;
+use log::warn;
 use std::future::{ready, Ready};
+use std::sync::Arc;
 
+/// Format used to serve the generated openapi specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum SpecFormat {
+  Json,
+  #[cfg(feature = "yaml")]
+  Yaml,
+  /// Serve YAML when the request `Accept` header asks for it, JSON otherwise.
+  #[cfg(feature = "yaml")]
+  Negotiated,
+}
+
+#[cfg(feature = "yaml")]
+const YAML_MEDIA_TYPES: &[&str] = &["application/yaml", "application/x-yaml", "text/yaml", "text/x-yaml"];
+
+impl SpecFormat {
+  #[cfg_attr(not(feature = "yaml"), allow(unused_variables))]
+  fn resolve(self, req: &HttpRequest) -> Self {
+    match self {
+      #[cfg(feature = "yaml")]
+      SpecFormat::Negotiated => {
+        // media types explicitly refused with `q=0` are ignored, the most preferred remaining one being served
+        let preferred = req.get_header::<Accept>().and_then(|accept| {
+          Accept(
+            accept
+              .0
+              .into_iter()
+              .filter(|item| item.quality > Quality::ZERO)
+              .collect(),
+          )
+          .ranked()
+          .into_iter()
+          .next()
+        });
+        match preferred {
+          Some(mime) if YAML_MEDIA_TYPES.contains(&mime.essence_str()) => SpecFormat::Yaml,
+          _ => SpecFormat::Json,
+        }
+      }
+      format => format,
+    }
+  }
+
+  fn serves(self, format: SpecFormat) -> bool {
+    match self {
+      #[cfg(feature = "yaml")]
+      SpecFormat::Negotiated => true,
+      served => served == format,
+    }
+  }
+}
+
+/// The openapi specification serialized in a given format when building the app, along with its compressed bodies.
+struct Representation {
+  content_type: &'static str,
+  body: Bytes,
+  /// Hash of the uncompressed body, used to build the `ETag` of each encoding.
+  hash: String,
+  #[cfg(feature = "compression")]
+  gzip: Option<Bytes>,
+  #[cfg(feature = "compression")]
+  brotli: Option<Bytes>,
+}
+
+impl Representation {
+  #[cfg_attr(not(feature = "compression"), allow(unused_variables))]
+  fn new(content_type: &'static str, body: Vec<u8>, compression: bool) -> Self {
+    Self {
+      content_type,
+      hash: format!("{:x}", md5::compute(&body)),
+      #[cfg(feature = "compression")]
+      gzip: compression.then(|| gzip(&body)).flatten(),
+      #[cfg(feature = "compression")]
+      brotli: compression.then(|| brotli(&body)).flatten(),
+      body: Bytes::from(body),
+    }
+  }
+
+  /// The body to send for the request, along with its content encoding if it is compressed.
+  #[cfg_attr(not(feature = "compression"), allow(unused_variables))]
+  fn encoded(&self, req: &HttpRequest) -> (Option<&'static str>, Bytes) {
+    #[cfg(feature = "compression")]
+    if let Some(accept_encoding) = req.get_header::<AcceptEncoding>() {
+      let mut supported = vec![Encoding::identity()];
+      if self.brotli.is_some() {
+        supported.push(Encoding::brotli());
+      }
+      if self.gzip.is_some() {
+        supported.push(Encoding::gzip());
+      }
+      match (accept_encoding.negotiate(supported.iter()), &self.brotli, &self.gzip) {
+        (Some(Encoding::Known(ContentEncoding::Brotli)), Some(brotli), _) => return (Some("br"), brotli.clone()),
+        (Some(Encoding::Known(ContentEncoding::Gzip)), _, Some(gzip)) => return (Some("gzip"), gzip.clone()),
+        _ => {}
+      }
+    }
+    (None, self.body.clone())
+  }
+
+  fn respond(&self, req: &HttpRequest, vary: Option<&str>) -> HttpResponse {
+    let (encoding, body) = self.encoded(req);
+    let etag = EntityTag::new_strong(match encoding {
+      Some(encoding) => format!("{}-{encoding}", self.hash),
+      None => self.hash.clone(),
+    });
+
+    let not_modified = match req.get_header::<IfNoneMatch>() {
+      Some(IfNoneMatch::Any) => true,
+      Some(IfNoneMatch::Items(etags)) => etags.iter().any(|e| e.weak_eq(&etag)),
+      None => false,
+    };
+    let mut response = if not_modified {
+      HttpResponse::NotModified()
+    } else {
+      HttpResponse::Ok()
+    };
+    response.insert_header((ETAG, etag.to_string()));
+    if let Some(vary) = vary {
+      response.insert_header((VARY, vary.to_owned()));
+    }
+    if not_modified {
+      return response.finish();
+    }
+    #[cfg(feature = "compression")]
+    if let Some(encoding) = encoding {
+      response.insert_header((CONTENT_ENCODING, encoding));
+    }
+    response.insert_header((CONTENT_TYPE, self.content_type)).body(body)
+  }
+}
+
+#[cfg(feature = "compression")]
+fn gzip(body: &[u8]) -> Option<Bytes> {
+  use std::io::Write;
+
+  let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+  encoder
+    .write_all(body)
+    .and_then(|_| encoder.finish())
+    .map(Bytes::from)
+    .map_err(|e| warn!("Unable to gzip openapi specification, it will be served uncompressed: {e}"))
+    .ok()
+}
+
+#[cfg(feature = "compression")]
+fn brotli(body: &[u8]) -> Option<Bytes> {
+  use std::io::Write;
+
+  let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+  encoder
+    .write_all(body)
+    .and_then(|_| encoder.flush())
+    .map(|_| Bytes::from(encoder.into_inner()))
+    .map_err(|e| warn!("Unable to brotli compress openapi specification, it will be served uncompressed: {e}"))
+    .ok()
+}
+
+/// Serves the openapi specification, serialized and compressed once when building the app.
 #[derive(Clone)]
-pub(crate) struct OASHandler(OpenApi);
+pub(crate) struct OASHandler {
+  json: Option<Arc<Representation>>,
+  #[cfg(feature = "yaml")]
+  yaml: Option<Arc<Representation>>,
+  format: SpecFormat,
+  vary: Option<String>,
+}
 
 impl OASHandler {
-  pub(crate) fn new(open_api: OpenApi) -> Self {
-    Self(open_api)
+  pub(crate) fn new(open_api: &OpenApi, format: SpecFormat, compression: bool) -> Self {
+    let json = format
+      .serves(SpecFormat::Json)
+      .then(|| {
+        serde_json::to_vec(open_api)
+          .map_err(|e| warn!("Unable to serialize openapi specification as json: {e}"))
+          .ok()
+      })
+      .flatten()
+      .map(|body| Arc::new(Representation::new("application/json", body, compression)));
+    #[cfg(feature = "yaml")]
+    let yaml = format
+      .serves(SpecFormat::Yaml)
+      .then(|| {
+        serde_norway::to_string(open_api)
+          .map_err(|e| warn!("Unable to serialize openapi specification as yaml: {e}"))
+          .ok()
+      })
+      .flatten()
+      .map(|body| Arc::new(Representation::new("application/yaml", body.into_bytes(), compression)));
+
+    let mut vary = vec![];
+    #[cfg(feature = "yaml")]
+    if format == SpecFormat::Negotiated {
+      vary.push("Accept");
+    }
+    if compression {
+      vary.push("Accept-Encoding");
+    }
+
+    Self {
+      json,
+      #[cfg(feature = "yaml")]
+      yaml,
+      format,
+      vary: (!vary.is_empty()).then(|| vary.join(", ")),
+    }
   }
 }
 
-impl actix_web::Handler<()> for OASHandler {
+impl actix_web::Handler<(HttpRequest,)> for OASHandler {
   type Output = Result<HttpResponse, Error>;
   type Future = Ready<Self::Output>;
 
-  fn call(&self, _: ()) -> Self::Future {
-    ready(Ok(HttpResponse::Ok().json(self.0.clone())))
+  fn call(&self, (req,): (HttpRequest,)) -> Self::Future {
+    let representation = match self.format.resolve(&req) {
+      SpecFormat::Json => self.json.as_ref(),
+      #[cfg(feature = "yaml")]
+      SpecFormat::Yaml | SpecFormat::Negotiated => self.yaml.as_ref(),
+    };
+    ready(
+      representation
+        .map(|representation| representation.respond(&req, self.vary.as_deref()))
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("Unable to serialize openapi specification")),
+    )
   }
 }
@@ -5,6 +5,7 @@ pub(crate) mod resource;
 pub(crate) mod route;
 pub(crate) mod scope;
 pub(crate) mod service_config;
+pub(crate) mod transform;
 
 mod utils;
 
@@ -16,4 +17,8 @@ const METHODS: &[OperationType] = &[
   OperationType::Options,
   OperationType::Head,
   OperationType::Patch,
+  OperationType::Trace,
 ];
+
+/// Extension listing the methods accepted by an operation documenting a route accepting any method.
+const METHODS_EXTENSION: &str = "x-methods";
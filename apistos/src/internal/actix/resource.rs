@@ -1,6 +1,9 @@
-use crate::internal::actix::route::{Route, RouteWrapper};
+use crate::guard::{DocumentedGuard, GuardDocumentation};
+use crate::internal::actix::route::{any_method, Route, RouteWrapper};
+use crate::internal::actix::transform::{DocumentedTransform, TransformDocumentation};
 use crate::internal::actix::utils::OperationUpdater;
-use crate::internal::actix::METHODS;
+use crate::internal::cache::handler_definitions;
+use crate::internal::links::{OperationHandler, OperationHandlers};
 use actix_service::{ServiceFactory, Transform};
 use actix_web::body::MessageBody;
 use actix_web::dev::{AppService, HttpServiceFactory, ServiceRequest, ServiceResponse};
@@ -16,6 +19,9 @@ pub struct Resource<R = actix_web::Resource> {
   pub(crate) path: String,
   pub(crate) item_definition: Option<PathItem>,
   pub(crate) components: Vec<Components>,
+  pub(crate) handlers: OperationHandlers,
+  pub(crate) transforms: Vec<TransformDocumentation>,
+  pub(crate) guards: Vec<GuardDocumentation>,
   tags: Vec<String>,
   inner: R,
 }
@@ -27,6 +33,9 @@ impl Resource {
       path: path.to_owned(),
       item_definition: None,
       components: Default::default(),
+      handlers: Default::default(),
+      transforms: Default::default(),
+      guards: Default::default(),
       tags: Default::default(),
       inner: actix_web::Resource::new(path),
     }
@@ -39,6 +48,9 @@ impl Resource {
       path: path.to_owned(),
       item_definition: None,
       components: Default::default(),
+      handlers: Default::default(),
+      transforms: Default::default(),
+      guards: Default::default(),
       tags: tags.into_iter().map(Into::into).collect(),
       inner: actix_web::Resource::new(path),
     }
@@ -70,12 +82,20 @@ where
 
   /// Proxy for [`actix_web::Resource::guard`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.guard).
   ///
-  /// **NOTE:** This doesn't affect spec generation.
+  /// **NOTE:** This doesn't affect spec generation, see [`Resource::guard_documented`].
   pub fn guard<G: Guard + 'static>(mut self, guard: G) -> Self {
     self.inner = self.inner.guard(guard);
     self
   }
 
+  /// Wrapper for [`actix_web::Resource::guard`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.guard)
+  /// documenting the constraints the guard puts on requests using its [`DocumentedGuard`] implementation.
+  pub fn guard_documented<G: Guard + DocumentedGuard + 'static>(mut self, guard: G) -> Self {
+    self.guards.push(GuardDocumentation::new(&guard));
+    self.inner = self.inner.guard(guard);
+    self
+  }
+
   /// Wrapper for [`actix_web::Resource::route`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.route).
   pub fn route(mut self, route: Route) -> Self {
     let w = RouteWrapper::new(&self.path, route);
@@ -87,6 +107,7 @@ where
     item_definition.operations.extend(operations);
     self.item_definition = Some(item_definition);
     self.components.extend(w.component);
+    self.handlers.extend(w.handlers);
     self.inner = self.inner.route(w.inner);
     self
   }
@@ -109,15 +130,16 @@ where
     F::Future: PathItemDefinition,
   {
     if F::Future::is_visible() {
-      let mut operation = F::Future::operation();
+      let (mut operation, components) = handler_definitions::<F, F::Future>();
       operation.tags.append(&mut self.tags.clone());
-      let mut item_definition = self.item_definition.unwrap_or_default();
-      for method in METHODS {
-        item_definition.operations.insert(method.clone(), operation.clone());
-      }
       operation.update_path_parameter_name_from_path(&self.path);
+      let mut item_definition = self.item_definition.unwrap_or_default();
+      item_definition.operations.insert(any_method(), operation);
       self.item_definition = Some(item_definition);
-      self.components.extend(F::Future::components());
+      self.components.extend(components);
+      self
+        .handlers
+        .push((self.path.clone(), any_method(), OperationHandler::new::<F, F::Future>()));
     }
     self.inner = self.inner.to(handler);
     self
@@ -125,7 +147,8 @@ where
 
   /// Proxy for [`actix_web::web::Resource::wrap`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.wrap).
   ///
-  /// **NOTE:** This doesn't affect spec generation.
+  /// **NOTE:** This doesn't affect spec generation, even if the middleware implements [`DocumentedTransform`]: its parameters, responses,
+  /// security requirements and tags are only documented when it is registered using [`Resource::wrap_documented`].
   pub fn wrap<M, B>(
     self,
     mw: M,
@@ -142,11 +165,36 @@ where
       path: self.path,
       item_definition: self.item_definition,
       components: self.components,
+      handlers: self.handlers,
+      transforms: self.transforms,
+      guards: self.guards,
       tags: self.tags,
       inner: self.inner.wrap(mw),
     }
   }
 
+  /// Wrapper for [`actix_web::web::Resource::wrap`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.wrap)
+  /// documenting what the middleware adds to the operations of this resource using its [`DocumentedTransform`] implementation.
+  pub fn wrap_documented<M, B>(
+    self,
+    mw: M,
+  ) -> Resource<
+    actix_web::Resource<
+      impl ServiceFactory<ServiceRequest, Config = (), Response = ServiceResponse<B>, Error = Error, InitError = ()>,
+    >,
+  >
+  where
+    B: MessageBody,
+    M: Transform<T::Service, ServiceRequest, Response = ServiceResponse<B>, Error = Error, InitError = ()>
+      + DocumentedTransform
+      + 'static,
+  {
+    let documentation = TransformDocumentation::new(&mw);
+    let mut resource = self.wrap(mw);
+    resource.transforms.push(documentation);
+    resource
+  }
+
   /// Proxy for [`actix_web::web::Resource::wrap_fn`](https://docs.rs/actix-web/*/actix_web/struct.Resource.html#method.wrap_fn).
   ///
   /// **NOTE:** This doesn't affect spec generation.
@@ -167,6 +215,9 @@ where
       path: self.path,
       item_definition: self.item_definition,
       components: self.components,
+      handlers: self.handlers,
+      transforms: self.transforms,
+      guards: self.guards,
       tags: self.tags,
       inner: self.inner.wrap_fn(mw),
     }
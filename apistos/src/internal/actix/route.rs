@@ -1,5 +1,8 @@
+use crate::guard::{DocumentedGuard, GuardDocumentation};
 use crate::internal::actix::utils::OperationUpdater;
-use crate::internal::actix::METHODS;
+use crate::internal::actix::{METHODS, METHODS_EXTENSION};
+use crate::internal::cache::handler_definitions;
+use crate::internal::links::{OperationHandler, OperationHandlers};
 use actix_service::ServiceFactory;
 use actix_web::dev::ServiceRequest;
 use actix_web::guard::Guard;
@@ -9,7 +12,7 @@ use apistos_core::PathItemDefinition;
 use apistos_models::components::Components;
 use apistos_models::paths::{Operation, OperationType, PathItem};
 use indexmap::IndexMap;
-use log::warn;
+use serde_json::Value;
 
 /// Wrapper for [`actix_web::web::method`](https://docs.rs/actix-web/*/actix_web/web/fn.method.html).
 pub fn method(method: Method) -> Route {
@@ -51,16 +54,22 @@ pub fn head() -> Route {
   method(Method::HEAD)
 }
 
+/// Wrapper for [`actix_web::web::trace`](https://docs.rs/actix-web/*/actix_web/web/fn.trace.html).
+pub fn trace() -> Route {
+  method(Method::TRACE)
+}
+
 pub enum OperationTypeDoc {
   OperationType(OperationType),
   AllMethods,
-  Undocumented,
 }
 
 pub struct Route {
   operation: Option<Operation>,
+  handler: Option<OperationHandler>,
   path_item_type: OperationTypeDoc,
   components: Vec<Components>,
+  guards: Vec<GuardDocumentation>,
   inner: actix_web::Route,
 }
 
@@ -85,8 +94,10 @@ impl Route {
   pub fn new() -> Route {
     Route {
       operation: None,
+      handler: None,
       path_item_type: OperationTypeDoc::AllMethods,
       components: Default::default(),
+      guards: Default::default(),
       inner: actix_web::Route::new(),
     }
   }
@@ -102,10 +113,7 @@ impl Route {
       "PATCH" => OperationTypeDoc::OperationType(OperationType::Patch),
       "TRACE" => OperationTypeDoc::OperationType(OperationType::Trace),
       "GET" => OperationTypeDoc::OperationType(OperationType::Get),
-      m => {
-        warn!("Unsupported method found: {m}, operation will not be documented");
-        OperationTypeDoc::Undocumented
-      }
+      m => OperationTypeDoc::OperationType(OperationType::Extension(m.to_lowercase())),
     };
     self.path_item_type = path_item_type;
     self.inner = self.inner.method(method);
@@ -114,12 +122,20 @@ impl Route {
 
   /// Proxy for [`actix_web::Route::guard`](https://docs.rs/actix-web/*/actix_web/struct.Route.html#method.guard).
   ///
-  /// **NOTE:** This doesn't affect spec generation.
+  /// **NOTE:** This doesn't affect spec generation, see [`Route::guard_documented`].
   pub fn guard<G: Guard + 'static>(mut self, guard: G) -> Self {
     self.inner = self.inner.guard(guard);
     self
   }
 
+  /// Wrapper for [`actix_web::Route::guard`](https://docs.rs/actix-web/*/actix_web/struct.Route.html#method.guard)
+  /// documenting the constraints the guard puts on requests using its [`DocumentedGuard`] implementation.
+  pub fn guard_documented<G: Guard + DocumentedGuard + 'static>(mut self, guard: G) -> Self {
+    self.guards.push(GuardDocumentation::new(&guard));
+    self.inner = self.inner.guard(guard);
+    self
+  }
+
   /// Wrapper for [`actix_web::Route::to`](https://docs.rs/actix-web/*/actix_web/struct.Route.html#method.to)
   pub fn to<F, Args>(mut self, handler: F) -> Self
   where
@@ -129,8 +145,10 @@ impl Route {
     F::Future: PathItemDefinition,
   {
     if F::Future::is_visible() {
-      self.operation = Some(F::Future::operation());
-      self.components = F::Future::components();
+      let (operation, components) = handler_definitions::<F, F::Future>();
+      self.operation = Some(operation);
+      self.handler = Some(OperationHandler::new::<F, F::Future>());
+      self.components = components;
     }
     self.inner = self.inner.to(handler);
     self
@@ -145,35 +163,83 @@ pub(crate) struct PathDefinition {
 pub(crate) struct RouteWrapper {
   pub(crate) def: PathDefinition,
   pub(crate) component: Vec<Components>,
+  pub(crate) handlers: OperationHandlers,
   pub(crate) inner: actix_web::Route,
 }
 
 impl RouteWrapper {
   pub(crate) fn new<S: Into<String>>(path: S, route: Route) -> Self {
     let mut operations: IndexMap<OperationType, Operation> = Default::default();
+    let mut handlers = vec![];
     let mut path_item = PathItem::default();
     let path: String = path.into();
     if let Some(mut operation) = route.operation {
       operation.update_path_parameter_name_from_path(&path);
-
-      match route.path_item_type {
-        OperationTypeDoc::OperationType(path_item_type) => {
-          operations.insert(path_item_type, operation);
-        }
-        OperationTypeDoc::AllMethods => {
-          for path_item_type in METHODS {
-            operations.insert(path_item_type.clone(), operation.clone());
-          }
-        }
-        OperationTypeDoc::Undocumented => {}
+      for guard in &route.guards {
+        guard.apply(&mut operation);
       }
+
+      let operation_type = match route.path_item_type {
+        OperationTypeDoc::OperationType(path_item_type) => path_item_type,
+        OperationTypeDoc::AllMethods => any_method(),
+      };
+      handlers.extend(
+        route
+          .handler
+          .map(|handler| (path.clone(), operation_type.clone(), handler)),
+      );
+      operations.insert(operation_type, operation);
     }
     path_item.operations = operations;
 
     Self {
       def: PathDefinition { path, item: path_item },
       component: route.components,
+      handlers,
       inner: route.inner,
     }
   }
 }
+
+/// Replace the operation documenting a route accepting any method by a copy for each method not documented by another
+/// route, explicit operation ids being suffixed by the method to keep them unique.
+///
+/// When **`collapse`** is set, a single operation is documented for the first of those methods instead, listing all of
+/// them in an `x-methods` extension.
+///
+/// Returns the methods the operation is documented for.
+pub(crate) fn document_any_method_operations(
+  operations: &mut IndexMap<OperationType, Operation>,
+  collapse: bool,
+) -> Vec<OperationType> {
+  let Some(any_method_operation) = operations.shift_remove(&any_method()) else {
+    return vec![];
+  };
+  let methods: Vec<&OperationType> = METHODS.iter().filter(|m| !operations.contains_key(*m)).collect();
+  if collapse {
+    if let Some(first_method) = methods.first() {
+      let mut operation = any_method_operation;
+      operation.extensions.insert(
+        METHODS_EXTENSION.to_owned(),
+        Value::Array(methods.iter().map(|m| Value::String(m.to_string())).collect()),
+      );
+      operations.insert((*first_method).clone(), operation);
+      return vec![(*first_method).clone()];
+    }
+    return vec![];
+  }
+  for method in &methods {
+    let mut operation = any_method_operation.clone();
+    operation.operation_id = operation
+      .operation_id
+      .map(|operation_id| format!("{operation_id}_{method}"));
+    operations.insert((*method).clone(), operation);
+  }
+  methods.into_iter().cloned().collect()
+}
+
+/// Placeholder method of operations documenting a route accepting any method, until the document replaces them using
+/// [`document_any_method_operations`].
+pub(crate) fn any_method() -> OperationType {
+  OperationType::Extension("*".to_owned())
+}
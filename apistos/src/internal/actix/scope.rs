@@ -1,7 +1,11 @@
+use crate::guard::{DocumentedGuard, GuardDocumentation};
 use crate::internal::actix::route::{Route, RouteWrapper};
 use crate::internal::actix::service_config::ServiceConfig;
+use crate::internal::actix::transform::{DocumentedTransform, TransformDocumentation};
 use crate::internal::actix::utils::OperationUpdater;
 use crate::internal::definition_holder::DefinitionHolder;
+use crate::internal::links::OperationHandlers;
+use crate::spec::DefaultSecurity;
 use actix_service::{ServiceFactory, Transform};
 use actix_web::body::MessageBody;
 use actix_web::dev::{AppService, HttpServiceFactory, ServiceRequest, ServiceResponse};
@@ -17,6 +21,11 @@ use std::future::Future;
 pub struct Scope<S = actix_web::Scope> {
   pub(crate) item_map: BTreeMap<String, PathItem>,
   pub(crate) components: Vec<Components>,
+  pub(crate) handlers: OperationHandlers,
+  pub(crate) document: Option<String>,
+  pub(crate) security: Vec<DefaultSecurity>,
+  pub(crate) transforms: Vec<TransformDocumentation>,
+  pub(crate) guards: Vec<GuardDocumentation>,
   tags: Vec<String>,
   path: String,
   inner: Option<S>,
@@ -28,6 +37,11 @@ impl Scope {
     Scope {
       item_map: Default::default(),
       components: Default::default(),
+      handlers: Default::default(),
+      document: Default::default(),
+      security: Default::default(),
+      transforms: Default::default(),
+      guards: Default::default(),
       tags: Default::default(),
       path: path.into(),
       inner: Some(actix_web::Scope::new(path)),
@@ -40,6 +54,11 @@ impl Scope {
     Scope {
       item_map: Default::default(),
       components: Default::default(),
+      handlers: Default::default(),
+      document: Default::default(),
+      security: Default::default(),
+      transforms: Default::default(),
+      guards: Default::default(),
       tags: tags.into_iter().map(Into::into).collect(),
       path: path.into(),
       inner: Some(actix_web::Scope::new(path)),
@@ -64,14 +83,44 @@ impl<T> Scope<actix_web::Scope<T>>
 where
   T: ServiceFactory<ServiceRequest, Config = (), Error = Error, InitError = ()>,
 {
+  /// Document the operations of this scope in the openapi document declared with [`App::named_document`](crate::app::App::named_document) under **`name`** instead of the default one.
+  ///
+  /// Only scopes registered directly on the app can be assigned to a document: registering such a scope in another scope or through a
+  /// [`ServiceConfig`] panics, as its operations would silently be documented along with the ones of its parent.
+  pub fn document(mut self, name: &str) -> Self {
+    self.document = Some(name.to_owned());
+    self
+  }
+
+  /// Document **`security`** as an alternative security requirement of every operation of this scope which does not declare its own,
+  /// overriding the default security of the `Spec`. Associated security schemes are added to the document components.
+  pub fn security(mut self, security: DefaultSecurity) -> Self {
+    self.security.push(security);
+    self
+  }
+
+  /// Document every operation of this scope which does not declare its own security as accessible without authentication,
+  /// emitting an empty security requirement. Combined with [`Scope::security`], this makes security optional.
+  pub fn public(self) -> Self {
+    self.security(DefaultSecurity::public())
+  }
+
   /// Proxy for [`actix_web::Scope::guard`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.guard).
   ///
-  /// **NOTE:** This doesn't affect spec generation.
+  /// **NOTE:** This doesn't affect spec generation, see [`Scope::guard_documented`].
   pub fn guard<G: Guard + 'static>(mut self, guard: G) -> Self {
     self.inner = self.inner.take().map(|s| s.guard(guard));
     self
   }
 
+  /// Wrapper for [`actix_web::Scope::guard`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.guard)
+  /// documenting the constraints the guard puts on requests using its [`DocumentedGuard`] implementation.
+  pub fn guard_documented<G: Guard + DocumentedGuard + 'static>(mut self, guard: G) -> Self {
+    self.guards.push(GuardDocumentation::new(&guard));
+    self.inner = self.inner.take().map(|s| s.guard(guard));
+    self
+  }
+
   /// Proxy for [`actix_web::Scope::app_data`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.data).
   ///
   /// **NOTE:** This doesn't affect spec generation.
@@ -96,6 +145,8 @@ where
   }
 
   /// Wrapper for [`actix_web::Scope::service`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.service).
+  ///
+  /// Panics if **`factory`** is a scope assigned to a document with [`Scope::document`].
   pub fn service<F>(mut self, mut factory: F) -> Self
   where
     F: DefinitionHolder + HttpServiceFactory + 'static,
@@ -128,7 +179,8 @@ where
 
   /// Proxy for [`actix_web::web::Scope::wrap`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.wrap).
   ///
-  /// **NOTE:** This doesn't affect spec generation.
+  /// **NOTE:** This doesn't affect spec generation, even if the middleware implements [`DocumentedTransform`]: its parameters, responses,
+  /// security requirements and tags are only documented when it is registered using [`Scope::wrap_documented`].
   pub fn wrap<M, B>(
     mut self,
     mw: M,
@@ -144,12 +196,39 @@ where
     Scope {
       item_map: self.item_map,
       components: self.components,
+      handlers: self.handlers,
+      document: self.document,
+      security: self.security,
+      transforms: self.transforms,
+      guards: self.guards,
       tags: self.tags,
       path: self.path,
       inner: self.inner.take().map(|s| s.wrap(mw)),
     }
   }
 
+  /// Wrapper for [`actix_web::web::Scope::wrap`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.wrap)
+  /// documenting what the middleware adds to the operations of this scope using its [`DocumentedTransform`] implementation.
+  pub fn wrap_documented<M, B>(
+    self,
+    mw: M,
+  ) -> Scope<
+    actix_web::Scope<
+      impl ServiceFactory<ServiceRequest, Config = (), Response = ServiceResponse<B>, Error = Error, InitError = ()>,
+    >,
+  >
+  where
+    M: Transform<T::Service, ServiceRequest, Response = ServiceResponse<B>, Error = Error, InitError = ()>
+      + DocumentedTransform
+      + 'static,
+    B: MessageBody,
+  {
+    let documentation = TransformDocumentation::new(&mw);
+    let mut scope = self.wrap(mw);
+    scope.transforms.push(documentation);
+    scope
+  }
+
   /// Proxy for [`actix_web::web::Scope::wrap_fn`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.wrap_fn).
   ///
   /// **NOTE:** This doesn't affect spec generation.
@@ -168,6 +247,11 @@ where
     Scope {
       item_map: self.item_map,
       components: self.components,
+      handlers: self.handlers,
+      document: self.document,
+      security: self.security,
+      transforms: self.transforms,
+      guards: self.guards,
       tags: self.tags,
       path: self.path,
       inner: self.inner.take().map(|s| s.wrap_fn(mw)),
@@ -175,16 +259,18 @@ where
   }
 
   fn update_from_def_holder<D: DefinitionHolder>(&mut self, dh: &mut D) {
+    reject_nested_document(dh);
     self.components.extend(dh.components());
+    let handlers: OperationHandlers = dh
+      .handlers()
+      .into_iter()
+      .map(|(path, method, handler)| (self.scoped_path(path), method, handler))
+      .collect();
+    self.handlers.extend(handlers);
     let mut item_map = IndexMap::new();
     dh.update_path_items(&mut item_map);
     for (path, mut path_item) in item_map {
-      let p = [self.path.clone(), path]
-        .iter()
-        .filter(|p| !p.is_empty())
-        .map(|p| p.trim_start_matches('/'))
-        .collect::<Vec<&str>>()
-        .join("/");
+      let p = self.scoped_path(path);
 
       for operation in path_item.operations.values_mut() {
         operation.update_path_parameter_name_from_path(&p);
@@ -194,6 +280,24 @@ where
       self.item_map.insert(p, path_item);
     }
   }
+
+  /// The given path of a service of this scope, prefixed by the scope path.
+  fn scoped_path(&self, path: String) -> String {
+    [self.path.clone(), path]
+      .iter()
+      .filter(|p| !p.is_empty())
+      .map(|p| p.trim_start_matches('/'))
+      .collect::<Vec<&str>>()
+      .join("/")
+  }
+}
+
+/// Panics if the given definition holder is a scope assigned to a document with [`Scope::document`], which is only supported on scopes registered directly on the app.
+#[allow(clippy::panic)]
+pub(crate) fn reject_nested_document<D: DefinitionHolder>(dh: &D) {
+  if let Some(name) = dh.document_name() {
+    panic!("Scope assigned to openapi document {name} must be registered directly on the app");
+  }
 }
 
 /// Wrapper for [`actix_web::web::scope`](https://docs.rs/actix-web/*/actix_web/web/fn.scope.html).
@@ -1,5 +1,7 @@
 use crate::internal::actix::route::{Route, RouteWrapper};
+use crate::internal::actix::scope::reject_nested_document;
 use crate::internal::definition_holder::DefinitionHolder;
+use crate::internal::links::OperationHandlers;
 use actix_web::dev::HttpServiceFactory;
 use apistos_models::components::Components;
 use apistos_models::paths::PathItem;
@@ -8,6 +10,7 @@ use indexmap::IndexMap;
 pub struct ServiceConfig<'a> {
   pub(crate) item_map: IndexMap<String, PathItem>,
   pub(crate) components: Vec<Components>,
+  pub(crate) handlers: OperationHandlers,
   inner: &'a mut actix_web::web::ServiceConfig,
 }
 
@@ -16,6 +19,7 @@ impl<'a> From<&'a mut actix_web::web::ServiceConfig> for ServiceConfig<'a> {
     ServiceConfig {
       item_map: Default::default(),
       components: Default::default(),
+      handlers: Default::default(),
       inner: cfg,
     }
   }
@@ -27,17 +31,22 @@ impl<'a> ServiceConfig<'a> {
     let mut w = RouteWrapper::new(path, route);
     w.update_path_items(&mut self.item_map);
     self.components.extend(w.components());
+    self.handlers.extend(w.handlers());
     self.inner.route(path, w.inner);
     self
   }
 
   /// Wrapper for [`actix_web::web::ServiceConfig::service`](https://docs.rs/actix-web/*/actix_web/web/struct.ServiceConfig.html#method.service).
+  ///
+  /// Panics if **`factory`** is a scope assigned to a document with [`Scope::document`](crate::web::Scope::document).
   pub fn service<F>(&mut self, mut factory: F) -> &mut Self
   where
     F: DefinitionHolder + HttpServiceFactory + 'static,
   {
+    reject_nested_document(&factory);
     factory.update_path_items(&mut self.item_map);
     self.components.extend(factory.components());
+    self.handlers.extend(factory.handlers());
     self.inner.service(factory);
     self
   }
@@ -0,0 +1,133 @@
+use crate::spec::DefaultSecurity;
+use apistos_models::components::Components;
+use apistos_models::paths::{Operation, Parameter, Response};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::security::SecurityRequirement;
+use std::collections::BTreeMap;
+
+/// Describes what a middleware adds to the operations it wraps when registered using `wrap_documented` on an [`App`](crate::app::App),
+/// a [`Scope`](crate::web::Scope) or a [`Resource`](crate::web::Resource).
+///
+/// Middlewares registered using plain `wrap` are never documented, whether they implement this trait or not.
+///
+/// ```rust,ignore
+/// use apistos::spec::{DefaultSecurity, DefaultSecurityAccessor};
+/// use apistos::web::DocumentedTransform;
+///
+/// impl DocumentedTransform for AuthMiddleware {
+///   fn security(&self) -> Vec<DefaultSecurity> {
+///     vec![ApiKey::get_default_security()]
+///   }
+///
+///   fn responses(&self) -> BTreeMap<String, ReferenceOr<Response>> {
+///     BTreeMap::from_iter(vec![(
+///       "401".to_string(),
+///       ReferenceOr::Object(Response {
+///         description: "Unauthorized".to_string(),
+///         ..Default::default()
+///       }),
+///     )])
+///   }
+/// }
+/// ```
+pub trait DocumentedTransform {
+  /// Parameters added to every wrapped operation, unless the operation already declares a parameter with the same name and location.
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![]
+  }
+
+  /// Responses added to every wrapped operation, by status code, unless the operation already documents this status code.
+  fn responses(&self) -> BTreeMap<String, ReferenceOr<Response>> {
+    BTreeMap::new()
+  }
+
+  /// Alternative security requirements applying to every wrapped operation which does not declare its own. Their security schemes are added to the document components.
+  fn security(&self) -> Vec<DefaultSecurity> {
+    vec![]
+  }
+
+  /// Tags added to every wrapped operation.
+  fn tags(&self) -> Vec<String> {
+    vec![]
+  }
+
+  /// Components referenced by the parameters and responses of this middleware.
+  fn components(&self) -> Vec<Components> {
+    vec![]
+  }
+}
+
+/// Snapshot of the documentation of a middleware implementing [`DocumentedTransform`].
+#[derive(Clone, Default)]
+pub(crate) struct TransformDocumentation {
+  parameters: Vec<Parameter>,
+  responses: BTreeMap<String, ReferenceOr<Response>>,
+  security: Vec<SecurityRequirement>,
+  tags: Vec<String>,
+  components: Vec<Components>,
+}
+
+impl TransformDocumentation {
+  pub(crate) fn new<M: DocumentedTransform>(middleware: &M) -> Self {
+    let security = middleware.security();
+    let mut components = middleware.components();
+    let security_schemes: BTreeMap<_, _> = security
+      .iter()
+      .flat_map(|s| s.security_schemes.clone())
+      .map(|(name, scheme)| (name, ReferenceOr::Object(scheme)))
+      .collect();
+    if !security_schemes.is_empty() {
+      components.push(Components {
+        security_schemes,
+        ..Default::default()
+      });
+    }
+    Self {
+      parameters: middleware.parameters(),
+      responses: middleware.responses(),
+      security: security.into_iter().map(|s| s.requirement).collect(),
+      tags: middleware.tags(),
+      components,
+    }
+  }
+
+  pub(crate) fn components(&self) -> Vec<Components> {
+    self.components.clone()
+  }
+
+  pub(crate) fn apply(&self, operation: &mut Operation) {
+    add_parameters(operation, &self.parameters);
+    for (status, response) in &self.responses {
+      operation
+        .responses
+        .responses
+        .entry(status.clone())
+        .or_insert_with(|| response.clone());
+    }
+    if operation.security.is_empty() {
+      operation.security.clone_from(&self.security);
+    }
+    for tag in &self.tags {
+      if !operation.tags.contains(tag) {
+        operation.tags.push(tag.clone());
+      }
+    }
+  }
+}
+
+/// Add the given parameters to the operation, unless it already declares a parameter with the same name and location.
+pub(crate) fn add_parameters(operation: &mut Operation, parameters: &[Parameter]) {
+  for parameter in parameters {
+    let declared = operation
+      .parameters
+      .iter()
+      .filter_map(|p| match p {
+        ReferenceOr::Object(p) => Some(p),
+        ReferenceOr::Reference { .. } => None,
+      })
+      .any(|p| p.name == parameter.name && p._in == parameter._in);
+    if !declared {
+      operation.parameters.push(ReferenceOr::Object(parameter.clone()));
+    }
+  }
+}
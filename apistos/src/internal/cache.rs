@@ -0,0 +1,136 @@
+//! Process-wide cache of generated specifications, shared by the apps documented with the same [`Spec::cache_key`](crate::spec::Spec::cache_key),
+//! and of the definitions of handlers, shared by the routes using them once an app is documented with a cache key.
+//!
+//! Entries are never evicted, [`clear_cache`](crate::spec::clear_cache) empties the whole cache.
+
+use crate::internal::actix::handler::{OASHandler, SpecFormat};
+use apistos_core::PathItemDefinition;
+use apistos_models::components::Components;
+use apistos_models::paths::{Operation, OperationType};
+use apistos_models::OpenApi;
+use once_cell::sync::Lazy;
+use std::any::TypeId;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+/// Paths and methods of the operations documented by an app, before any transformer is applied.
+pub(crate) type DocumentedRoutes = BTreeSet<(String, OperationType)>;
+
+/// A specification cached along with the routes it was generated from.
+pub(crate) struct CachedOpenApi {
+  pub(crate) open_api: Arc<OpenApi>,
+  pub(crate) routes: DocumentedRoutes,
+}
+
+static SPECIFICATIONS: Lazy<Mutex<HashMap<String, Arc<CachedOpenApi>>>> = Lazy::new(Default::default);
+
+/// Cache key, openapi path, format and compression of a handler serving a cached specification.
+type HandlerKey = (String, String, SpecFormat, bool);
+
+static HANDLERS: Lazy<Mutex<HashMap<HandlerKey, OASHandler>>> = Lazy::new(Default::default);
+
+/// The specification cached under **`key`**, if an app documented with this key was already built.
+pub(crate) fn cached_open_api(key: &str) -> Option<Arc<CachedOpenApi>> {
+  SPECIFICATIONS
+    .lock()
+    .unwrap_or_else(PoisonError::into_inner)
+    .get(key)
+    .cloned()
+}
+
+/// Cache the specification generated from **`routes`** under **`key`**, returning the specification cached by another app if any.
+pub(crate) fn cache_open_api(key: &str, open_api: OpenApi, routes: DocumentedRoutes) -> Arc<CachedOpenApi> {
+  SPECIFICATIONS
+    .lock()
+    .unwrap_or_else(PoisonError::into_inner)
+    .entry(key.to_owned())
+    .or_insert_with(|| {
+      Arc::new(CachedOpenApi {
+        open_api: Arc::new(open_api),
+        routes,
+      })
+    })
+    .clone()
+}
+
+/// The handler serving the specification cached under **`key`** at **`openapi_path`**, only serializing it for the first app built.
+pub(crate) fn cached_handler(
+  key: &str,
+  openapi_path: &str,
+  format: SpecFormat,
+  compression: bool,
+  handler: impl FnOnce() -> OASHandler,
+) -> OASHandler {
+  HANDLERS
+    .lock()
+    .unwrap_or_else(PoisonError::into_inner)
+    .entry((key.to_owned(), openapi_path.to_owned(), format, compression))
+    .or_insert_with(handler)
+    .clone()
+}
+
+/// Operation and components documented for a handler.
+type HandlerDefinitions = (Operation, Vec<Components>);
+
+static HANDLER_DEFINITIONS: Lazy<RwLock<HashMap<TypeId, HandlerDefinitions>>> = Lazy::new(Default::default);
+
+/// Whether handler definitions are memoized, which is only the case once an app is documented with a cache key.
+static MEMOIZE_HANDLER_DEFINITIONS: AtomicBool = AtomicBool::new(false);
+
+/// Memoize handler definitions from now on, called when an app is documented with a cache key.
+pub(crate) fn memoize_handler_definitions() {
+  MEMOIZE_HANDLER_DEFINITIONS.store(true, Ordering::Relaxed);
+}
+
+/// Operation and components documented by **`D`** for handler **`F`**.
+///
+/// Once an app is documented with a cache key, they are only generated for the first route using this handler in the process.
+pub(crate) fn handler_definitions<F: 'static, D: PathItemDefinition>() -> HandlerDefinitions {
+  if !MEMOIZE_HANDLER_DEFINITIONS.load(Ordering::Relaxed) {
+    return (D::operation(), D::components());
+  }
+
+  let type_id = TypeId::of::<F>();
+  if let Some(definitions) = HANDLER_DEFINITIONS
+    .read()
+    .unwrap_or_else(PoisonError::into_inner)
+    .get(&type_id)
+  {
+    return definitions.clone();
+  }
+
+  let definitions = (D::operation(), D::components());
+  HANDLER_DEFINITIONS
+    .write()
+    .unwrap_or_else(PoisonError::into_inner)
+    .entry(type_id)
+    .or_insert(definitions)
+    .clone()
+}
+
+/// Remove every cached specification, handler and handler definition, handler definitions not being memoized anymore until an app is documented with a cache key.
+pub(crate) fn clear() {
+  MEMOIZE_HANDLER_DEFINITIONS.store(false, Ordering::Relaxed);
+  SPECIFICATIONS.lock().unwrap_or_else(PoisonError::into_inner).clear();
+  HANDLERS.lock().unwrap_or_else(PoisonError::into_inner).clear();
+  HANDLER_DEFINITIONS
+    .write()
+    .unwrap_or_else(PoisonError::into_inner)
+    .clear();
+}
+
+#[cfg(test)]
+mod test {
+  use crate::internal::cache::{cache_open_api, cached_open_api, clear};
+  use apistos_models::OpenApi;
+
+  #[test]
+  fn cache_cleared() {
+    cache_open_api("clear_test", OpenApi::default(), Default::default());
+    assert!(cached_open_api("clear_test").is_some());
+
+    clear();
+    assert!(cached_open_api("clear_test").is_none());
+  }
+}
@@ -2,15 +2,28 @@ use crate::internal::actix::resource::Resource;
 use crate::internal::actix::route::RouteWrapper;
 use crate::internal::actix::scope::Scope;
 use crate::internal::actix::service_config::ServiceConfig;
+use crate::internal::actix::transform::TransformDocumentation;
+use crate::internal::links::OperationHandlers;
 use apistos_models::components::Components;
 use apistos_models::paths::{Operation, OperationType, PathItem};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::security::{SecurityRequirement, SecurityScheme};
 use indexmap::IndexMap;
+use std::collections::BTreeMap;
 use std::mem;
 
 pub trait DefinitionHolder {
   fn path(&self) -> &str;
   fn operations(&mut self) -> IndexMap<OperationType, Operation>;
   fn components(&mut self) -> Vec<Components>;
+  /// Name of the openapi document the definitions should be added to, the default document being used if `None`.
+  fn document_name(&self) -> Option<&str> {
+    None
+  }
+  /// Handlers documented by the operations of this holder, by path and method as added by [`DefinitionHolder::update_path_items`].
+  fn handlers(&mut self) -> OperationHandlers {
+    vec![]
+  }
   fn update_path_items(&mut self, path_op_map: &mut IndexMap<String, PathItem>) {
     let ops = self.operations();
     if !ops.is_empty() {
@@ -32,6 +45,10 @@ impl DefinitionHolder for RouteWrapper {
   fn components(&mut self) -> Vec<Components> {
     mem::take(&mut self.component)
   }
+
+  fn handlers(&mut self) -> OperationHandlers {
+    mem::take(&mut self.handlers)
+  }
 }
 
 impl<T> DefinitionHolder for Resource<T> {
@@ -40,11 +57,24 @@ impl<T> DefinitionHolder for Resource<T> {
   }
 
   fn operations(&mut self) -> IndexMap<OperationType, Operation> {
-    mem::take(&mut self.item_definition).unwrap_or_default().operations
+    let mut operations = mem::take(&mut self.item_definition).unwrap_or_default().operations;
+    for guard in &self.guards {
+      operations.values_mut().for_each(|op| guard.apply(op));
+    }
+    for transform in &self.transforms {
+      operations.values_mut().for_each(|op| transform.apply(op));
+    }
+    operations
   }
 
   fn components(&mut self) -> Vec<Components> {
-    mem::take(&mut self.components)
+    let mut components = mem::take(&mut self.components);
+    components.extend(self.transforms.iter().flat_map(TransformDocumentation::components));
+    components
+  }
+
+  fn handlers(&mut self) -> OperationHandlers {
+    mem::take(&mut self.handlers)
   }
 }
 
@@ -59,11 +89,47 @@ impl<T> DefinitionHolder for Scope<T> {
   }
 
   fn components(&mut self) -> Vec<Components> {
-    mem::take(&mut self.components)
+    let mut components = mem::take(&mut self.components);
+    components.extend(self.transforms.iter().flat_map(TransformDocumentation::components));
+    let security_schemes: BTreeMap<String, ReferenceOr<SecurityScheme>> = self
+      .security
+      .iter()
+      .flat_map(|s| s.security_schemes.clone())
+      .map(|(name, scheme)| (name, ReferenceOr::Object(scheme)))
+      .collect();
+    if !security_schemes.is_empty() {
+      components.push(Components {
+        security_schemes,
+        ..Default::default()
+      });
+    }
+    components
+  }
+
+  fn document_name(&self) -> Option<&str> {
+    self.document.as_deref()
+  }
+
+  fn handlers(&mut self) -> OperationHandlers {
+    mem::take(&mut self.handlers)
   }
 
   fn update_path_items(&mut self, path_op_map: &mut IndexMap<String, PathItem>) {
-    for (path, item) in mem::take(&mut self.item_map) {
+    let security: Vec<SecurityRequirement> = self.security.iter().map(|s| s.requirement.clone()).collect();
+    for (path, mut item) in mem::take(&mut self.item_map) {
+      if !security.is_empty() {
+        item
+          .operations
+          .values_mut()
+          .filter(|op| op.security.is_empty())
+          .for_each(|op| op.security.clone_from(&security));
+      }
+      for guard in &self.guards {
+        item.operations.values_mut().for_each(|op| guard.apply(op));
+      }
+      for transform in &self.transforms {
+        item.operations.values_mut().for_each(|op| transform.apply(op));
+      }
       let op_map = path_op_map.entry(path).or_default();
       op_map.operations.extend(item.operations.into_iter());
     }
@@ -84,6 +150,10 @@ impl<'a> DefinitionHolder for ServiceConfig<'a> {
     mem::take(&mut self.components)
   }
 
+  fn handlers(&mut self) -> OperationHandlers {
+    mem::take(&mut self.handlers)
+  }
+
   fn update_path_items(&mut self, path_op_map: &mut IndexMap<String, PathItem>) {
     for (path, item) in mem::take(&mut self.item_map) {
       let op_map = path_op_map.entry(path).or_default();
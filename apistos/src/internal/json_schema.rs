@@ -0,0 +1,571 @@
+use crate::contract::SchemaViolation;
+use crate::internal::references::parse_component_reference;
+use apistos_models::components::Components;
+use apistos_models::reference_or::ReferenceOr;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+/// Maximum number of nested references followed, guarding against schemas referencing themselves without nesting.
+const MAX_DEPTH: usize = 64;
+
+static PATTERNS: Lazy<RwLock<HashMap<String, Option<Regex>>>> = Lazy::new(Default::default);
+
+/// Validates json values against the schemas of an openapi document.
+///
+/// Only the keywords generated by schemars are checked, `format` being ignored. References to unknown components are considered valid.
+pub(crate) struct SchemaValidator<'a> {
+  schemas: Option<&'a BTreeMap<String, ReferenceOr<Schema>>>,
+}
+
+impl<'a> SchemaValidator<'a> {
+  pub(crate) fn new(components: Option<&'a Components>) -> Self {
+    Self {
+      schemas: components.map(|components| &components.schemas),
+    }
+  }
+
+  /// Every violation of the given schema by the given value, scoped by their json pointer.
+  pub(crate) fn validate(&self, schema: &ReferenceOr<Schema>, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = vec![];
+    match schema {
+      ReferenceOr::Object(schema) => self.schema(schema, value, "", 0, &mut violations),
+      ReferenceOr::Reference { _ref } => self.reference(_ref, value, "", 0, &mut violations),
+    }
+    violations
+  }
+
+  /// Convert the raw values of a parameter, e.g. from a query string, to the json value described by its schema.
+  ///
+  /// Array items are either given as multiple values, or as a single comma separated value when **`delimited`** is set.
+  /// Values which cannot be converted are kept as strings, so that validating them reports the type mismatch.
+  pub(crate) fn parameter_value(&self, schema: &ReferenceOr<Schema>, values: &[String], delimited: bool) -> Value {
+    let schema = match schema {
+      ReferenceOr::Object(schema) => self.resolve(schema, 0),
+      ReferenceOr::Reference { _ref } => self.resolve_reference(_ref, 0),
+    };
+    let items = schema
+      .filter(|schema| has_instance_type(schema, InstanceType::Array))
+      .map(
+        |schema| match schema.array.as_deref().and_then(|array| array.items.as_ref()) {
+          Some(SingleOrVec::Single(items)) => self.resolve(items, 0),
+          _ => None,
+        },
+      );
+    match items {
+      Some(items) if delimited => Value::Array(
+        values
+          .iter()
+          .flat_map(|value| value.split(','))
+          .map(|value| scalar_value(items, value))
+          .collect(),
+      ),
+      Some(items) => Value::Array(values.iter().map(|value| scalar_value(items, value)).collect()),
+      None => values
+        .first()
+        .map(|value| scalar_value(schema, value))
+        .unwrap_or(Value::Null),
+    }
+  }
+
+  fn resolve<'s>(&self, schema: &'s Schema, depth: usize) -> Option<&'s SchemaObject>
+  where
+    'a: 's,
+  {
+    match schema {
+      Schema::Object(SchemaObject {
+        reference: Some(reference),
+        ..
+      }) => self.resolve_reference(reference, depth),
+      Schema::Object(schema) => Some(schema),
+      Schema::Bool(_) => None,
+    }
+  }
+
+  fn resolve_reference(&self, reference: &str, depth: usize) -> Option<&'a SchemaObject> {
+    if depth >= MAX_DEPTH {
+      return None;
+    }
+    let (kind, name) = parse_component_reference(reference)?;
+    if kind != "schemas" {
+      return None;
+    }
+    match self.schemas?.get(&name)? {
+      ReferenceOr::Object(schema) => self.resolve(schema, depth + 1),
+      ReferenceOr::Reference { _ref } => self.resolve_reference(_ref, depth + 1),
+    }
+  }
+
+  fn is_valid(&self, schema: &Schema, value: &Value, depth: usize) -> bool {
+    let mut violations = vec![];
+    self.schema(schema, value, "", depth, &mut violations);
+    violations.is_empty()
+  }
+
+  fn reference(
+    &self,
+    reference: &str,
+    value: &Value,
+    pointer: &str,
+    depth: usize,
+    violations: &mut Vec<SchemaViolation>,
+  ) {
+    if depth >= MAX_DEPTH {
+      return;
+    }
+    let schema = parse_component_reference(reference)
+      .filter(|(kind, _)| *kind == "schemas")
+      .and_then(|(_, name)| self.schemas?.get(&name));
+    match schema {
+      Some(ReferenceOr::Object(schema)) => self.schema(schema, value, pointer, depth + 1, violations),
+      Some(ReferenceOr::Reference { _ref }) => self.reference(_ref, value, pointer, depth + 1, violations),
+      None => {}
+    }
+  }
+
+  fn schema(&self, schema: &Schema, value: &Value, pointer: &str, depth: usize, violations: &mut Vec<SchemaViolation>) {
+    match schema {
+      Schema::Bool(true) => {}
+      Schema::Bool(false) => violations.push(violation(pointer, "no value is allowed".to_string())),
+      Schema::Object(SchemaObject {
+        reference: Some(reference),
+        ..
+      }) => self.reference(reference, value, pointer, depth, violations),
+      Schema::Object(schema) => self.schema_object(schema, value, pointer, depth, violations),
+    }
+  }
+
+  fn schema_object(
+    &self,
+    schema: &SchemaObject,
+    value: &Value,
+    pointer: &str,
+    depth: usize,
+    violations: &mut Vec<SchemaViolation>,
+  ) {
+    if value.is_null() && schema.extensions.get("nullable") == Some(&Value::Bool(true)) {
+      return;
+    }
+
+    if let Some(instance_type) = schema.instance_type.as_ref() {
+      let instance_types = match instance_type {
+        SingleOrVec::Single(instance_type) => vec![**instance_type],
+        SingleOrVec::Vec(instance_types) => instance_types.clone(),
+      };
+      if !instance_types
+        .iter()
+        .any(|instance_type| is_instance_of(value, *instance_type))
+      {
+        let expected = instance_types
+          .iter()
+          .copied()
+          .map(instance_type_name)
+          .collect::<Vec<_>>()
+          .join(" or ");
+        violations.push(violation(
+          pointer,
+          format!("expected {expected}, found {}", value_type_name(value)),
+        ));
+        return;
+      }
+    }
+
+    if let Some(enum_values) = schema.enum_values.as_ref() {
+      if !enum_values.contains(value) {
+        let allowed = enum_values.iter().map(Value::to_string).collect::<Vec<_>>().join(", ");
+        violations.push(violation(pointer, format!("value is not one of {allowed}")));
+      }
+    }
+    if let Some(const_value) = schema.const_value.as_ref() {
+      if const_value != value {
+        violations.push(violation(pointer, format!("value must be {const_value}")));
+      }
+    }
+
+    if let Some(subschemas) = schema.subschemas.as_deref() {
+      for subschema in subschemas.all_of.iter().flatten() {
+        self.schema(subschema, value, pointer, depth, violations);
+      }
+      if let Some(any_of) = subschemas.any_of.as_ref() {
+        if !any_of.iter().any(|subschema| self.is_valid(subschema, value, depth)) {
+          violations.push(violation(
+            pointer,
+            "value does not match any of the allowed schemas".to_string(),
+          ));
+        }
+      }
+      if let Some(one_of) = subschemas.one_of.as_ref() {
+        let matching = one_of
+          .iter()
+          .filter(|subschema| self.is_valid(subschema, value, depth))
+          .count();
+        if matching != 1 {
+          violations.push(violation(
+            pointer,
+            format!("value matches {matching} schemas instead of exactly one"),
+          ));
+        }
+      }
+      if let Some(not) = subschemas.not.as_deref() {
+        if self.is_valid(not, value, depth) {
+          violations.push(violation(pointer, "value matches a forbidden schema".to_string()));
+        }
+      }
+    }
+
+    match value {
+      Value::String(string) => string_constraints(schema, string, pointer, violations),
+      Value::Number(_) => number_constraints(schema, value, pointer, violations),
+      Value::Array(values) => self.array_constraints(schema, values, pointer, depth, violations),
+      Value::Object(object) => self.object_constraints(schema, object, pointer, depth, violations),
+      Value::Null | Value::Bool(_) => {}
+    }
+  }
+
+  fn array_constraints(
+    &self,
+    schema: &SchemaObject,
+    values: &[Value],
+    pointer: &str,
+    depth: usize,
+    violations: &mut Vec<SchemaViolation>,
+  ) {
+    let Some(array) = schema.array.as_deref() else {
+      return;
+    };
+    if let Some(min_items) = array.min_items {
+      if values.len() < min_items as usize {
+        violations.push(violation(pointer, format!("array has less than {min_items} items")));
+      }
+    }
+    if let Some(max_items) = array.max_items {
+      if values.len() > max_items as usize {
+        violations.push(violation(pointer, format!("array has more than {max_items} items")));
+      }
+    }
+    if array.unique_items == Some(true) && values.iter().enumerate().any(|(i, v)| values[..i].contains(v)) {
+      violations.push(violation(pointer, "array items are not unique".to_string()));
+    }
+    match array.items.as_ref() {
+      Some(SingleOrVec::Single(items)) => {
+        for (index, value) in values.iter().enumerate() {
+          self.schema(items, value, &format!("{pointer}/{index}"), depth, violations);
+        }
+      }
+      Some(SingleOrVec::Vec(items)) => {
+        for (index, (items, value)) in items.iter().zip(values).enumerate() {
+          self.schema(items, value, &format!("{pointer}/{index}"), depth, violations);
+        }
+      }
+      None => {}
+    }
+  }
+
+  fn object_constraints(
+    &self,
+    schema: &SchemaObject,
+    object: &serde_json::Map<String, Value>,
+    pointer: &str,
+    depth: usize,
+    violations: &mut Vec<SchemaViolation>,
+  ) {
+    let Some(validation) = schema.object.as_deref() else {
+      return;
+    };
+    if let Some(min_properties) = validation.min_properties {
+      if object.len() < min_properties as usize {
+        violations.push(violation(
+          pointer,
+          format!("object has less than {min_properties} properties"),
+        ));
+      }
+    }
+    if let Some(max_properties) = validation.max_properties {
+      if object.len() > max_properties as usize {
+        violations.push(violation(
+          pointer,
+          format!("object has more than {max_properties} properties"),
+        ));
+      }
+    }
+    for required in validation.required.iter().filter(|name| !object.contains_key(*name)) {
+      violations.push(violation(
+        &child_pointer(pointer, required),
+        "required property is missing".to_string(),
+      ));
+    }
+    for (name, value) in object {
+      let property_pointer = child_pointer(pointer, name);
+      if let Some(property) = validation.properties.get(name) {
+        self.schema(property, value, &property_pointer, depth, violations);
+        continue;
+      }
+      let mut pattern_properties = validation
+        .pattern_properties
+        .iter()
+        .filter(|(pattern, _)| matches_pattern(pattern, name) != Some(false))
+        .peekable();
+      if pattern_properties.peek().is_some() {
+        for (_, property) in pattern_properties {
+          self.schema(property, value, &property_pointer, depth, violations);
+        }
+        continue;
+      }
+      if let Some(additional_properties) = validation.additional_properties.as_deref() {
+        match additional_properties {
+          Schema::Bool(false) => violations.push(violation(&property_pointer, "property is not allowed".to_string())),
+          additional_properties => self.schema(additional_properties, value, &property_pointer, depth, violations),
+        }
+      }
+    }
+  }
+}
+
+fn string_constraints(schema: &SchemaObject, string: &str, pointer: &str, violations: &mut Vec<SchemaViolation>) {
+  let Some(validation) = schema.string.as_deref() else {
+    return;
+  };
+  let length = string.chars().count();
+  if let Some(min_length) = validation.min_length {
+    if length < min_length as usize {
+      violations.push(violation(
+        pointer,
+        format!("string is shorter than {min_length} characters"),
+      ));
+    }
+  }
+  if let Some(max_length) = validation.max_length {
+    if length > max_length as usize {
+      violations.push(violation(
+        pointer,
+        format!("string is longer than {max_length} characters"),
+      ));
+    }
+  }
+  if let Some(pattern) = validation.pattern.as_ref() {
+    if matches_pattern(pattern, string) == Some(false) {
+      violations.push(violation(pointer, format!("string does not match pattern `{pattern}`")));
+    }
+  }
+}
+
+fn number_constraints(schema: &SchemaObject, value: &Value, pointer: &str, violations: &mut Vec<SchemaViolation>) {
+  let (Some(validation), Some(number)) = (schema.number.as_deref(), value.as_f64()) else {
+    return;
+  };
+  if let Some(minimum) = validation.minimum {
+    if number < minimum {
+      violations.push(violation(pointer, format!("value is lower than {minimum}")));
+    }
+  }
+  if let Some(exclusive_minimum) = validation.exclusive_minimum {
+    if number <= exclusive_minimum {
+      violations.push(violation(
+        pointer,
+        format!("value must be greater than {exclusive_minimum}"),
+      ));
+    }
+  }
+  if let Some(maximum) = validation.maximum {
+    if number > maximum {
+      violations.push(violation(pointer, format!("value is greater than {maximum}")));
+    }
+  }
+  if let Some(exclusive_maximum) = validation.exclusive_maximum {
+    if number >= exclusive_maximum {
+      violations.push(violation(
+        pointer,
+        format!("value must be lower than {exclusive_maximum}"),
+      ));
+    }
+  }
+  if let Some(multiple_of) = validation.multiple_of {
+    if multiple_of > 0.0 && (number / multiple_of).fract().abs() > f64::EPSILON {
+      violations.push(violation(pointer, format!("value is not a multiple of {multiple_of}")));
+    }
+  }
+}
+
+/// Whether the value matches the given pattern, `None` if the pattern is not a valid regex.
+fn matches_pattern(pattern: &str, value: &str) -> Option<bool> {
+  if let Some(regex) = PATTERNS.read().ok().and_then(|patterns| patterns.get(pattern).cloned()) {
+    return regex.map(|regex| regex.is_match(value));
+  }
+  let regex = Regex::new(pattern).ok();
+  let matches = regex.as_ref().map(|regex| regex.is_match(value));
+  if let Ok(mut patterns) = PATTERNS.write() {
+    patterns.insert(pattern.to_owned(), regex);
+  }
+  matches
+}
+
+fn has_instance_type(schema: &SchemaObject, instance_type: InstanceType) -> bool {
+  match schema.instance_type.as_ref() {
+    Some(SingleOrVec::Single(single)) => **single == instance_type,
+    Some(SingleOrVec::Vec(instance_types)) => instance_types.contains(&instance_type),
+    None => false,
+  }
+}
+
+fn scalar_value(schema: Option<&SchemaObject>, value: &str) -> Value {
+  let Some(schema) = schema else {
+    return Value::String(value.to_owned());
+  };
+  if has_instance_type(schema, InstanceType::Integer) {
+    if let Ok(integer) = value.parse::<i64>() {
+      return Value::from(integer);
+    }
+    if let Ok(integer) = value.parse::<u64>() {
+      return Value::from(integer);
+    }
+  }
+  if has_instance_type(schema, InstanceType::Number) {
+    if let Some(number) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+      return Value::Number(number);
+    }
+  }
+  if has_instance_type(schema, InstanceType::Boolean) {
+    if let Ok(boolean) = value.parse::<bool>() {
+      return Value::Bool(boolean);
+    }
+  }
+  Value::String(value.to_owned())
+}
+
+fn is_instance_of(value: &Value, instance_type: InstanceType) -> bool {
+  match instance_type {
+    InstanceType::Null => value.is_null(),
+    InstanceType::Boolean => value.is_boolean(),
+    InstanceType::Object => value.is_object(),
+    InstanceType::Array => value.is_array(),
+    InstanceType::Number => value.is_number(),
+    InstanceType::String => value.is_string(),
+    InstanceType::Integer => {
+      value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|number| number.fract().abs() < f64::EPSILON)
+    }
+  }
+}
+
+fn instance_type_name(instance_type: InstanceType) -> &'static str {
+  match instance_type {
+    InstanceType::Null => "null",
+    InstanceType::Boolean => "boolean",
+    InstanceType::Object => "object",
+    InstanceType::Array => "array",
+    InstanceType::Number => "number",
+    InstanceType::String => "string",
+    InstanceType::Integer => "integer",
+  }
+}
+
+fn value_type_name(value: &Value) -> &'static str {
+  match value {
+    Value::Null => "null",
+    Value::Bool(_) => "boolean",
+    Value::Number(_) => "number",
+    Value::String(_) => "string",
+    Value::Array(_) => "array",
+    Value::Object(_) => "object",
+  }
+}
+
+/// Append the given property to a json pointer, escaping it following [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901).
+pub(crate) fn child_pointer(pointer: &str, property: &str) -> String {
+  format!("{pointer}/{}", property.replace('~', "~0").replace('/', "~1"))
+}
+
+fn violation(pointer: &str, message: String) -> SchemaViolation {
+  SchemaViolation {
+    pointer: pointer.to_owned(),
+    message,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::contract::SchemaViolation;
+  use crate::internal::json_schema::SchemaValidator;
+  use apistos_models::components::Components;
+  use apistos_models::reference_or::ReferenceOr;
+  use schemars::gen::SchemaSettings;
+  use schemars::JsonSchema;
+  use serde_json::json;
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Pet {
+    #[schemars(length(min = 1, max = 8), regex(pattern = r"^[a-z]+$"))]
+    name: String,
+    #[schemars(range(min = 0, max = 30))]
+    age: u8,
+    status: Status,
+    owner: Option<Owner>,
+    tags: Vec<String>,
+  }
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  enum Status {
+    Available,
+    Sold,
+  }
+
+  #[allow(dead_code)]
+  #[derive(JsonSchema)]
+  struct Owner {
+    name: String,
+  }
+
+  fn violations(value: &serde_json::Value) -> Vec<SchemaViolation> {
+    let root_schema = SchemaSettings::openapi3()
+      .into_generator()
+      .into_root_schema_for::<Pet>();
+    let components = Components {
+      schemas: root_schema
+        .definitions
+        .into_iter()
+        .map(|(name, schema)| (name, ReferenceOr::Object(schema)))
+        .collect(),
+      ..Default::default()
+    };
+    SchemaValidator::new(Some(&components)).validate(&ReferenceOr::Object(root_schema.schema.into()), value)
+  }
+
+  #[test]
+  fn valid_value() {
+    let value = json!({ "name": "rex", "age": 3, "status": "Sold", "owner": null, "tags": ["dog"] });
+    assert_eq!(violations(&value), vec![]);
+  }
+
+  #[test]
+  fn invalid_value_reported_by_pointer() {
+    let value = json!({ "name": "Rex the dog", "age": 31, "status": "Lost", "owner": {}, "tags": ["dog", 1] });
+    let violations: Vec<(String, String)> = violations(&value)
+      .into_iter()
+      .map(|violation| (violation.pointer, violation.message))
+      .collect();
+
+    assert_eq!(
+      violations,
+      vec![
+        ("/age".to_string(), "value is greater than 30".to_string()),
+        ("/name".to_string(), "string is longer than 8 characters".to_string()),
+        (
+          "/name".to_string(),
+          "string does not match pattern `^[a-z]+$`".to_string()
+        ),
+        ("/owner/name".to_string(), "required property is missing".to_string()),
+        (
+          "/status".to_string(),
+          "value is not one of \"Available\", \"Sold\"".to_string()
+        ),
+        ("/tags/1".to_string(), "expected string, found number".to_string()),
+      ]
+    );
+  }
+}
@@ -0,0 +1,105 @@
+use crate::validation::{ValidationError, ValidationErrorKind};
+use apistos_core::{HandlerLink, PathItemDefinition};
+use apistos_models::paths::{OperationIdentifier, OperationType};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::OpenApi;
+use std::any::TypeId;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Handler documented by an operation, kept out of the openapi model to name its operation and resolve the links targeting it.
+#[derive(Clone, Debug)]
+pub struct OperationHandler {
+  pub(crate) id: TypeId,
+  pub(crate) name: Option<&'static str>,
+  pub(crate) links: Vec<HandlerLink>,
+}
+
+impl OperationHandler {
+  /// Handler **`F`**, documented by **`D`**.
+  pub(crate) fn new<F: 'static, D: PathItemDefinition>() -> Self {
+    Self {
+      id: TypeId::of::<F>(),
+      name: D::handler_name(),
+      links: D::handler_links(),
+    }
+  }
+}
+
+/// Handlers of the operations of a definition holder, by path and method.
+pub(crate) type OperationHandlers = Vec<(String, OperationType, OperationHandler)>;
+
+/// Resolve links declared by handler reference to the `operationId` of their target.
+///
+/// Links targeting a handler which is not documented in this document, or documented by several operations, are removed, a validation error being returned for each of them.
+pub(crate) fn resolve_links(open_api: &mut OpenApi, handlers: &OperationHandlers) -> Vec<ValidationError> {
+  let mut operation_ids: BTreeMap<TypeId, BTreeSet<String>> = BTreeMap::new();
+  for (path, method, handler) in handlers {
+    let operation_id = open_api
+      .paths
+      .paths
+      .get(path)
+      .and_then(|pi| pi.operations.get(method))
+      .and_then(|operation| operation.operation_id.clone());
+    if let Some(operation_id) = operation_id {
+      operation_ids.entry(handler.id).or_default().insert(operation_id);
+    }
+  }
+
+  let mut errors = vec![];
+  for (path, method, handler) in handlers.iter().filter(|(_, _, handler)| !handler.links.is_empty()) {
+    let Some(operation) = open_api
+      .paths
+      .paths
+      .get_mut(path)
+      .and_then(|pi| pi.operations.get_mut(method))
+    else {
+      continue;
+    };
+    for handler_link in &handler.links {
+      let target_operation_ids = operation_ids.get(&handler_link.handler_id);
+      let operation_id = target_operation_ids
+        .filter(|operation_ids| operation_ids.len() == 1)
+        .and_then(|operation_ids| operation_ids.first());
+      let responses = operation
+        .responses
+        .default
+        .iter_mut()
+        .chain(operation.responses.responses.values_mut())
+        .filter_map(|response| match response {
+          ReferenceOr::Object(response) => Some(response),
+          ReferenceOr::Reference { .. } => None,
+        });
+      let mut unresolved = false;
+      for response in responses {
+        match (operation_id, response.links.get_mut(&handler_link.name)) {
+          (Some(operation_id), Some(ReferenceOr::Object(link))) => {
+            link.operation_identifier = Some(OperationIdentifier::OperationId(operation_id.clone()));
+          }
+          (None, Some(ReferenceOr::Object(_))) => {
+            response.links.remove(&handler_link.name);
+            unresolved = true;
+          }
+          _ => {}
+        }
+      }
+      if unresolved {
+        let link = handler_link.name.clone();
+        let handler = handler_link.handler.to_owned();
+        let kind = match target_operation_ids {
+          Some(operation_ids) if operation_ids.len() > 1 => ValidationErrorKind::AmbiguousLinkHandler {
+            link,
+            handler,
+            operation_ids: operation_ids.iter().cloned().collect(),
+          },
+          _ => ValidationErrorKind::UnknownLinkHandler { link, handler },
+        };
+        errors.push(ValidationError {
+          location: path.clone(),
+          method: Some(method.clone()),
+          kind,
+        });
+      }
+    }
+  }
+  errors
+}
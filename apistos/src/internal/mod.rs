@@ -1,2 +1,7 @@
 pub(crate) mod actix;
+pub(crate) mod cache;
 pub(crate) mod definition_holder;
+pub(crate) mod json_schema;
+pub(crate) mod links;
+pub(crate) mod operations;
+pub(crate) mod references;
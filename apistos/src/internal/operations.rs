@@ -0,0 +1,178 @@
+use actix_web::http::Method;
+use apistos_models::paths::{Operation, OperationType, PathItem};
+use apistos_models::OpenApi;
+
+enum Segment {
+  Literal(String),
+  Parameter(String),
+}
+
+struct PathTemplate {
+  path: String,
+  segments: Vec<Segment>,
+}
+
+/// Operation documented for a request.
+pub(crate) struct MatchedOperation<'a> {
+  /// Templated path of the operation, e.g. `/pets/{id}`.
+  pub(crate) path: &'a str,
+  pub(crate) path_item: &'a PathItem,
+  pub(crate) operation: &'a Operation,
+  /// Percent-decoded values of the path parameters, by name.
+  pub(crate) path_parameters: Vec<(&'a str, String)>,
+}
+
+/// Match requests against the paths of an openapi document.
+///
+/// Templates with the most literal segments take precedence, so that `/pets/mine` is preferred over `/pets/{id}`.
+pub(crate) struct OperationMatcher {
+  templates: Vec<PathTemplate>,
+}
+
+impl OperationMatcher {
+  pub(crate) fn new(open_api: &OpenApi) -> Self {
+    let mut templates: Vec<PathTemplate> = open_api
+      .paths
+      .paths
+      .keys()
+      .map(|path| PathTemplate {
+        path: path.clone(),
+        segments: split(path)
+          .map(
+            |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+              Some(name) => Segment::Parameter(name.to_owned()),
+              None => Segment::Literal(segment.to_owned()),
+            },
+          )
+          .collect(),
+      })
+      .collect();
+    templates.sort_by_key(|template| {
+      std::cmp::Reverse(
+        template
+          .segments
+          .iter()
+          .filter(|segment| matches!(segment, Segment::Literal(_)))
+          .count(),
+      )
+    });
+    Self { templates }
+  }
+
+  /// Find the operation documented in **`open_api`** for the given method and path, `open_api` being the document the matcher was built from.
+  pub(crate) fn find<'a>(&'a self, open_api: &'a OpenApi, method: &Method, path: &str) -> Option<MatchedOperation<'a>> {
+    let operation_type = operation_type(method);
+    let segments: Vec<&str> = split(path).collect();
+    self.templates.iter().find_map(|template| {
+      if template.segments.len() != segments.len() {
+        return None;
+      }
+      let mut path_parameters = vec![];
+      for (segment, value) in template.segments.iter().zip(&segments) {
+        match segment {
+          Segment::Literal(literal) if literal == value => {}
+          Segment::Literal(_) => return None,
+          Segment::Parameter(name) => path_parameters.push((name.as_str(), percent_decode(value))),
+        }
+      }
+      let path_item = open_api.paths.paths.get(&template.path)?;
+      Some(MatchedOperation {
+        path: &template.path,
+        path_item,
+        operation: path_item.operations.get(&operation_type)?,
+        path_parameters,
+      })
+    })
+  }
+}
+
+fn split(path: &str) -> impl Iterator<Item = &str> {
+  path.split('/').filter(|segment| !segment.is_empty())
+}
+
+fn operation_type(method: &Method) -> OperationType {
+  match method.as_str() {
+    "PUT" => OperationType::Put,
+    "POST" => OperationType::Post,
+    "DELETE" => OperationType::Delete,
+    "OPTIONS" => OperationType::Options,
+    "HEAD" => OperationType::Head,
+    "PATCH" => OperationType::Patch,
+    "TRACE" => OperationType::Trace,
+    "GET" => OperationType::Get,
+    m => OperationType::Extension(m.to_lowercase()),
+  }
+}
+
+/// Decode `%XX` sequences, invalid sequences being kept as is, as well as the whole value if it is not valid utf-8 once decoded.
+fn percent_decode(value: &str) -> String {
+  let bytes = value.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'%' {
+      if let (Some(high), Some(low)) = (hex_digit(bytes.get(i + 1)), hex_digit(bytes.get(i + 2))) {
+        decoded.push(high << 4 | low);
+        i += 3;
+        continue;
+      }
+    }
+    decoded.push(bytes[i]);
+    i += 1;
+  }
+  String::from_utf8(decoded).unwrap_or_else(|_| value.to_owned())
+}
+
+fn hex_digit(byte: Option<&u8>) -> Option<u8> {
+  char::from(*byte?)
+    .to_digit(16)
+    .and_then(|digit| u8::try_from(digit).ok())
+}
+
+#[cfg(test)]
+mod test {
+  use crate::internal::operations::OperationMatcher;
+  use actix_web::http::Method;
+  use apistos_models::paths::{Operation, OperationType, PathItem, Paths};
+  use apistos_models::OpenApi;
+  use indexmap::IndexMap;
+
+  fn open_api(paths: &[&str]) -> OpenApi {
+    OpenApi {
+      paths: Paths {
+        paths: paths
+          .iter()
+          .map(|path| {
+            (
+              (*path).to_owned(),
+              PathItem {
+                operations: IndexMap::from_iter([(OperationType::Get, Operation::default())]),
+                ..Default::default()
+              },
+            )
+          })
+          .collect(),
+        ..Default::default()
+      },
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn literal_segments_take_precedence() {
+    let open_api = open_api(&["/pets/{id}", "/pets/mine", "/pets"]);
+    let matcher = OperationMatcher::new(&open_api);
+
+    let matched = matcher.find(&open_api, &Method::GET, "/pets/mine");
+    assert_eq!(matched.map(|matched| matched.path), Some("/pets/mine"));
+
+    let matched = matcher.find(&open_api, &Method::GET, "/pets/r%C3%A9x%2F1%zz/");
+    assert_eq!(
+      matched.map(|matched| (matched.path, matched.path_parameters)),
+      Some(("/pets/{id}", vec![("id", "réx/1%zz".to_string())]))
+    );
+
+    assert!(matcher.find(&open_api, &Method::POST, "/pets").is_none());
+    assert!(matcher.find(&open_api, &Method::GET, "/owners").is_none());
+  }
+}
@@ -0,0 +1,98 @@
+use apistos_models::components::Components;
+use apistos_models::OpenApi;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+pub(crate) const COMPONENTS_REFERENCE_PREFIX: &str = "#/components/";
+
+/// Collect every `$ref` found in the given value.
+pub(crate) fn collect_references(value: &Value, references: &mut BTreeSet<String>) {
+  match value {
+    Value::Object(map) => {
+      for (key, value) in map {
+        match value {
+          Value::String(reference) if key == "$ref" => {
+            references.insert(reference.clone());
+          }
+          _ => collect_references(value, references),
+        }
+      }
+    }
+    Value::Array(values) => values.iter().for_each(|v| collect_references(v, references)),
+    _ => {}
+  }
+}
+
+/// Split a `#/components/<kind>/<name>` reference into its kind and its (unescaped) name.
+pub(crate) fn parse_component_reference(reference: &str) -> Option<(&str, String)> {
+  let (kind, name) = reference.strip_prefix(COMPONENTS_REFERENCE_PREFIX)?.split_once('/')?;
+  Some((kind, name.replace("~1", "/").replace("~0", "~")))
+}
+
+fn component_value(components: &Components, kind: &str, name: &str) -> Option<Value> {
+  let value = match kind {
+    "schemas" => serde_json::to_value(components.schemas.get(name)?),
+    "responses" => serde_json::to_value(components.responses.get(name)?),
+    "parameters" => serde_json::to_value(components.parameters.get(name)?),
+    "examples" => serde_json::to_value(components.examples.get(name)?),
+    "requestBodies" => serde_json::to_value(components.request_bodies.get(name)?),
+    "headers" => serde_json::to_value(components.headers.get(name)?),
+    "links" => serde_json::to_value(components.links.get(name)?),
+    "callbacks" => serde_json::to_value(components.callbacks.get(name)?),
+    _ => return None,
+  };
+  value.ok()
+}
+
+/// Whether the given `#/components/...` reference targets a component declared in the given components.
+pub(crate) fn is_declared_component(components: Option<&Components>, reference: &str) -> bool {
+  let (Some(components), Some((kind, name))) = (components, parse_component_reference(reference)) else {
+    return false;
+  };
+  component_value(components, kind, &name).is_some()
+}
+
+/// Remove from the document components which are neither directly nor transitively referenced by its paths or webhooks.
+///
+/// Security schemes are kept as is as they are not referenced through `$ref`.
+pub(crate) fn retain_referenced_components(open_api: &mut OpenApi) {
+  let Some(components) = open_api.components.as_mut() else {
+    return;
+  };
+  let (Ok(paths), Ok(webhooks)) = (
+    serde_json::to_value(&open_api.paths),
+    serde_json::to_value(&open_api.webhooks),
+  ) else {
+    return;
+  };
+
+  let mut pending = BTreeSet::new();
+  collect_references(&paths, &mut pending);
+  collect_references(&webhooks, &mut pending);
+
+  let mut referenced: BTreeSet<(String, String)> = BTreeSet::new();
+  while let Some(reference) = pending.pop_first() {
+    let Some((kind, name)) = parse_component_reference(&reference) else {
+      continue;
+    };
+    if let Some(value) = component_value(components, kind, &name) {
+      if referenced.insert((kind.to_owned(), name)) {
+        collect_references(&value, &mut pending);
+      }
+    }
+  }
+
+  let is_referenced = |kind: &str, name: &String| referenced.contains(&(kind.to_owned(), name.clone()));
+  components.schemas.retain(|name, _| is_referenced("schemas", name));
+  components.responses.retain(|name, _| is_referenced("responses", name));
+  components
+    .parameters
+    .retain(|name, _| is_referenced("parameters", name));
+  components.examples.retain(|name, _| is_referenced("examples", name));
+  components
+    .request_bodies
+    .retain(|name, _| is_referenced("requestBodies", name));
+  components.headers.retain(|name, _| is_referenced("headers", name));
+  components.links.retain(|name, _| is_referenced("links", name));
+  components.callbacks.retain(|name, _| is_referenced("callbacks", name));
+}
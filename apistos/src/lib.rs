@@ -128,6 +128,9 @@
 //! | `rapidoc`         | Enables `RapiDoc` to expose the generated openapi file                   |                                                                |
 //! | `redoc`           | Enables `ReDoc` to expose the generated openapi file                     |                                                                |
 //! | `swagger-ui`      | Enables Swagger UI to expose the generated openapi file                  |                                                                |
+//! | `yaml`            | Enables serving the generated openapi file as yaml                       | [`serde_norway`](https://crates.io/crates/serde_norway)        |
+//! | `compression`     | Enables serving the generated openapi file gzip or brotli compressed     | [`flate2`](https://crates.io/crates/flate2), [`brotli`](https://crates.io/crates/brotli) |
+//! | `testing`         | Enables the spec snapshot testing helpers of the `testing` module        |                                                                |
 //! | `chrono`          | Enables documenting types from `chrono`                                  | [`chrono`](https://crates.io/crates/chrono)                    |
 //! | `multipart`       | Enables documenting types from `actix-multipart`                         | [`actix-multipart`](https://crates.io/crates/actix-multipart)  |
 //! | `rust_decimal`    | Enables documenting types from `rust_decimal`                            | [`rust_decimal`](https://crates.io/crates/rust-decimal)        |
@@ -151,8 +154,10 @@
 pub use indexmap::IndexMap;
 
 pub use apistos_core::parameters::header::ApiHeader;
-pub use apistos_core::PathItemDefinition;
-pub use apistos_core::{ApiComponent, ApiErrorComponent, TypedSchema};
+pub use apistos_core::{
+  component_response, component_responses, one_of_schema, root_schema_for, ApiComponent, ApiErrorComponent, TypedSchema,
+};
+pub use apistos_core::{HandlerLink, PathItemDefinition};
 pub use apistos_gen::{api_operation, ApiComponent, ApiCookie, ApiErrorComponent, ApiHeader, ApiSecurity, ApiType};
 pub use apistos_models::*;
 #[cfg(feature = "rapidoc")]
@@ -169,7 +174,15 @@ mod internal;
 pub mod actix;
 
 pub mod app;
+pub mod contract;
+pub mod export;
+pub mod guard;
+pub mod operation_id;
+pub mod problem;
 pub mod spec;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod validation;
 pub mod web;
 
 #[cfg(test)]
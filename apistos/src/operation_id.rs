@@ -0,0 +1,179 @@
+//! Strategies used to generate the `operationId` of operations which do not declare one using `#[api_operation(operation_id = "...")]`.
+//!
+//! ```rust,ignore
+//! use actix_web::App;
+//! use apistos::app::OpenApiWrapper;
+//! use apistos::operation_id::HandlerNameOperationId;
+//! use apistos::spec::Spec;
+//! use std::sync::Arc;
+//!
+//! App::new()
+//!   .document(Spec {
+//!     operation_id_strategy: Some(Arc::new(HandlerNameOperationId)),
+//!     ..Default::default()
+//!   })
+//!   .service(todo!())
+//!   .build("/openapi.json");
+//! ```
+//!
+//! Generated ids already used by another operation of the same document are suffixed with a number to keep them unique.
+
+use apistos_models::paths::{Operation, OperationType};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Generates the `operationId` of an operation.
+pub trait OperationIdStrategy: Send + Sync {
+  /// Generate the operation id of the operation registered at **`path`**, as declared in actix (patterned path parameters like `{id:\d+}` are not sanitized), for the given method.
+  ///
+  /// **`handler`** is the name of the handler function documented with `#[api_operation]`, if any.
+  fn operation_id(
+    &self,
+    path: &str,
+    operation_type: &OperationType,
+    operation: &Operation,
+    handler: Option<&str>,
+  ) -> String;
+}
+
+/// Default strategy, building ids like `get_api-v1-pets-<md5 of the path>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashedOperationId;
+
+impl OperationIdStrategy for HashedOperationId {
+  fn operation_id(
+    &self,
+    path: &str,
+    operation_type: &OperationType,
+    _operation: &Operation,
+    _handler: Option<&str>,
+  ) -> String {
+    let resource = PATH_RESOURCE_REGEX
+      .captures(path)
+      .and_then(|c| c.get(1))
+      .map(|_match| _match.as_str())
+      .unwrap_or(path)
+      .trim_matches('/');
+    format!(
+      "{}_{}-{:x}",
+      operation_type,
+      resource.replace('/', "-"),
+      md5::compute(path)
+    )
+    .to_lowercase()
+  }
+}
+
+/// Use the name of the handler function documented with `#[api_operation]`, for example `get_pets`.
+///
+/// Falls back to [`HashedOperationId`] for operations not generated from a documented handler.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HandlerNameOperationId;
+
+impl OperationIdStrategy for HandlerNameOperationId {
+  fn operation_id(
+    &self,
+    path: &str,
+    operation_type: &OperationType,
+    operation: &Operation,
+    handler: Option<&str>,
+  ) -> String {
+    match handler {
+      Some(handler) => handler.to_owned(),
+      None => HashedOperationId.operation_id(path, operation_type, operation, None),
+    }
+  }
+}
+
+/// Build camelCase ids from the method and the path, for example `getPetsByPetIdOwners` for `GET /pets/{pet_id}/owners`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CamelCaseOperationId;
+
+impl OperationIdStrategy for CamelCaseOperationId {
+  fn operation_id(
+    &self,
+    path: &str,
+    operation_type: &OperationType,
+    _operation: &Operation,
+    _handler: Option<&str>,
+  ) -> String {
+    let mut operation_id = operation_type.to_string();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+      let segment = match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+        Some(parameter) => {
+          operation_id.push_str("By");
+          parameter.split(':').next().unwrap_or(parameter)
+        }
+        None => segment,
+      };
+      segment
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .for_each(|word| {
+          let mut chars = word.chars();
+          if let Some(first) = chars.next() {
+            operation_id.push(first.to_ascii_uppercase());
+            operation_id.push_str(chars.as_str());
+          }
+        });
+    }
+    operation_id
+  }
+}
+
+#[allow(clippy::expect_used)]
+static PATH_RESOURCE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/(.*?)/\{(.*?)\}").expect("path template regex"));
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use crate::operation_id::{CamelCaseOperationId, HandlerNameOperationId, HashedOperationId, OperationIdStrategy};
+  use apistos_models::paths::{Operation, OperationType};
+
+  #[test]
+  fn hashed_operation_id() {
+    let operation = Operation::default();
+
+    let operation_id = HashedOperationId.operation_id("/api/v1/plop/", &OperationType::Get, &operation, None);
+    assert_eq!(operation_id, "get_api-v1-plop-89654e0732d51aafdc164076a57fd663");
+
+    let operation_id = HashedOperationId.operation_id("/api/v1/plap/{test_id}", &OperationType::Get, &operation, None);
+    assert_eq!(operation_id, "get_api-v1-plap-97ba4631a55f77d23b996bf558be60da");
+
+    let operation_id =
+      HashedOperationId.operation_id("/api/v1/plip/{test_id}/test/", &OperationType::Get, &operation, None);
+    assert_eq!(operation_id, "get_api-v1-plip-f5c9e39d7a1acb928c72745f3893bce8")
+  }
+
+  #[test]
+  fn handler_name_operation_id() {
+    let operation = Operation::default();
+
+    let operation_id = HandlerNameOperationId.operation_id("/pets", &OperationType::Get, &operation, Some("get_pets"));
+    assert_eq!(operation_id, "get_pets");
+
+    let operation_id = HandlerNameOperationId.operation_id("/api/v1/plop/", &OperationType::Get, &operation, None);
+    assert_eq!(operation_id, "get_api-v1-plop-89654e0732d51aafdc164076a57fd663");
+  }
+
+  #[test]
+  fn camel_case_operation_id() {
+    let operation = Operation::default();
+
+    let operation_id =
+      CamelCaseOperationId.operation_id("/pets/{pet_id}/owners", &OperationType::Get, &operation, None);
+    assert_eq!(operation_id, "getPetsByPetIdOwners");
+
+    let operation_id = CamelCaseOperationId.operation_id(
+      "/api/v1/user-tokens/{id:\\d+}",
+      &OperationType::Delete,
+      &operation,
+      None,
+    );
+    assert_eq!(operation_id, "deleteApiV1UserTokensById");
+
+    let operation_id = CamelCaseOperationId.operation_id("/", &OperationType::Post, &operation, None);
+    assert_eq!(operation_id, "post");
+  }
+}
@@ -0,0 +1,197 @@
+//! [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) problem details for HTTP APIs.
+//!
+//! [`ProblemDetails`] can be returned as an error by handlers, or used to document the error responses of an [`ApiErrorComponent`] derive with `#[openapi_error(problem_details, ...)]`.
+//!
+//! ```rust,ignore
+//! use actix_web::http::StatusCode;
+//! use actix_web::web::Json;
+//! use apistos::api_operation;
+//! use apistos::problem::ProblemDetails;
+//!
+//! #[api_operation(summary = "Get a pet")]
+//! async fn get_pet() -> Result<Json<Pet>, ProblemDetails> {
+//!   Err(ProblemDetails::new(StatusCode::NOT_FOUND).with_detail("Pet 42 does not exist"))
+//! }
+//! ```
+
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use apistos_core::{root_schema_for, ApiComponent, ApiErrorComponent};
+use apistos_models::paths::{MediaType, Response};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Content type of problem details responses.
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Name of the [`ProblemDetails`] schema in the document components.
+pub const PROBLEM_DETAILS_SCHEMA: &str = "ProblemDetails";
+
+/// Problem details of an error response, as defined by [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457#name-members-of-a-problem-detai).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemDetails {
+  /// A URI reference identifying the problem type, `about:blank` when the problem has no additional semantics beyond the status code.
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub problem_type: Option<String>,
+  /// A short, human-readable summary of the problem type.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  /// The HTTP status code generated by the origin server for this occurrence of the problem.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub status: Option<u16>,
+  /// A human-readable explanation specific to this occurrence of the problem.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub detail: Option<String>,
+  /// A URI reference identifying the specific occurrence of the problem.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub instance: Option<String>,
+  /// Extension members of the problem type.
+  #[serde(flatten)]
+  pub extensions: Map<String, Value>,
+}
+
+impl ProblemDetails {
+  /// Problem details for the given status, titled after its canonical reason.
+  pub fn new(status: StatusCode) -> Self {
+    Self {
+      title: status.canonical_reason().map(ToOwned::to_owned),
+      status: Some(status.as_u16()),
+      ..Default::default()
+    }
+  }
+
+  pub fn with_type(mut self, problem_type: impl Into<String>) -> Self {
+    self.problem_type = Some(problem_type.into());
+    self
+  }
+
+  pub fn with_title(mut self, title: impl Into<String>) -> Self {
+    self.title = Some(title.into());
+    self
+  }
+
+  pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+    self.detail = Some(detail.into());
+    self
+  }
+
+  pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+    self.instance = Some(instance.into());
+    self
+  }
+
+  /// Add an extension member, values which cannot be serialized being ignored.
+  pub fn with_extension(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+    if let Ok(value) = serde_json::to_value(value) {
+      self.extensions.insert(name.into(), value);
+    }
+    self
+  }
+}
+
+impl Display for ProblemDetails {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let title = self
+      .title
+      .as_deref()
+      .unwrap_or_else(|| self.status_code().canonical_reason().unwrap_or_default());
+    match &self.detail {
+      Some(detail) => write!(f, "{title}: {detail}"),
+      None => write!(f, "{title}"),
+    }
+  }
+}
+
+impl std::error::Error for ProblemDetails {}
+
+impl ResponseError for ProblemDetails {
+  /// The status of the problem, `500 Internal Server Error` if missing or invalid.
+  fn status_code(&self) -> StatusCode {
+    self
+      .status
+      .and_then(|status| StatusCode::from_u16(status).ok())
+      .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    HttpResponse::build(self.status_code())
+      .insert_header((CONTENT_TYPE, PROBLEM_JSON_CONTENT_TYPE))
+      .json(self)
+  }
+}
+
+impl ApiComponent for ProblemDetails {
+  fn content_type() -> String {
+    PROBLEM_JSON_CONTENT_TYPE.to_string()
+  }
+
+  fn child_schemas() -> Vec<(String, ReferenceOr<Schema>)> {
+    vec![]
+  }
+
+  fn schema() -> Option<(String, ReferenceOr<Schema>)> {
+    Some((
+      PROBLEM_DETAILS_SCHEMA.to_string(),
+      ReferenceOr::Object(Schema::Object(root_schema_for::<Self>().schema.clone())),
+    ))
+  }
+}
+
+/// Documents a `default` response, as the status of problem details is only known at runtime.
+impl ApiErrorComponent for ProblemDetails {
+  fn schemas_by_status_code() -> BTreeMap<String, (String, ReferenceOr<Schema>)> {
+    <Self as ApiComponent>::schema()
+      .map(|schema| ("default".to_string(), schema))
+      .into_iter()
+      .collect()
+  }
+
+  fn error_responses() -> Vec<(String, Response)> {
+    vec![(
+      "default".to_string(),
+      problem_response("Problem details".to_string(), None),
+    )]
+  }
+}
+
+/// A response documenting a [`ProblemDetails`] body, optionally extended by the fields of the **`extension`** schema.
+///
+/// Used by the [`ApiErrorComponent`](crate::ApiErrorComponent) derive when `problem_details` is set.
+#[doc(hidden)]
+pub fn problem_response(description: String, extension: Option<ReferenceOr<Schema>>) -> Response {
+  let problem_details = Schema::new_ref(format!("#/components/schemas/{PROBLEM_DETAILS_SCHEMA}"));
+  let schema = match extension {
+    None => ReferenceOr::Object(problem_details),
+    Some(extension) => {
+      let extension = match extension {
+        ReferenceOr::Reference { _ref } => Schema::new_ref(_ref),
+        ReferenceOr::Object(schema) => schema,
+      };
+      ReferenceOr::Object(Schema::Object(schemars::schema::SchemaObject {
+        subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+          all_of: Some(vec![problem_details, extension]),
+          ..Default::default()
+        })),
+        ..Default::default()
+      }))
+    }
+  };
+  Response {
+    description,
+    content: BTreeMap::from_iter([(
+      PROBLEM_JSON_CONTENT_TYPE.to_string(),
+      MediaType {
+        schema: Some(schema),
+        ..Default::default()
+      },
+    )]),
+    ..Default::default()
+  }
+}
@@ -1,11 +1,17 @@
 use schemars::schema::Schema;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
+use crate::operation_id::OperationIdStrategy;
+use crate::validation::ValidationLevel;
 use apistos_core::ApiComponent;
 use apistos_models::info::Info;
 use apistos_models::paths::{ExternalDocumentation, Parameter};
 use apistos_models::reference_or::ReferenceOr;
+use apistos_models::security::{SecurityRequirement, SecurityScheme};
 use apistos_models::server::Server;
 use apistos_models::tag::Tag;
+use apistos_models::{OpenApi, OpenApiVersion};
 
 /// Defines an accessor for `DefaultParameters`
 pub trait DefaultParameterAccessor {
@@ -35,11 +41,68 @@ pub struct DefaultParameters {
   pub components: Vec<(String, ReferenceOr<Schema>)>,
 }
 
+/// Defines an accessor for `DefaultSecurity`
+pub trait DefaultSecurityAccessor {
+  fn get_default_security() -> DefaultSecurity;
+}
+
+impl<T> DefaultSecurityAccessor for T
+where
+  T: ApiComponent,
+{
+  fn get_default_security() -> DefaultSecurity {
+    DefaultSecurity {
+      requirement: SecurityRequirement {
+        requirements: T::security_requirement_name()
+          .map(|name| BTreeMap::from_iter(vec![(name, vec![])]))
+          .unwrap_or_default(),
+      },
+      security_schemes: T::securities(),
+    }
+  }
+}
+
+/// Defines a security requirement with its associated security schemes. Can be built from a type deriving `ApiSecurity` using the `DefaultSecurityAccessor` trait.
+///
+/// An empty requirement, as built by [`DefaultSecurity::public`], makes security optional.
+#[derive(Default, Clone)]
+pub struct DefaultSecurity {
+  pub requirement: SecurityRequirement,
+  pub security_schemes: BTreeMap<String, SecurityScheme>,
+}
+
+impl DefaultSecurity {
+  /// An empty security requirement, documenting operations as accessible without authentication.
+  pub fn public() -> Self {
+    Self::default()
+  }
+
+  /// Require the given scopes for every security scheme of this requirement.
+  pub fn with_scopes<T: Into<String>>(mut self, scopes: Vec<T>) -> Self {
+    let scopes: Vec<String> = scopes.into_iter().map(Into::into).collect();
+    for requirement_scopes in self.requirement.requirements.values_mut() {
+      requirement_scopes.clone_from(&scopes);
+    }
+    self
+  }
+
+  /// Also require the schemes of **`other`**, both having to be satisfied.
+  pub fn and(mut self, other: DefaultSecurity) -> Self {
+    self.requirement.requirements.extend(other.requirement.requirements);
+    self.security_schemes.extend(other.security_schemes);
+    self
+  }
+}
+
 #[derive(Default, Clone)]
 pub struct Spec {
+  /// Version of the generated openapi specification. Defaults to OAS 3.0, schemas being converted to [JSON Schema 2020-12](https://json-schema.org/draft/2020-12/release-notes) when using OAS 3.1.
+  pub openapi_version: OpenApiVersion,
+  /// See more details at <https://spec.openapis.org/oas/v3.1.0#fixed-fields>. Only supported by OAS 3.1.
+  pub json_schema_dialect: Option<String>,
   pub info: Info,
   pub default_tags: Vec<String>,
-  /// See more details at <https://spec.openapis.org/oas/latest.html#tagObject>.
+  /// See more details at <https://spec.openapis.org/oas/latest.html#tagObject>. Tags of operations missing from these tags are reported by validation.
   pub tags: Vec<Tag>,
   /// See more details at <https://spec.openapis.org/oas/latest.html#external-documentation-object>.
   pub external_docs: Option<ExternalDocumentation>,
@@ -47,4 +110,56 @@ pub struct Spec {
   pub servers: Vec<Server>,
   /// Default parameters to be added to each operation. This only serves for documentation purpose.
   pub default_parameters: Vec<DefaultParameters>,
+  /// Security requirements applying to every operation which does not declare its own, only one of them needing to be satisfied. Their security schemes are added to the document components.
+  pub default_security: Vec<DefaultSecurity>,
+  /// How problems found while validating the generated document are reported when building the app. Defaults to logging them.
+  pub validation: ValidationLevel,
+  /// Strategy generating the id of operations which do not declare one. Defaults to [`HashedOperationId`](crate::operation_id::HashedOperationId).
+  pub operation_id_strategy: Option<Arc<dyn OperationIdStrategy>>,
+  /// Document routes accepting any method, like `Route::new().to(handler)`, as a single operation listing the accepted methods in an `x-methods` extension instead of one operation per method.
+  pub collapse_any_method_routes: bool,
+  /// Post-processing steps applied in order to the generated specification when building the app, before it is validated and served. See [`Spec::with_transformer`].
+  pub transformers: Vec<Transformer>,
+  /// Share the generated specification with every app documented with the same key in the process.
+  ///
+  /// `HttpServer::new` runs the app factory on every worker: with a cache key, the specification is only assembled, transformed, validated and serialized by the first app built,
+  /// later apps skipping its generation and serving the same cached specification. Apps sharing a key must document the same paths and methods,
+  /// building an app documenting other operations than the ones of the cached specification fails with [`BuildError::CacheConflict`](crate::validation::BuildError::CacheConflict).
+  ///
+  /// Once an app is documented with a cache key, the operation and components of each handler are also generated once per process, for the first route using it.
+  /// Cached specifications and handler definitions are kept for the lifetime of the process unless [`clear_cache`] is called.
+  pub cache_key: Option<String>,
 }
+
+impl Spec {
+  /// Add a transformer editing the generated specification, for example to rewrite descriptions, strip internal fields or add vendor extensions.
+  ///
+  /// Transformers run in the order they were added. A failing transformer aborts the build, see [`BuildError::Transformer`](crate::validation::BuildError::Transformer).
+  ///
+  /// ```rust,ignore
+  /// use apistos::spec::Spec;
+  ///
+  /// Spec::default().with_transformer(|open_api| {
+  ///   open_api.paths.paths.retain(|path, _| !path.starts_with("/internal"));
+  ///   Ok(())
+  /// });
+  /// ```
+  pub fn with_transformer<F>(mut self, transformer: F) -> Self
+  where
+    F: Fn(&mut OpenApi) -> Result<(), TransformerError> + Send + Sync + 'static,
+  {
+    self.transformers.push(Arc::new(transformer));
+    self
+  }
+}
+
+/// Remove the specifications cached under every [`Spec::cache_key`] and the memoized handler definitions, apps built afterwards generating their specification again.
+pub fn clear_cache() {
+  crate::internal::cache::clear();
+}
+
+/// Post-processing step editing a generated openapi specification.
+pub type Transformer = Arc<dyn Fn(&mut OpenApi) -> Result<(), TransformerError> + Send + Sync>;
+
+/// Error returned by a failing [`Transformer`].
+pub type TransformerError = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,176 @@
+//! Utilities to compare the generated openapi specification against a snapshot committed along with the tests.
+//!
+//! Requires the `testing` feature, usually only enabled for dev-dependencies:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! apistos = { version = "*", features = ["testing"] }
+//! ```
+//!
+//! ```rust,ignore
+//! use actix_web::App;
+//! use apistos::app::OpenApiWrapper;
+//! use apistos::assert_spec_snapshot;
+//!
+//! #[test]
+//! fn openapi_snapshot() {
+//!   assert_spec_snapshot!(|| App::new().document(todo!()).service(todo!()), "tests/snapshots/openapi.json");
+//! }
+//! ```
+//!
+//! Snapshots are written, or rewritten, instead of being compared when the [`UPDATE_SNAPSHOTS_ENV`] environment variable is set to `1`:
+//!
+//! ```sh
+//! APISTOS_UPDATE_SNAPSHOTS=1 cargo test
+//! ```
+
+use crate::app::App;
+use actix_service::ServiceFactory;
+use actix_web::dev::ServiceRequest;
+use actix_web::Error;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+/// Environment variable rewriting snapshots instead of comparing them when set to `1`.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "APISTOS_UPDATE_SNAPSHOTS";
+
+/// Assert the openapi specification generated by the app returned by **`app_factory`** matches the json snapshot at **`path`**, relative to the calling crate root.
+///
+/// See [`assert_spec_snapshot`](crate::testing::assert_spec_snapshot()).
+#[macro_export]
+macro_rules! assert_spec_snapshot {
+  ($app_factory:expr, $path:expr) => {
+    $crate::testing::assert_spec_snapshot(
+      $app_factory,
+      ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+    )
+  };
+}
+
+/// Assert the openapi specification generated by the app returned by **`app_factory`** matches the json snapshot at **`path`**.
+///
+/// The specification is generated the same way it would be exposed when building the app, with its transformers applied and its validation performed.
+/// Both the specification and the snapshot are normalized before being compared, so that the order of object keys and of `required` properties does not matter.
+///
+/// Panics listing every difference, scoped by their path in the specification, if they don't match or if the snapshot does not exist.
+/// When the [`UPDATE_SNAPSHOTS_ENV`] environment variable is set to `1`, the snapshot is written instead.
+#[track_caller]
+#[allow(clippy::panic)]
+pub fn assert_spec_snapshot<T, F, P>(app_factory: F, path: P)
+where
+  T: ServiceFactory<ServiceRequest, Config = (), Error = Error, InitError = ()>,
+  F: FnOnce() -> App<T>,
+  P: AsRef<Path>,
+{
+  let path = path.as_ref();
+  let open_api = match app_factory().built_open_api() {
+    Ok(open_api) => open_api,
+    Err(e) => panic!("{e}"),
+  };
+  let actual = match serde_json::to_value(&*open_api) {
+    Ok(actual) => normalize(actual),
+    Err(e) => panic!("unable to serialize openapi specification: {e}"),
+  };
+
+  if std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|update| update == "1") {
+    if let Err(e) = write_snapshot(path, &actual) {
+      panic!("unable to write snapshot {}: {e}", path.display());
+    }
+    return;
+  }
+
+  let snapshot = match fs::read_to_string(path) {
+    Ok(snapshot) => snapshot,
+    Err(e) => panic!(
+      "unable to read snapshot {}: {e}\nrun with {UPDATE_SNAPSHOTS_ENV}=1 to create it",
+      path.display()
+    ),
+  };
+  let expected = match serde_json::from_str(&snapshot) {
+    Ok(expected) => normalize(expected),
+    Err(e) => panic!("unable to parse snapshot {}: {e}", path.display()),
+  };
+
+  let mut differences = vec![];
+  diff("$", &expected, &actual, &mut differences);
+  assert!(
+    differences.is_empty(),
+    "openapi specification does not match snapshot {}:\n{}\nrun with {UPDATE_SNAPSHOTS_ENV}=1 to update it",
+    path.display(),
+    differences.join("\n")
+  );
+}
+
+fn write_snapshot(path: &Path, snapshot: &Value) -> std::io::Result<()> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  let mut json = serde_json::to_string_pretty(snapshot)?;
+  json.push('\n');
+  fs::write(path, json)
+}
+
+/// Sort object keys as well as `required` properties.
+fn normalize(value: Value) -> Value {
+  match value {
+    Value::Object(object) => {
+      let mut entries: Vec<(String, Value)> = object
+        .into_iter()
+        .map(|(key, value)| {
+          let value = match (key.as_str(), value) {
+            ("required", Value::Array(mut required)) if required.iter().all(Value::is_string) => {
+              required.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+              Value::Array(required)
+            }
+            (_, value) => normalize(value),
+          };
+          (key, value)
+        })
+        .collect();
+      entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+      Value::Object(entries.into_iter().collect::<Map<String, Value>>())
+    }
+    Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+    value => value,
+  }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+  if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+    format!("{path}.{key}")
+  } else {
+    format!("{path}[{key:?}]")
+  }
+}
+
+fn diff(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
+  match (expected, actual) {
+    (Value::Object(expected), Value::Object(actual)) => {
+      for (key, expected_value) in expected {
+        match actual.get(key) {
+          Some(actual_value) => diff(&child_path(path, key), expected_value, actual_value, differences),
+          None => differences.push(format!("- {}: {expected_value}", child_path(path, key))),
+        }
+      }
+      for (key, actual_value) in actual.iter().filter(|(key, _)| !expected.contains_key(*key)) {
+        differences.push(format!("+ {}: {actual_value}", child_path(path, key)));
+      }
+    }
+    (Value::Array(expected), Value::Array(actual)) => {
+      for (index, (expected_value, actual_value)) in expected.iter().zip(actual).enumerate() {
+        diff(&format!("{path}[{index}]"), expected_value, actual_value, differences);
+      }
+      for (index, expected_value) in expected.iter().enumerate().skip(actual.len()) {
+        differences.push(format!("- {path}[{index}]: {expected_value}"));
+      }
+      for (index, actual_value) in actual.iter().enumerate().skip(expected.len()) {
+        differences.push(format!("+ {path}[{index}]: {actual_value}"));
+      }
+    }
+    (expected, actual) if expected != actual => {
+      differences.push(format!("~ {path}: expected {expected}, got {actual}"));
+    }
+    _ => {}
+  }
+}
@@ -0,0 +1,378 @@
+//! Structural validation of generated openapi specifications.
+//!
+//! Documents are validated when the [`App`](crate::app::App) is built, the [`ValidationLevel`] of the document [`Spec`](crate::spec::Spec) defining how problems are reported.
+//!
+//! ```rust,ignore
+//! use actix_web::App;
+//! use apistos::app::OpenApiWrapper;
+//! use apistos::spec::Spec;
+//! use apistos::validation::ValidationLevel;
+//!
+//! let app = App::new()
+//!   .document(Spec {
+//!     validation: ValidationLevel::Error,
+//!     ..Default::default()
+//!   })
+//!   .service(todo!())
+//!   .try_build("/openapi.json")
+//!   .expect("Invalid openapi specification");
+//! ```
+
+use crate::internal::references::{
+  collect_references, is_declared_component, parse_component_reference, COMPONENTS_REFERENCE_PREFIX,
+};
+use crate::spec::TransformerError;
+use apistos_models::paths::{Link, OperationIdentifier, OperationType, Parameter, ParameterIn};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::security::{SecurityScheme, SecurityType};
+use apistos_models::{OpenApi, OpenApiVersion};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// How problems found while validating a document are reported when building the [`App`](crate::app::App).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationLevel {
+  /// Skip validation.
+  Ignore,
+  /// Log a warning for each problem.
+  #[default]
+  Log,
+  /// Fail building the app. `try_build` and `try_build_with` return a [`BuildError`] while `build` and `build_with` panic.
+  Error,
+  /// Panic listing every problem.
+  Panic,
+}
+
+/// A problem found in a generated openapi specification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+  /// Path, webhook, component or document field the problem was found in.
+  pub location: String,
+  /// Method of the operation the problem was found in, if any.
+  pub method: Option<OperationType>,
+  pub kind: ValidationErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+  /// A `$ref` targets a component which is not declared in the document.
+  DanglingReference(String),
+  /// The operation id is already used by another operation.
+  DuplicateOperationId(String),
+  /// The tag is not declared in the document tags.
+  UndeclaredTag(String),
+  /// A path parameter has no matching `{}` template in the path.
+  UnknownPathParameter(String),
+  /// A link targets an operation id which is not used by any operation of the document.
+  UnknownLinkOperation { link: String, operation_id: String },
+  /// A link targets a handler which is not documented in the document, the link being removed from the document.
+  UnknownLinkHandler { link: String, handler: String },
+  /// A link targets a handler documented by several operations, e.g. mounted at several paths, the link being removed from the document.
+  AmbiguousLinkHandler {
+    link: String,
+    handler: String,
+    operation_ids: Vec<String>,
+  },
+  /// The request body documents no media type, e.g. when a content type guard restricts it to a content type it does not document.
+  MissingRequestBodyContent,
+  /// The field or value is only supported by OAS 3.1 while the document uses OAS 3.0.
+  UnsupportedByOas30(String),
+  /// The license declares both an `identifier` and an `url`, which are mutually exclusive.
+  ConflictingLicense,
+}
+
+impl Display for ValidationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match &self.method {
+      Some(method) => write!(f, "{} {}: ", method.to_string().to_uppercase(), self.location)?,
+      None => write!(f, "{}: ", self.location)?,
+    }
+    match &self.kind {
+      ValidationErrorKind::DanglingReference(reference) => {
+        write!(f, "reference {reference} targets an undeclared component")
+      }
+      ValidationErrorKind::DuplicateOperationId(operation_id) => {
+        write!(f, "operation id {operation_id} is used by multiple operations")
+      }
+      ValidationErrorKind::UndeclaredTag(tag) => write!(f, "tag {tag} is not declared in the document tags"),
+      ValidationErrorKind::UnknownPathParameter(name) => {
+        write!(f, "path parameter {name} does not match any template of the path")
+      }
+      ValidationErrorKind::UnknownLinkOperation { link, operation_id } => {
+        write!(f, "link {link} targets unknown operation {operation_id}")
+      }
+      ValidationErrorKind::UnknownLinkHandler { link, handler } => {
+        write!(
+          f,
+          "link {link} targets handler {handler} which is not documented in this document"
+        )
+      }
+      ValidationErrorKind::AmbiguousLinkHandler {
+        link,
+        handler,
+        operation_ids,
+      } => {
+        write!(
+          f,
+          "link {link} targets handler {handler} which is documented by several operations: {}",
+          operation_ids.join(", ")
+        )
+      }
+      ValidationErrorKind::MissingRequestBodyContent => write!(f, "request body does not document any media type"),
+      ValidationErrorKind::UnsupportedByOas30(name) => write!(f, "{name} is only supported by OAS 3.1"),
+      ValidationErrorKind::ConflictingLicense => write!(f, "license identifier and url are mutually exclusive"),
+    }
+  }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Error returned when building an app whose documents could not be generated.
+#[derive(Debug)]
+pub enum BuildError {
+  /// The document failed validation with [`ValidationLevel::Error`].
+  Invalid {
+    /// Name of the invalid document, `None` for the default document.
+    document: Option<String>,
+    errors: Vec<ValidationError>,
+  },
+  /// One of the [`Spec::transformers`](crate::spec::Spec::transformers) of the document failed.
+  Transformer {
+    /// Name of the document being transformed, `None` for the default document.
+    document: Option<String>,
+    error: TransformerError,
+  },
+  /// The specification cached under the [`Spec::cache_key`](crate::spec::Spec::cache_key) of the document was generated by an app documenting other paths or methods.
+  CacheConflict {
+    /// Name of the document, `None` for the default document.
+    document: Option<String>,
+    cache_key: String,
+  },
+}
+
+impl Display for BuildError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BuildError::Invalid { document, errors } => {
+        match document {
+          Some(document) => write!(f, "openapi document {document} is invalid:")?,
+          None => write!(f, "openapi document is invalid:")?,
+        }
+        for error in errors {
+          write!(f, "\n  - {error}")?;
+        }
+        Ok(())
+      }
+      BuildError::Transformer { document, error } => match document {
+        Some(document) => write!(f, "failed to transform openapi document {document}: {error}"),
+        None => write!(f, "failed to transform openapi document: {error}"),
+      },
+      BuildError::CacheConflict { document, cache_key } => match document {
+        Some(document) => write!(
+          f,
+          "openapi document {document} documents other operations than the specification cached under key {cache_key}"
+        ),
+        None => write!(
+          f,
+          "openapi document documents other operations than the specification cached under key {cache_key}"
+        ),
+      },
+    }
+  }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Validate the given openapi specification, returning every problem found:
+/// - references to undeclared components
+/// - operation ids used by multiple operations
+/// - operation tags missing from the document tags
+/// - path parameters without matching template in their path
+/// - links targeting an operation id not used by any operation of the document
+/// - request bodies without any media type
+/// - info summary, license identifier and mutual TLS security schemes in OAS 3.0 documents
+/// - licenses declaring both an identifier and an url
+pub fn validate(open_api: &OpenApi) -> Vec<ValidationError> {
+  let mut errors = vec![];
+  let is_oas3_0 = matches!(open_api.openapi, OpenApiVersion::OAS3_0);
+  let mut document_error = |location: &str, kind| {
+    errors.push(ValidationError {
+      location: location.to_owned(),
+      method: None,
+      kind,
+    })
+  };
+  if is_oas3_0 && open_api.info.summary.is_some() {
+    document_error("#/info", ValidationErrorKind::UnsupportedByOas30("summary".to_owned()));
+  }
+  if let Some(license) = &open_api.info.license {
+    if is_oas3_0 && license.identifier.is_some() {
+      document_error(
+        "#/info/license",
+        ValidationErrorKind::UnsupportedByOas30("identifier".to_owned()),
+      );
+    }
+    if license.identifier.is_some() && license.url.is_some() {
+      document_error("#/info/license", ValidationErrorKind::ConflictingLicense);
+    }
+  }
+  let declared_tags: BTreeSet<&str> = open_api.tags.iter().map(|tag| tag.name.as_str()).collect();
+  let mut operation_ids = BTreeSet::new();
+  let path_operation_ids: BTreeSet<&str> = open_api
+    .paths
+    .paths
+    .values()
+    .flat_map(|pi| pi.operations.values())
+    .filter_map(|operation| operation.operation_id.as_deref())
+    .collect();
+
+  let path_operations = open_api.paths.paths.iter().map(|(path, pi)| (path.clone(), pi, true));
+  let webhook_operations = open_api.webhooks.iter().filter_map(|(name, webhook)| match webhook {
+    ReferenceOr::Object(pi) => Some((format!("webhook {name}"), pi, false)),
+    ReferenceOr::Reference { .. } => None,
+  });
+  for (location, path_item, is_path) in path_operations.chain(webhook_operations) {
+    for (method, operation) in &path_item.operations {
+      let mut error = |kind| {
+        errors.push(ValidationError {
+          location: location.clone(),
+          method: Some(method.clone()),
+          kind,
+        })
+      };
+
+      if let Ok(value) = serde_json::to_value(operation) {
+        for reference in dangling_references(open_api, &value) {
+          error(ValidationErrorKind::DanglingReference(reference));
+        }
+      }
+
+      if let Some(operation_id) = &operation.operation_id {
+        if !operation_ids.insert(operation_id.clone()) {
+          error(ValidationErrorKind::DuplicateOperationId(operation_id.clone()));
+        }
+      }
+
+      operation
+        .tags
+        .iter()
+        .filter(|tag| !declared_tags.contains(tag.as_str()))
+        .for_each(|tag| error(ValidationErrorKind::UndeclaredTag(tag.clone())));
+
+      if let Some(ReferenceOr::Object(request_body)) = &operation.request_body {
+        if request_body.content.is_empty() {
+          error(ValidationErrorKind::MissingRequestBodyContent);
+        }
+      }
+
+      if is_path {
+        let templates = path_templates(&location);
+        operation
+          .parameters
+          .iter()
+          .filter_map(|parameter| resolve_parameter(open_api, parameter))
+          .filter(|parameter| parameter._in == ParameterIn::Path && !templates.contains(parameter.name.as_str()))
+          .for_each(|parameter| error(ValidationErrorKind::UnknownPathParameter(parameter.name.clone())));
+      }
+
+      let links = operation
+        .responses
+        .default
+        .iter()
+        .chain(operation.responses.responses.values())
+        .filter_map(|response| match response {
+          ReferenceOr::Object(response) => Some(&response.links),
+          ReferenceOr::Reference { .. } => None,
+        })
+        .flatten();
+      let mut unknown_targets = BTreeSet::new();
+      for (name, link) in links {
+        if let ReferenceOr::Object(Link {
+          operation_identifier: Some(OperationIdentifier::OperationId(operation_id)),
+          ..
+        }) = link
+        {
+          if !path_operation_ids.contains(operation_id.as_str()) {
+            unknown_targets.insert((name.clone(), operation_id.clone()));
+          }
+        }
+      }
+      for (link, operation_id) in unknown_targets {
+        error(ValidationErrorKind::UnknownLinkOperation { link, operation_id });
+      }
+    }
+  }
+
+  if let Some(Ok(Value::Object(components))) = open_api.components.as_ref().map(serde_json::to_value) {
+    let declared_components = components.iter().filter(|(kind, _)| !kind.starts_with("x-"));
+    for (kind, declared) in declared_components {
+      for (name, component) in declared.as_object().into_iter().flatten() {
+        for reference in dangling_references(open_api, component) {
+          errors.push(ValidationError {
+            location: format!("{COMPONENTS_REFERENCE_PREFIX}{kind}/{name}"),
+            method: None,
+            kind: ValidationErrorKind::DanglingReference(reference),
+          });
+        }
+      }
+    }
+  }
+
+  if is_oas3_0 {
+    let security_schemes = open_api.components.iter().flat_map(|c| &c.security_schemes);
+    for (name, security_scheme) in security_schemes {
+      if let ReferenceOr::Object(SecurityScheme {
+        _type: SecurityType::MutualTLS,
+        ..
+      }) = security_scheme
+      {
+        errors.push(ValidationError {
+          location: format!("{COMPONENTS_REFERENCE_PREFIX}securitySchemes/{name}"),
+          method: None,
+          kind: ValidationErrorKind::UnsupportedByOas30("mutualTLS".to_owned()),
+        });
+      }
+    }
+  }
+
+  errors
+}
+
+/// References to undeclared components found in the given value. References to external documents are not checked.
+fn dangling_references(open_api: &OpenApi, value: &Value) -> Vec<String> {
+  let mut references = BTreeSet::new();
+  collect_references(value, &mut references);
+  references
+    .into_iter()
+    .filter(|reference| {
+      reference.starts_with(COMPONENTS_REFERENCE_PREFIX)
+        && !is_declared_component(open_api.components.as_ref(), reference)
+    })
+    .collect()
+}
+
+fn resolve_parameter<'a>(open_api: &'a OpenApi, parameter: &'a ReferenceOr<Parameter>) -> Option<&'a Parameter> {
+  match parameter {
+    ReferenceOr::Object(parameter) => Some(parameter),
+    ReferenceOr::Reference { _ref } => {
+      let (kind, name) = parse_component_reference(_ref)?;
+      if kind != "parameters" {
+        return None;
+      }
+      match open_api.components.as_ref()?.parameters.get(&name)? {
+        ReferenceOr::Object(parameter) => Some(parameter),
+        ReferenceOr::Reference { .. } => None,
+      }
+    }
+  }
+}
+
+/// Names of the `{}` templates of the given path.
+fn path_templates(path: &str) -> BTreeSet<&str> {
+  path
+    .split('{')
+    .skip(1)
+    .filter_map(|segment| segment.split_once('}').map(|(name, _)| name))
+    .collect()
+}
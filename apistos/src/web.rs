@@ -2,3 +2,4 @@ pub use crate::internal::actix::resource::*;
 pub use crate::internal::actix::route::*;
 pub use crate::internal::actix::scope::*;
 pub use crate::internal::actix::service_config::*;
+pub use crate::internal::actix::transform::DocumentedTransform;
@@ -0,0 +1,114 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::Spec;
+use apistos::validation::BuildError;
+use apistos::web::{get, resource};
+use apistos_gen::api_operation;
+use apistos_models::OpenApi;
+
+#[api_operation(summary = "Get orders")]
+pub(crate) async fn get_orders() -> Result<Json<Vec<u32>>, Error> {
+  Ok(Json(vec![]))
+}
+
+#[api_operation(summary = "Get invoices")]
+pub(crate) async fn get_invoices() -> Result<Json<Vec<u32>>, Error> {
+  Ok(Json(vec![]))
+}
+
+fn spec(cache_key: &str) -> Spec {
+  Spec {
+    cache_key: Some(cache_key.to_string()),
+    ..Default::default()
+  }
+}
+
+#[actix_web::test]
+async fn open_api_built_once_per_cache_key() {
+  let first_app = App::new()
+    .document(spec("cache_test"))
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/invoices").route(get().to(get_invoices)))
+    .build("/openapi.json");
+  let first_app = init_service(first_app).await;
+
+  // Apps sharing a cache key reuse the specification of the first app built instead of generating their own.
+  let second_app = App::new()
+    .document(spec("cache_test"))
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/invoices").route(get().to(get_invoices)));
+  assert_eq!(
+    second_app.open_api().paths.paths.keys().collect::<Vec<_>>(),
+    vec!["/orders", "/invoices"]
+  );
+  let second_app = init_service(second_app.build("/openapi.json")).await;
+
+  let resp = call_service(&first_app, TestRequest::get().uri("/openapi.json").to_request()).await;
+  assert!(resp.status().is_success());
+  let first_etag = resp.headers().get("ETag").cloned().expect("Missing etag");
+  let first_open_api: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+
+  let resp = call_service(&second_app, TestRequest::get().uri("/openapi.json").to_request()).await;
+  assert!(resp.status().is_success());
+  assert_eq!(resp.headers().get("ETag"), Some(&first_etag));
+  let second_open_api: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+
+  assert_eq!(first_open_api, second_open_api);
+
+  let resp = call_service(&second_app, TestRequest::get().uri("/invoices").to_request()).await;
+  assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn cache_key_reused_for_other_operations() {
+  let first_app = App::new()
+    .document(spec("cache_conflict_test"))
+    .service(resource("/orders").route(get().to(get_orders)))
+    .try_build("/openapi.json");
+  assert!(first_app.is_ok());
+
+  // An app documenting other operations under the same key would otherwise serve a specification missing them.
+  let second_app = App::new()
+    .document(spec("cache_conflict_test"))
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/invoices").route(get().to(get_invoices)))
+    .try_build("/openapi.json");
+  match second_app {
+    Err(BuildError::CacheConflict { document, cache_key }) => {
+      assert_eq!(document, None);
+      assert_eq!(cache_key, "cache_conflict_test");
+    }
+    Err(e) => panic!("Unexpected build error: {e}"),
+    Ok(_) => panic!("Expected app documenting other operations to be rejected"),
+  }
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use schemars as _;
+use serde as _;
+use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -0,0 +1,279 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+use actix_web::web::{Json, Path, Query};
+use actix_web::{App, ResponseError};
+use apistos::app::OpenApiWrapper;
+use apistos::contract::{
+  ContractViolation, ContractViolationKind, RequestValidation, ResponseContract, SchemaViolation,
+};
+use apistos::spec::Spec;
+use apistos::web::{get, post, resource, scope};
+use apistos_gen::{api_operation, ApiComponent, ApiErrorComponent};
+use apistos_models::info::Info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+#[derive(Serialize, Deserialize, Debug, Clone, ApiErrorComponent)]
+#[openapi_error(status(code = 404, description = "Pet not found"))]
+pub(crate) enum ErrorResponse {
+  NotFound,
+  Unavailable,
+}
+
+impl Display for ErrorResponse {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{self:?}")
+  }
+}
+
+impl ResponseError for ErrorResponse {
+  fn status_code(&self) -> StatusCode {
+    match self {
+      ErrorResponse::NotFound => StatusCode::NOT_FOUND,
+      ErrorResponse::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) struct Pet {
+  id: u32,
+  #[schemars(length(max = 8))]
+  name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) enum Status {
+  Available,
+  Sold,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) struct PetQuery {
+  #[schemars(range(max = 100))]
+  limit: Option<u32>,
+  status: Option<Status>,
+}
+
+#[api_operation(tag = "pet")]
+pub(crate) async fn list_pets(_query: Query<PetQuery>) -> Result<Json<Vec<Pet>>, ErrorResponse> {
+  Ok(Json(vec![]))
+}
+
+#[api_operation(tag = "pet")]
+pub(crate) async fn add_pet(body: Json<Pet>) -> Result<Json<Pet>, ErrorResponse> {
+  Ok(body)
+}
+
+#[api_operation(tag = "pet")]
+pub(crate) async fn get_pet(id: Path<u32>) -> Result<Json<Pet>, ErrorResponse> {
+  match id.into_inner() {
+    1 => Ok(Json(Pet {
+      id: 1,
+      name: "Rex".to_string(),
+    })),
+    2 => Ok(Json(Pet {
+      id: 2,
+      name: "Garfield the cat".to_string(),
+    })),
+    3 => Err(ErrorResponse::NotFound),
+    _ => Err(ErrorResponse::Unavailable),
+  }
+}
+
+fn app() -> apistos::app::App<
+  impl actix_service::ServiceFactory<
+    actix_web::dev::ServiceRequest,
+    Config = (),
+    Response = actix_web::dev::ServiceResponse,
+    Error = actix_web::Error,
+    InitError = (),
+  >,
+> {
+  App::new()
+    .document(Spec {
+      info: Info {
+        title: "Pets".to_string(),
+        ..Default::default()
+      },
+      ..Default::default()
+    })
+    .service(
+      scope("/pets")
+        .service(resource("").route(get().to(list_pets)).route(post().to(add_pet)))
+        .service(resource("/{id}").route(get().to(get_pet))),
+    )
+}
+
+#[actix_web::test]
+async fn response_contract_violations() {
+  let app = app();
+
+  let violations: Arc<Mutex<Vec<ContractViolation>>> = Default::default();
+  let contract = ResponseContract::new(app.built_open_api().expect("Unable to build openapi")).on_violation({
+    let violations = violations.clone();
+    move |violation| violations.lock().expect("Poisoned lock").push(violation.clone())
+  });
+  let app = init_service(app.build("/openapi.json").wrap(contract.clone())).await;
+
+  for uri in ["/pets/1", "/pets/3", "/pets/1/owner"] {
+    call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+  }
+  assert_eq!(contract.violations(), 0);
+
+  let res = call_service(&app, TestRequest::get().uri("/pets/2").to_request()).await;
+  let body: Value = read_body_json(res).await;
+  assert_eq!(body, json!({ "id": 2, "name": "Garfield the cat" }));
+
+  let res = call_service(&app, TestRequest::get().uri("/pets/4").to_request()).await;
+  assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+  assert_eq!(contract.violations(), 2);
+  let violations = violations.lock().expect("Poisoned lock");
+  assert_eq!(
+    violations
+      .iter()
+      .map(|violation| (violation.path.as_str(), violation.operation.as_str(), violation.status))
+      .collect::<Vec<_>>(),
+    vec![
+      ("/pets/2", "/pets/{id}", StatusCode::OK),
+      ("/pets/4", "/pets/{id}", StatusCode::SERVICE_UNAVAILABLE)
+    ]
+  );
+  assert_eq!(
+    violations[0].kind,
+    ContractViolationKind::InvalidBody(vec![SchemaViolation {
+      pointer: "/name".to_string(),
+      message: "string is longer than 8 characters".to_string(),
+    }])
+  );
+  assert_eq!(violations[1].kind, ContractViolationKind::UndocumentedStatus);
+  assert_eq!(
+    violations[1].to_string(),
+    "GET /pets/4 (/pets/{id}): status 503 Service Unavailable is not documented"
+  );
+}
+
+#[actix_web::test]
+async fn request_validation_errors() {
+  let app = app();
+  let validation = RequestValidation::new(app.built_open_api().expect("Unable to build openapi"));
+  let app = init_service(app.build("/openapi.json").wrap(validation)).await;
+
+  let cases = [
+    (TestRequest::get().uri("/pets?limit=10&status=Sold"), vec![]),
+    (TestRequest::get().uri("/pets/1"), vec![]),
+    (
+      TestRequest::post()
+        .uri("/pets")
+        .set_json(json!({ "id": 1, "name": "Rex" })),
+      vec![],
+    ),
+    (
+      TestRequest::get().uri("/pets/rex"),
+      vec![("/path/id", "expected integer, found string")],
+    ),
+    (
+      TestRequest::get().uri("/pets?limit=500&status=Lost"),
+      vec![
+        ("/query/limit", "value is greater than 100"),
+        ("/query/status", "value is not one of \"Available\", \"Sold\""),
+      ],
+    ),
+    (
+      TestRequest::post()
+        .uri("/pets")
+        .set_json(json!({ "id": -1, "name": "Garfield the cat" })),
+      vec![
+        ("/body/id", "value is lower than 0"),
+        ("/body/name", "string is longer than 8 characters"),
+      ],
+    ),
+    (
+      TestRequest::post().uri("/pets"),
+      vec![("/body", "required body is missing")],
+    ),
+    (
+      TestRequest::post()
+        .uri("/pets")
+        .insert_header((CONTENT_TYPE, "text/plain"))
+        .set_payload("Rex"),
+      vec![("/body", "content type text/plain is not documented")],
+    ),
+  ];
+
+  for (req, expected) in cases {
+    let res = call_service(&app, req.to_request()).await;
+    if expected.is_empty() {
+      assert_eq!(res.status(), StatusCode::OK);
+      continue;
+    }
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body: Value = read_body_json(res).await;
+    let errors: Vec<Value> = expected
+      .into_iter()
+      .map(|(pointer, message)| json!({ "pointer": pointer, "message": message }))
+      .collect();
+    assert_eq!(body, json!({ "errors": errors }));
+  }
+}
+
+#[actix_web::test]
+async fn request_validation_body_limit() {
+  // larger than the default `PayloadConfig` limit but accepted by the default `JsonConfig` of the handler
+  let pet = json!({ "id": 1, "name": "Rex", "description": "a".repeat(512 * 1024) });
+
+  let app = app();
+  let validation = RequestValidation::new(app.built_open_api().expect("Unable to build openapi"));
+  let app = init_service(app.build("/openapi.json").wrap(validation)).await;
+  let req = TestRequest::post().uri("/pets").set_json(&pet).to_request();
+  let res = call_service(&app, req).await;
+  assert_eq!(res.status(), StatusCode::OK);
+
+  let app = self::app();
+  let validation = RequestValidation::new(app.built_open_api().expect("Unable to build openapi")).with_body_limit(1024);
+  let app = init_service(app.build("/openapi.json").wrap(validation)).await;
+  let req = TestRequest::post().uri("/pets").set_json(&pet).to_request();
+  let res = call_service(&app, req).await;
+  assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[actix_web::test]
+async fn response_contract_body_limit() {
+  let app = app();
+  let contract = ResponseContract::new(app.built_open_api().expect("Unable to build openapi")).with_body_limit(8);
+  let app = init_service(app.build("/openapi.json").wrap(contract.clone())).await;
+
+  let res = call_service(&app, TestRequest::get().uri("/pets/2").to_request()).await;
+  let body: Value = read_body_json(res).await;
+  assert_eq!(body, json!({ "id": 2, "name": "Garfield the cat" }));
+  assert_eq!(contract.violations(), 0);
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -182,6 +182,10 @@ use apistos_rapidoc as _;
 use apistos_redoc as _;
 use apistos_scalar as _;
 use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
 use garde_actix_web as _;
 use indexmap as _;
 use log as _;
@@ -189,3 +193,5 @@ use md5 as _;
 use once_cell as _;
 use regex as _;
 use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
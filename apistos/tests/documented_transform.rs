@@ -0,0 +1,171 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_service::{Service, Transform};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::test::{call_service, init_service, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::{DefaultSecurity, DefaultSecurityAccessor, Spec};
+use apistos::web::{get, resource, scope, DocumentedTransform};
+use apistos_gen::{api_operation, ApiComponent, ApiSecurity};
+use apistos_models::paths::{OperationType, Parameter, ParameterIn, Response};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::OpenApi;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::future::{ready, Ready};
+
+#[derive(ApiSecurity)]
+#[openapi_security(scheme(security_type(api_key(name = "api_key", api_key_in = "header"))))]
+struct ApiKey;
+
+/// Middleware passing requests through, documented as requiring an api key and a tenant header.
+struct Auth;
+
+impl<S, B> Transform<S, ServiceRequest> for Auth
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Transform = S;
+  type InitError = ();
+  type Future = Ready<Result<S, ()>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(service))
+  }
+}
+
+impl DocumentedTransform for Auth {
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![Parameter {
+      name: "X-Tenant".to_string(),
+      _in: ParameterIn::Header,
+      required: Some(true),
+      ..Default::default()
+    }]
+  }
+
+  fn responses(&self) -> BTreeMap<String, ReferenceOr<Response>> {
+    BTreeMap::from_iter(vec![(
+      "401".to_string(),
+      ReferenceOr::Object(Response {
+        description: "Unauthorized".to_string(),
+        ..Default::default()
+      }),
+    )])
+  }
+
+  fn security(&self) -> Vec<DefaultSecurity> {
+    vec![ApiKey::get_default_security()]
+  }
+
+  fn tags(&self) -> Vec<String> {
+    vec!["authenticated".to_string()]
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(summary = "Get orders")]
+pub(crate) async fn get_orders() -> Result<Json<Vec<Order>>, Error> {
+  Ok(Json(vec![]))
+}
+
+fn operation(open_api: &OpenApi, path: &str) -> serde_json::Value {
+  let operation = open_api
+    .paths
+    .paths
+    .get(path)
+    .and_then(|pi| pi.operations.get(&OperationType::Get))
+    .cloned()
+    .expect("Missing operation");
+  json!({
+    "parameters": operation.parameters,
+    "responses": operation.responses.responses.keys().collect::<Vec<_>>(),
+    "security": operation.security,
+    "tags": operation.tags,
+  })
+}
+
+fn documented_operation() -> serde_json::Value {
+  json!({
+    "parameters": [{ "name": "X-Tenant", "in": "header", "required": true }],
+    "responses": ["200", "401"],
+    "security": [{ "api_key": [] }],
+    "tags": ["authenticated"],
+  })
+}
+
+#[actix_web::test]
+async fn documented_transforms() {
+  let open_api = App::new()
+    .document(Spec::default())
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(
+      resource("/resource-orders")
+        .route(get().to(get_orders))
+        .wrap_documented(Auth),
+    )
+    .service(
+      scope("/scope")
+        .wrap_documented(Auth)
+        .service(resource("/orders").route(get().to(get_orders))),
+    )
+    .open_api();
+
+  assert_eq!(
+    operation(&open_api, "/orders"),
+    json!({ "parameters": [], "responses": ["200"], "security": [], "tags": [] })
+  );
+  assert_eq!(operation(&open_api, "/resource-orders"), documented_operation());
+  assert_eq!(operation(&open_api, "/scope/orders"), documented_operation());
+
+  let security_schemes = open_api.components.expect("Missing components").security_schemes;
+  assert!(security_schemes.contains_key("api_key"));
+}
+
+#[actix_web::test]
+async fn documented_app_transform() {
+  let app = App::new()
+    .document(Spec::default())
+    .service(resource("/orders").route(get().to(get_orders)))
+    .wrap_documented(Auth);
+
+  let open_api = app.open_api();
+  assert_eq!(operation(&open_api, "/orders"), documented_operation());
+
+  let app = init_service(app.build("/openapi.json")).await;
+  let req = TestRequest::get().uri("/orders").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
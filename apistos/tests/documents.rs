@@ -0,0 +1,242 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::{BuildConfig, OpenApiWrapper};
+use apistos::spec::{DefaultParameters, Spec};
+use apistos::web::{get, resource, scope};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::info::Info;
+use apistos_models::paths::{Parameter, ParameterIn};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::tag::Tag;
+use apistos_models::OpenApi;
+use apistos_swagger_ui::SwaggerUIConfig;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[actix_web::test]
+async fn named_documents() {
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct PetV1 {
+    id: u32,
+  }
+
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct PetV2 {
+    id: String,
+  }
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet_v1() -> Result<Json<PetV1>, Error> {
+    panic!()
+  }
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet_v2() -> Result<Json<PetV2>, Error> {
+    panic!()
+  }
+
+  let v1_info = Info {
+    title: "Pets API".to_string(),
+    version: "1.0.0".to_string(),
+    ..Default::default()
+  };
+  let v2_info = Info {
+    title: "Pets API".to_string(),
+    version: "2.0.0".to_string(),
+    ..Default::default()
+  };
+  let v2_tags = vec![Tag {
+    name: "pet".to_owned(),
+    ..Default::default()
+  }];
+
+  let app = App::new()
+    .document(Spec {
+      info: v1_info.clone(),
+      ..Default::default()
+    })
+    .named_document(
+      "v2",
+      Spec {
+        info: v2_info.clone(),
+        tags: v2_tags.clone(),
+        ..Default::default()
+      },
+    )
+    .service(scope("/v1").service(resource("/pet").route(get().to(get_pet_v1))))
+    .service(
+      scope("/v2")
+        .document("v2")
+        .service(resource("/pet").route(get().to(get_pet_v2))),
+    );
+
+  assert!(app.named_open_api("v3").is_none());
+
+  let app = app.build_with(
+    "/openapi/v1.json",
+    BuildConfig::default().with_document(
+      "v2",
+      "/openapi/v2.json",
+      BuildConfig::default().with(SwaggerUIConfig::new(&"/swagger/v2")),
+    ),
+  );
+  let app = init_service(app).await;
+
+  let req = TestRequest::get().uri("/openapi/v1.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+  assert_eq!(body.info, v1_info);
+  assert!(body.tags.is_empty());
+  assert_eq!(body.paths.paths.keys().collect::<Vec<_>>(), vec!["/v1/pet"]);
+  let schemas = body.components.expect("Missing components").schemas;
+  assert!(schemas.contains_key("PetV1"));
+  assert!(!schemas.contains_key("PetV2"));
+
+  let req = TestRequest::get().uri("/openapi/v2.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+  assert_eq!(body.info, v2_info);
+  assert_eq!(body.tags, v2_tags);
+  assert_eq!(body.paths.paths.keys().collect::<Vec<_>>(), vec!["/v2/pet"]);
+  let schemas = body.components.expect("Missing components").schemas;
+  assert!(schemas.contains_key("PetV2"));
+  assert!(!schemas.contains_key("PetV1"));
+
+  let req = TestRequest::get().uri("/swagger/v2").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn components_accumulated_across_services() {
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct Pet {
+    id: u32,
+  }
+
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct Store {
+    id: u32,
+  }
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet() -> Result<Json<Pet>, Error> {
+    panic!()
+  }
+
+  #[api_operation(tag = "store")]
+  pub(crate) async fn get_store() -> Result<Json<Store>, Error> {
+    panic!()
+  }
+
+  let open_api = App::new()
+    .document(Spec {
+      default_tags: vec!["api".to_owned()],
+      ..Default::default()
+    })
+    .service(resource("/pet").route(get().to(get_pet)))
+    .service(resource("/store").route(get().to(get_store)))
+    .open_api();
+
+  let schemas = open_api.components.expect("Missing components").schemas;
+  assert!(schemas.contains_key("Pet"));
+  assert!(schemas.contains_key("Store"));
+
+  let tags: Vec<Vec<String>> = open_api
+    .paths
+    .paths
+    .values()
+    .flat_map(|path_item| path_item.operations.values())
+    .map(|operation| operation.tags.clone())
+    .collect();
+  assert_eq!(
+    tags,
+    vec![
+      vec!["pet".to_owned(), "api".to_owned()],
+      vec!["store".to_owned(), "api".to_owned()]
+    ]
+  );
+}
+
+#[actix_web::test]
+async fn unreferenced_components_kept_in_default_document() {
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet() -> Result<Json<u32>, Error> {
+    panic!()
+  }
+
+  let open_api = App::new()
+    .document(Spec {
+      default_parameters: vec![DefaultParameters {
+        parameters: vec![Parameter {
+          name: "X-Tenant".to_string(),
+          _in: ParameterIn::Header,
+          ..Default::default()
+        }],
+        components: vec![(
+          "Tenant".to_string(),
+          ReferenceOr::Object(Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            ..Default::default()
+          })),
+        )],
+      }],
+      ..Default::default()
+    })
+    .service(resource("/pet").route(get().to(get_pet)))
+    .open_api();
+
+  let schemas = open_api.components.expect("Missing components").schemas;
+  assert!(schemas.contains_key("Tenant"));
+}
+
+#[actix_web::test]
+#[should_panic(expected = "Scope assigned to openapi document v2 must be registered directly on the app")]
+async fn nested_scoped_document_rejected() {
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet() -> Result<Json<u32>, Error> {
+    panic!()
+  }
+
+  let _ = App::new()
+    .document(Spec::default())
+    .named_document("v2", Spec::default())
+    .service(
+      scope("/api").service(
+        scope("/v2")
+          .document("v2")
+          .service(resource("/pet").route(get().to(get_pet))),
+      ),
+    );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
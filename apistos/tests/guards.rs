@@ -0,0 +1,235 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::guard::{Guard, GuardContext};
+use actix_web::test::{call_service, init_service, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::guard::{self, DocumentedGuard};
+use apistos::spec::Spec;
+use apistos::validation::{validate, ValidationError, ValidationErrorKind};
+use apistos::web::{get, post, resource, scope};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::paths::{Operation, OperationType, Parameter, ParameterIn};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::OpenApi;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(summary = "Get orders")]
+pub(crate) async fn get_orders() -> Result<Json<Vec<Order>>, Error> {
+  Ok(Json(vec![]))
+}
+
+#[api_operation(summary = "Create an order", consumes = "application/json")]
+pub(crate) async fn create_order(body: Json<Order>) -> Result<Json<Order>, Error> {
+  Ok(body)
+}
+
+/// Custom guard only matching requests with a tenant header.
+struct Tenant;
+
+impl Guard for Tenant {
+  fn check(&self, ctx: &GuardContext<'_>) -> bool {
+    ctx.head().headers().contains_key("X-Tenant")
+  }
+}
+
+impl DocumentedGuard for Tenant {
+  fn parameters(&self) -> Vec<Parameter> {
+    vec![Parameter {
+      name: "X-Tenant".to_string(),
+      _in: ParameterIn::Header,
+      required: Some(true),
+      ..Default::default()
+    }]
+  }
+}
+
+fn operation(open_api: &OpenApi, path: &str, operation_type: &OperationType) -> Operation {
+  open_api
+    .paths
+    .paths
+    .get(path)
+    .and_then(|pi| pi.operations.get(operation_type))
+    .cloned()
+    .expect("Missing operation")
+}
+
+fn parameters(operation: &Operation) -> Vec<(String, ParameterIn)> {
+  operation
+    .parameters
+    .iter()
+    .cloned()
+    .filter_map(ReferenceOr::get_object)
+    .map(|p| (p.name, p._in))
+    .collect()
+}
+
+#[actix_web::test]
+async fn documented_guards() {
+  let app = App::new()
+    .document(Spec::default())
+    .service(
+      resource("/orders")
+        .guard_documented(guard::header("X-Api-Version", "2"))
+        .route(get().guard_documented(guard::query("status", "open")).to(get_orders))
+        .route(
+          post()
+            .guard_documented(guard::content_type("application/json"))
+            .to(create_order),
+        ),
+    )
+    .service(
+      scope("/tenant")
+        .guard_documented(guard::host("api.example.com"))
+        .guard_documented(Tenant)
+        .service(resource("/orders").route(get().to(get_orders))),
+    );
+
+  let open_api = app.open_api();
+
+  let get_operation = operation(&open_api, "/orders", &OperationType::Get);
+  assert_eq!(
+    parameters(&get_operation),
+    vec![
+      ("status".to_string(), ParameterIn::Query),
+      ("X-Api-Version".to_string(), ParameterIn::Header)
+    ]
+  );
+  assert_eq!(
+    serde_json::to_value(&get_operation.parameters[1]).expect("Unable to serialize as Json"),
+    json!({
+      "name": "X-Api-Version",
+      "in": "header",
+      "required": true,
+      "schema": {
+        "type": "string",
+        "enum": ["2"]
+      }
+    })
+  );
+
+  let post_operation = operation(&open_api, "/orders", &OperationType::Post);
+  let content_types = post_operation
+    .request_body
+    .and_then(ReferenceOr::get_object)
+    .map(|body| body.content.keys().cloned().collect::<Vec<_>>())
+    .expect("Missing request body");
+  assert_eq!(content_types, vec!["application/json".to_string()]);
+
+  let tenant_operation = operation(&open_api, "/tenant/orders", &OperationType::Get);
+  assert_eq!(
+    parameters(&tenant_operation),
+    vec![("X-Tenant".to_string(), ParameterIn::Header)]
+  );
+  assert_eq!(
+    tenant_operation
+      .servers
+      .iter()
+      .map(|s| s.url.clone())
+      .collect::<Vec<_>>(),
+    vec!["https://api.example.com".to_string()]
+  );
+
+  let app = init_service(app.build("/openapi.json")).await;
+
+  let req = TestRequest::get().uri("/orders?status=open").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_client_error());
+
+  let req = TestRequest::get()
+    .uri("/orders?status=open")
+    .insert_header(("X-Api-Version", "2"))
+    .to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let req = TestRequest::post()
+    .uri("/orders")
+    .insert_header(("X-Api-Version", "2"))
+    .insert_header(("Content-Type", "application/json; charset=utf-8"))
+    .set_payload(r#"{"id": 1}"#)
+    .to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn combined_guards() {
+  let app = App::new().document(Spec::default()).service(
+    scope("/tenant")
+      .guard_documented(guard::host("api.example.com"))
+      .service(
+        resource("/orders")
+          .guard_documented(guard::host("api.example.com"))
+          .route(get().to(get_orders))
+          .route(
+            post()
+              .guard_documented(guard::content_type("application/xml"))
+              .to(create_order),
+          ),
+      )
+      .service(
+        resource("/archived-orders")
+          .guard_documented(guard::host("archive.example.com"))
+          .route(get().to(get_orders)),
+      ),
+  );
+
+  let open_api = app.open_api();
+
+  let get_operation = operation(&open_api, "/tenant/orders", &OperationType::Get);
+  assert_eq!(
+    get_operation.servers.iter().map(|s| s.url.clone()).collect::<Vec<_>>(),
+    vec!["https://api.example.com".to_string()]
+  );
+
+  let archived_operation = operation(&open_api, "/tenant/archived-orders", &OperationType::Get);
+  assert!(archived_operation.servers.is_empty());
+
+  let post_operation = operation(&open_api, "/tenant/orders", &OperationType::Post);
+  let content_types = post_operation
+    .request_body
+    .and_then(ReferenceOr::get_object)
+    .map(|body| body.content.keys().cloned().collect::<Vec<_>>())
+    .expect("Missing request body");
+  assert!(content_types.is_empty());
+  assert_eq!(
+    validate(&open_api),
+    vec![ValidationError {
+      location: "/tenant/orders".to_string(),
+      method: Some(OperationType::Post),
+      kind: ValidationErrorKind::MissingRequestBodyContent,
+    }]
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -0,0 +1,209 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::web::{Json, Path};
+use actix_web::{App, Error};
+use apistos::actix::CreatedJson;
+use apistos::app::OpenApiWrapper;
+use apistos::spec::Spec;
+use apistos::validation::{BuildError, ValidationError, ValidationErrorKind, ValidationLevel};
+use apistos::web::{get, post, resource, scope};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::paths::{AnyOrExpression, OperationIdentifier, OperationType, Response};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::OpenApi;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(summary = "Get an order")]
+pub(crate) async fn get_order(_id: Path<u32>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+#[api_operation(summary = "Cancel an order", operation_id = "cancel_order")]
+pub(crate) async fn cancel_order(_id: Path<u32>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+#[api_operation(
+  summary = "Create an order",
+  link(
+    name = "GetOrder",
+    operation = get_order,
+    parameter(name = "id", expression = "$response.body#/id")
+  ),
+  link(name = "CancelOrder", operation_id = "cancel_order"),
+  link(name = "UnknownOrder", operation_id = "unknown_order")
+)]
+pub(crate) async fn create_order(_body: Json<Order>) -> Result<CreatedJson<Order>, Error> {
+  panic!()
+}
+
+fn create_order_response(open_api: &OpenApi) -> Response {
+  open_api
+    .paths
+    .paths
+    .get("/orders")
+    .and_then(|pi| pi.operations.get(&OperationType::Post))
+    .and_then(|op| op.responses.responses.get("201"))
+    .cloned()
+    .and_then(ReferenceOr::get_object)
+    .expect("Missing create order response")
+}
+
+#[actix_web::test]
+async fn links_resolved_to_operation_ids() {
+  let open_api = App::new()
+    .document(Spec::default())
+    .service(
+      scope("/orders")
+        .service(resource("").route(post().to(create_order)))
+        .service(resource("/{id}").route(get().to(get_order)))
+        .service(resource("/{id}/cancel").route(post().to(cancel_order))),
+    )
+    .open_api();
+
+  let get_order_id = open_api
+    .paths
+    .paths
+    .get("/orders/{id}")
+    .and_then(|pi| pi.operations.get(&OperationType::Get))
+    .and_then(|op| op.operation_id.clone())
+    .expect("Missing get order operation id");
+
+  let links = create_order_response(&open_api).links;
+  let get_link = links
+    .get("GetOrder")
+    .cloned()
+    .and_then(ReferenceOr::get_object)
+    .expect("Missing GetOrder link");
+  assert_eq!(
+    get_link.operation_identifier,
+    Some(OperationIdentifier::OperationId(get_order_id))
+  );
+  assert_eq!(
+    get_link.parameters.get("id"),
+    Some(&AnyOrExpression::Expression("$response.body#/id".to_string()))
+  );
+
+  let cancel_link = links
+    .get("CancelOrder")
+    .cloned()
+    .and_then(ReferenceOr::get_object)
+    .expect("Missing CancelOrder link");
+  assert_eq!(
+    cancel_link.operation_identifier,
+    Some(OperationIdentifier::OperationId("cancel_order".to_string()))
+  );
+
+  // links targeting unknown operation ids are kept, they are reported when validating the document
+  assert!(links.contains_key("UnknownOrder"));
+}
+
+#[actix_web::test]
+async fn links_to_unknown_targets_reported() {
+  let app = App::new()
+    .document(Spec {
+      validation: ValidationLevel::Error,
+      ..Default::default()
+    })
+    .service(resource("/orders").route(post().to(create_order)));
+
+  let links = create_order_response(&app.open_api()).links;
+  assert!(!links.contains_key("GetOrder"));
+  assert!(links.contains_key("CancelOrder"));
+
+  let errors = match app.try_build("/openapi.json") {
+    Err(BuildError::Invalid { errors, .. }) => errors,
+    Err(e) => panic!("Unexpected build error: {e}"),
+    Ok(_) => panic!("Expected links to unknown targets to be reported"),
+  };
+  let kinds: Vec<ValidationErrorKind> = errors
+    .into_iter()
+    .filter(|e| e.location == "/orders" && e.method == Some(OperationType::Post))
+    .map(|e| e.kind)
+    .collect();
+  assert_eq!(
+    kinds,
+    vec![
+      ValidationErrorKind::UnknownLinkHandler {
+        link: "GetOrder".to_string(),
+        handler: "get_order".to_string(),
+      },
+      ValidationErrorKind::UnknownLinkOperation {
+        link: "CancelOrder".to_string(),
+        operation_id: "cancel_order".to_string(),
+      },
+      ValidationErrorKind::UnknownLinkOperation {
+        link: "UnknownOrder".to_string(),
+        operation_id: "unknown_order".to_string(),
+      },
+    ]
+  );
+}
+
+#[actix_web::test]
+async fn links_to_handlers_mounted_twice_reported() {
+  let app = App::new()
+    .document(Spec {
+      validation: ValidationLevel::Error,
+      ..Default::default()
+    })
+    .service(resource("/orders").route(post().to(create_order)))
+    .service(resource("/orders/{id}").route(get().to(get_order)))
+    .service(resource("/archived-orders/{id}").route(get().to(get_order)));
+
+  let open_api = app.open_api();
+  assert!(!create_order_response(&open_api).links.contains_key("GetOrder"));
+  let mut get_order_ids: Vec<String> = ["/archived-orders/{id}", "/orders/{id}"]
+    .into_iter()
+    .filter_map(|path| open_api.paths.paths.get(path))
+    .filter_map(|pi| pi.operations.get(&OperationType::Get))
+    .filter_map(|op| op.operation_id.clone())
+    .collect();
+  get_order_ids.sort();
+  assert_eq!(get_order_ids.len(), 2);
+
+  let errors = match app.try_build("/openapi.json") {
+    Err(BuildError::Invalid { errors, .. }) => errors,
+    Err(e) => panic!("Unexpected build error: {e}"),
+    Ok(_) => panic!("Expected links to handlers mounted twice to be reported"),
+  };
+  assert!(errors.contains(&ValidationError {
+    location: "/orders".to_string(),
+    method: Some(OperationType::Post),
+    kind: ValidationErrorKind::AmbiguousLinkHandler {
+      link: "GetOrder".to_string(),
+      handler: "get_order".to_string(),
+      operation_ids: get_order_ids,
+    },
+  }));
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
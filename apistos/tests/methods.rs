@@ -0,0 +1,166 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::http::Method;
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::Spec;
+use apistos::web::{get, method, resource, trace, Route};
+use apistos_gen::api_operation;
+use apistos_models::paths::OperationType;
+use apistos_models::OpenApi;
+use serde_json::json;
+
+#[api_operation(summary = "Echo the request", operation_id = "echo")]
+pub(crate) async fn echo() -> Result<Json<()>, Error> {
+  Ok(Json(()))
+}
+
+#[api_operation(summary = "Trace the request")]
+pub(crate) async fn trace_request() -> Result<Json<()>, Error> {
+  Ok(Json(()))
+}
+
+#[api_operation(summary = "Open a tunnel")]
+pub(crate) async fn tunnel() -> Result<Json<()>, Error> {
+  Ok(Json(()))
+}
+
+fn open_api(spec: Spec) -> OpenApi {
+  App::new()
+    .document(spec)
+    .service(resource("/echo").route(get().to(echo)).route(Route::new().to(echo)))
+    .service(resource("/any").to(echo))
+    .service(resource("/trace").route(trace().to(trace_request)))
+    .service(resource("/tunnel").route(method(Method::CONNECT).to(tunnel)))
+    .open_api()
+}
+
+/// The placeholder method of routes accepting any method must never be exposed.
+fn assert_no_any_method_placeholder(open_api: &OpenApi) {
+  let placeholder = OperationType::Extension("*".to_owned());
+  assert!(open_api
+    .paths
+    .paths
+    .values()
+    .all(|path_item| !path_item.operations.contains_key(&placeholder)));
+  let paths = serde_json::to_value(&open_api.paths).expect("Unable to serialize as Json");
+  assert!(paths
+    .as_object()
+    .expect("Paths should be an object")
+    .values()
+    .all(|path_item| path_item.get("x-*").is_none()));
+}
+
+#[actix_web::test]
+async fn all_methods_documented() {
+  let open_api = open_api(Spec::default());
+  assert_no_any_method_placeholder(&open_api);
+
+  let any_path_item = open_api.paths.paths.get("/any").expect("Missing path");
+  assert_eq!(
+    any_path_item.operations.keys().cloned().collect::<Vec<_>>(),
+    vec![
+      OperationType::Get,
+      OperationType::Put,
+      OperationType::Post,
+      OperationType::Delete,
+      OperationType::Options,
+      OperationType::Head,
+      OperationType::Patch,
+      OperationType::Trace,
+    ]
+  );
+  assert_eq!(
+    any_path_item
+      .operations
+      .values()
+      .filter_map(|op| op.operation_id.clone())
+      .collect::<Vec<_>>(),
+    vec![
+      "echo_get",
+      "echo_put",
+      "echo_post",
+      "echo_delete",
+      "echo_options",
+      "echo_head",
+      "echo_patch",
+      "echo_trace"
+    ]
+  );
+  assert!(any_path_item.operations.values().all(|op| op.extensions.is_empty()));
+
+  let echo_path_item = open_api.paths.paths.get("/echo").expect("Missing path");
+  assert_eq!(echo_path_item.operations.len(), 8);
+  assert_eq!(
+    echo_path_item
+      .operations
+      .get(&OperationType::Get)
+      .and_then(|op| op.operation_id.clone()),
+    Some("echo".to_string())
+  );
+
+  let trace_path_item = open_api.paths.paths.get("/trace").expect("Missing path");
+  assert_eq!(
+    trace_path_item.operations.keys().cloned().collect::<Vec<_>>(),
+    vec![OperationType::Trace]
+  );
+
+  let tunnel_path_item = serde_json::to_value(open_api.paths.paths.get("/tunnel").expect("Missing path"))
+    .expect("Unable to serialize as Json");
+  assert_eq!(tunnel_path_item["x-connect"]["summary"], json!("Open a tunnel"));
+  assert!(tunnel_path_item["x-connect"]["operationId"].is_string());
+}
+
+#[actix_web::test]
+async fn any_method_routes_collapsed() {
+  let open_api = open_api(Spec {
+    collapse_any_method_routes: true,
+    ..Default::default()
+  });
+  assert_no_any_method_placeholder(&open_api);
+
+  let any_path_item =
+    serde_json::to_value(open_api.paths.paths.get("/any").expect("Missing path")).expect("Unable to serialize as Json");
+  assert_eq!(
+    any_path_item,
+    json!({
+      "get": {
+        "summary": "Echo the request",
+        "operationId": "echo",
+        "responses": {
+          "200": {
+            "description": ""
+          }
+        },
+        "deprecated": false,
+        "x-methods": ["get", "put", "post", "delete", "options", "head", "patch", "trace"]
+      }
+    })
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use schemars as _;
+use serde as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
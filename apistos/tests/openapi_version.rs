@@ -0,0 +1,186 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::{DefaultSecurityAccessor, Spec};
+use apistos::validation::{BuildError, ValidationError, ValidationErrorKind, ValidationLevel};
+use apistos::web::{get, resource};
+use apistos_gen::{api_operation, ApiComponent, ApiSecurity};
+use apistos_models::info::{Info, License};
+use apistos_models::OpenApiVersion;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[actix_web::test]
+async fn oas3_1() {
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct Pet {
+    id: u32,
+    name: Option<String>,
+  }
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet() -> Result<Json<Pet>, Error> {
+    panic!()
+  }
+
+  let spec = Spec {
+    openapi_version: OpenApiVersion::OAS3_1,
+    json_schema_dialect: Some("https://spec.openapis.org/oas/3.1/dialect/base".to_string()),
+    info: Info {
+      title: "Pets API".to_string(),
+      summary: Some("An API about pets".to_string()),
+      license: Some(License {
+        name: "Apache 2.0".to_string(),
+        identifier: Some("Apache-2.0".to_string()),
+        ..Default::default()
+      }),
+      version: "1.0.0".to_string(),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+  let app = App::new()
+    .document(spec)
+    .service(resource("/pet").route(get().to(get_pet)))
+    .build("/openapi.json");
+  let app = init_service(app).await;
+
+  let req = TestRequest::get().uri("/openapi.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: Value = read_body_json(resp).await;
+  assert_eq!(body["openapi"], json!("3.1.0"));
+  assert_eq!(
+    body["jsonSchemaDialect"],
+    json!("https://spec.openapis.org/oas/3.1/dialect/base")
+  );
+  assert_eq!(body["info"]["summary"], json!("An API about pets"));
+  assert_eq!(body["info"]["license"]["identifier"], json!("Apache-2.0"));
+  assert_eq!(
+    body["components"]["schemas"]["Pet"]["properties"]["name"],
+    json!({ "type": ["string", "null"] })
+  );
+}
+
+#[actix_web::test]
+async fn oas3_0_by_default() {
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct Pet {
+    id: u32,
+    name: Option<String>,
+  }
+
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn get_pet() -> Result<Json<Pet>, Error> {
+    panic!()
+  }
+
+  let app = App::new()
+    .document(Spec::default())
+    .service(resource("/pet").route(get().to(get_pet)))
+    .build("/openapi.json");
+  let app = init_service(app).await;
+
+  let req = TestRequest::get().uri("/openapi.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: Value = read_body_json(resp).await;
+  assert_eq!(body["openapi"], json!("3.0.3"));
+  assert_eq!(
+    body["components"]["schemas"]["Pet"]["properties"]["name"],
+    json!({ "type": "string", "nullable": true })
+  );
+}
+
+#[actix_web::test]
+async fn oas3_1_fields_reported_in_oas3_0() {
+  #[derive(ApiSecurity)]
+  #[openapi_security(scheme(security_type(mutual_tls)))]
+  struct ClientCertificate;
+
+  #[api_operation(summary = "Get a pet")]
+  pub(crate) async fn get_pet() -> Result<Json<()>, Error> {
+    panic!()
+  }
+
+  let spec = Spec {
+    info: Info {
+      title: "Pets API".to_string(),
+      summary: Some("An API about pets".to_string()),
+      license: Some(License {
+        name: "Apache 2.0".to_string(),
+        identifier: Some("Apache-2.0".to_string()),
+        url: Some("https://www.apache.org/licenses/LICENSE-2.0".to_string()),
+        ..Default::default()
+      }),
+      version: "1.0.0".to_string(),
+      ..Default::default()
+    },
+    default_security: vec![ClientCertificate::get_default_security()],
+    validation: ValidationLevel::Error,
+    ..Default::default()
+  };
+  let result = App::new()
+    .document(spec)
+    .service(resource("/pet").route(get().to(get_pet)))
+    .try_build("/openapi.json");
+
+  let Err(BuildError::Invalid { errors, .. }) = result else {
+    panic!("Expected validation to fail");
+  };
+  assert_eq!(
+    errors,
+    vec![
+      ValidationError {
+        location: "#/info".to_string(),
+        method: None,
+        kind: ValidationErrorKind::UnsupportedByOas30("summary".to_string()),
+      },
+      ValidationError {
+        location: "#/info/license".to_string(),
+        method: None,
+        kind: ValidationErrorKind::UnsupportedByOas30("identifier".to_string()),
+      },
+      ValidationError {
+        location: "#/info/license".to_string(),
+        method: None,
+        kind: ValidationErrorKind::ConflictingLicense,
+      },
+      ValidationError {
+        location: "#/components/securitySchemes/client_certificate".to_string(),
+        method: None,
+        kind: ValidationErrorKind::UnsupportedByOas30("mutualTLS".to_string()),
+      },
+    ]
+  );
+  assert_eq!(errors[0].to_string(), "#/info: summary is only supported by OAS 3.1");
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
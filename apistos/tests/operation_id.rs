@@ -4,18 +4,20 @@
 use actix_web::http::StatusCode;
 use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
 use actix_web::web::{Json, Path};
-use actix_web::{App, ResponseError};
+use actix_web::{App, Error, ResponseError};
 use apistos::app::OpenApiWrapper;
+use apistos::operation_id::{CamelCaseOperationId, HandlerNameOperationId, OperationIdStrategy};
 use apistos::spec::Spec;
-use apistos::web::{get, resource, scope};
+use apistos::web::{get, post, resource, scope};
 use apistos_gen::{api_operation, ApiComponent, ApiErrorComponent};
 use apistos_models::info::Info;
-use apistos_models::paths::OperationType;
+use apistos_models::paths::{Operation, OperationType};
 use apistos_models::tag::Tag;
 use apistos_models::OpenApi;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
 #[actix_web::test]
 async fn default_operation_id() {
@@ -136,6 +138,105 @@ async fn default_operation_id() {
   assert_eq!(operation_id3, Some("test3".to_string()))
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(summary = "Get an order")]
+pub(crate) async fn get_order(_id: Path<u32>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+#[api_operation(summary = "Create an order", operation_id = "newOrder")]
+pub(crate) async fn create_order(_body: Json<Order>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+fn open_api(strategy: Arc<dyn OperationIdStrategy>) -> OpenApi {
+  App::new()
+    .document(Spec {
+      operation_id_strategy: Some(strategy),
+      ..Default::default()
+    })
+    .service(
+      scope("/api")
+        .service(resource("/orders").route(post().to(create_order)))
+        .service(resource("/orders/{order_id}").route(get().to(get_order))),
+    )
+    .service(resource("/archived-orders/{order_id}").route(get().to(get_order)))
+    .open_api()
+}
+
+fn operation_id(open_api: &OpenApi, path: &str, operation_type: &OperationType) -> Option<String> {
+  open_api
+    .paths
+    .paths
+    .get(path)
+    .and_then(|pi| pi.operations.get(operation_type))
+    .and_then(|op| op.operation_id.clone())
+}
+
+#[actix_web::test]
+async fn handler_name_operation_id() {
+  let open_api = open_api(Arc::new(HandlerNameOperationId));
+
+  assert_eq!(
+    operation_id(&open_api, "/api/orders", &OperationType::Post),
+    Some("newOrder".to_string())
+  );
+  assert_eq!(
+    operation_id(&open_api, "/api/orders/{order_id}", &OperationType::Get),
+    Some("get_order".to_string())
+  );
+  assert_eq!(
+    operation_id(&open_api, "/archived-orders/{order_id}", &OperationType::Get),
+    Some("get_order_2".to_string())
+  );
+}
+
+#[actix_web::test]
+async fn camel_case_operation_id() {
+  let open_api = open_api(Arc::new(CamelCaseOperationId));
+
+  assert_eq!(
+    operation_id(&open_api, "/api/orders", &OperationType::Post),
+    Some("newOrder".to_string())
+  );
+  assert_eq!(
+    operation_id(&open_api, "/api/orders/{order_id}", &OperationType::Get),
+    Some("getApiOrdersByOrderId".to_string())
+  );
+  assert_eq!(
+    operation_id(&open_api, "/archived-orders/{order_id}", &OperationType::Get),
+    Some("getArchivedOrdersByOrderId".to_string())
+  );
+}
+
+#[actix_web::test]
+async fn custom_operation_id() {
+  struct SummaryOperationId;
+
+  impl OperationIdStrategy for SummaryOperationId {
+    fn operation_id(
+      &self,
+      _path: &str,
+      _operation_type: &OperationType,
+      operation: &Operation,
+      _handler: Option<&str>,
+    ) -> String {
+      operation.summary.clone().unwrap_or_default().replace(' ', "-")
+    }
+  }
+
+  let open_api = open_api(Arc::new(SummaryOperationId));
+
+  assert_eq!(
+    operation_id(&open_api, "/api/orders/{order_id}", &OperationType::Get),
+    Some("Get-an-order".to_string())
+  );
+}
+
 // Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
 use actix_service as _;
 use actix_web_lab as _;
@@ -145,6 +246,10 @@ use apistos_rapidoc as _;
 use apistos_redoc as _;
 use apistos_scalar as _;
 use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
 use garde_actix_web as _;
 use indexmap as _;
 use log as _;
@@ -152,3 +257,5 @@ use md5 as _;
 use once_cell as _;
 use regex as _;
 use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -346,6 +346,10 @@ use apistos_rapidoc as _;
 use apistos_redoc as _;
 use apistos_scalar as _;
 use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
 use garde_actix_web as _;
 use indexmap as _;
 use log as _;
@@ -355,3 +359,5 @@ use regex as _;
 use schemars as _;
 use serde as _;
 use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
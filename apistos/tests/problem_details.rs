@@ -0,0 +1,175 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+use actix_web::web::{Json, Path};
+use actix_web::{App, ResponseError};
+use apistos::app::OpenApiWrapper;
+use apistos::problem::{ProblemDetails, PROBLEM_JSON_CONTENT_TYPE};
+use apistos::spec::Spec;
+use apistos::web::{get, post, resource, scope};
+use apistos_gen::{api_operation, ApiComponent, ApiErrorComponent};
+use apistos_models::info::Info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::{Display, Formatter};
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) struct Pet {
+  id: u32,
+  name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) struct ConflictDetails {
+  conflicting_id: u32,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, ApiErrorComponent)]
+#[openapi_error(
+  problem_details,
+  status(code = 400),
+  status(code = 409, description = "Pet already exists", extension = ConflictDetails)
+)]
+pub(crate) enum ErrorResponse {
+  BadRequest,
+  Conflict(u32),
+}
+
+impl Display for ErrorResponse {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{self:?}")
+  }
+}
+
+impl ResponseError for ErrorResponse {
+  fn status_code(&self) -> StatusCode {
+    panic!()
+  }
+}
+
+#[api_operation(tag = "pet")]
+pub(crate) async fn add_pet(_body: Json<Pet>) -> Result<Json<Pet>, ErrorResponse> {
+  panic!()
+}
+
+#[api_operation(tag = "pet")]
+pub(crate) async fn get_pet(id: Path<u32>) -> Result<Json<Pet>, ProblemDetails> {
+  Err(
+    ProblemDetails::new(StatusCode::NOT_FOUND)
+      .with_type("https://example.com/problems/unknown-pet")
+      .with_detail(format!("Pet {id} does not exist"))
+      .with_extension("pet_id", *id),
+  )
+}
+
+fn app() -> apistos::app::App<
+  impl actix_service::ServiceFactory<
+    actix_web::dev::ServiceRequest,
+    Config = (),
+    Response = actix_web::dev::ServiceResponse,
+    Error = actix_web::Error,
+    InitError = (),
+  >,
+> {
+  App::new()
+    .document(Spec {
+      info: Info {
+        title: "Pets".to_string(),
+        ..Default::default()
+      },
+      ..Default::default()
+    })
+    .service(
+      scope("/pets")
+        .service(resource("").route(post().to(add_pet)))
+        .service(resource("/{id}").route(get().to(get_pet))),
+    )
+}
+
+#[actix_web::test]
+async fn problem_details_documented() {
+  let open_api = serde_json::to_value(app().open_api()).expect("Unable to serialize openapi");
+
+  let schemas = open_api["components"]["schemas"].as_object().expect("Missing schemas");
+  assert!(schemas.contains_key("ProblemDetails"));
+  assert!(schemas.contains_key("ConflictDetails"));
+  assert_eq!(schemas["ProblemDetails"]["properties"]["type"]["type"], json!("string"));
+
+  let problem_details = json!({ "$ref": "#/components/schemas/ProblemDetails" });
+  let add_pet_responses = &open_api["paths"]["/pets"]["post"]["responses"];
+  assert_eq!(
+    add_pet_responses["400"],
+    json!({
+      "description": "Bad Request",
+      "content": { PROBLEM_JSON_CONTENT_TYPE: { "schema": problem_details } }
+    })
+  );
+  assert_eq!(
+    add_pet_responses["409"],
+    json!({
+      "description": "Pet already exists",
+      "content": {
+        PROBLEM_JSON_CONTENT_TYPE: {
+          "schema": {
+            "allOf": [problem_details, { "$ref": "#/components/schemas/ConflictDetails" }]
+          }
+        }
+      }
+    })
+  );
+
+  let get_pet_responses = &open_api["paths"]["/pets/{id}"]["get"]["responses"];
+  assert_eq!(
+    get_pet_responses["default"]["content"][PROBLEM_JSON_CONTENT_TYPE]["schema"],
+    problem_details
+  );
+}
+
+#[actix_web::test]
+async fn problem_details_responded() {
+  let app = init_service(app().build("/openapi.json")).await;
+
+  let res = call_service(&app, TestRequest::get().uri("/pets/42").to_request()).await;
+  assert_eq!(res.status(), StatusCode::NOT_FOUND);
+  assert_eq!(
+    res.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok()),
+    Some(PROBLEM_JSON_CONTENT_TYPE)
+  );
+  let body: Value = read_body_json(res).await;
+  assert_eq!(
+    body,
+    json!({
+      "type": "https://example.com/problems/unknown-pet",
+      "title": "Not Found",
+      "status": 404,
+      "detail": "Pet 42 does not exist",
+      "pet_id": 42
+    })
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
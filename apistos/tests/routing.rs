@@ -87,6 +87,42 @@ async fn actix_routing() {
   assert_eq!(paths, expected_paths)
 }
 
+#[actix_web::test]
+async fn open_api_without_serving() {
+  #[api_operation(tag = "pet")]
+  pub(crate) async fn test(_params: Path<(u32, String)>) -> Result<Json<()>, Error> {
+    Ok(Json(()))
+  }
+
+  let info = Info {
+    title: "A well documented API".to_string(),
+    ..Default::default()
+  };
+  let spec = Spec {
+    info: info.clone(),
+    ..Default::default()
+  };
+
+  let app = App::new()
+    .document(spec)
+    .service(scope("test").service(resource("/{plop_id}/{clap_name}").route(get().to(test))))
+    .route("test2", patch().to(test));
+
+  let open_api = app.open_api();
+  assert_eq!(open_api.info, info);
+  let mut paths: Vec<&String> = open_api.paths.paths.keys().collect();
+  paths.sort();
+  assert_eq!(paths, vec!["/test/{plop_id}/{clap_name}", "/test2"]);
+
+  let app = init_service(app.build("/openapi.json")).await;
+  let req = TestRequest::get().uri("/openapi.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+  assert_eq!(body, open_api);
+}
+
 // Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
 use actix_service as _;
 use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
@@ -98,6 +134,10 @@ use apistos_rapidoc as _;
 use apistos_redoc as _;
 use apistos_scalar as _;
 use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
 use garde_actix_web as _;
 use indexmap as _;
 use log as _;
@@ -107,3 +147,5 @@ use regex as _;
 use schemars as _;
 use serde as _;
 use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
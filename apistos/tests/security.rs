@@ -0,0 +1,137 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::dev::Payload;
+use actix_web::web::Json;
+use actix_web::{App, Error, FromRequest, HttpRequest};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::{DefaultSecurityAccessor, Spec};
+use apistos::web::{get, resource, scope};
+use apistos_gen::{api_operation, ApiComponent, ApiSecurity};
+use apistos_models::paths::OperationType;
+use apistos_models::OpenApi;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::future::{ready, Ready};
+
+#[derive(ApiSecurity)]
+#[openapi_security(scheme(security_type(api_key(name = "api_key", api_key_in = "header"))))]
+struct ApiKey;
+
+impl FromRequest for ApiKey {
+  type Error = Error;
+  type Future = Ready<Result<Self, Self::Error>>;
+
+  fn from_request(_req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+    ready(Ok(ApiKey))
+  }
+}
+
+#[derive(ApiSecurity)]
+#[openapi_security(scheme(security_type(oauth2(flows(implicit(
+  authorization_url = "https://authorize.com",
+  scopes(scope = "orders:read", description = "Read orders")
+))))))]
+struct OAuth;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(summary = "Get orders")]
+pub(crate) async fn get_orders() -> Result<Json<Vec<Order>>, Error> {
+  panic!()
+}
+
+#[api_operation(summary = "Get orders with an api key")]
+pub(crate) async fn get_orders_with_key(_key: ApiKey) -> Result<Json<Vec<Order>>, Error> {
+  panic!()
+}
+
+fn operation_security(open_api: &OpenApi, path: &str) -> Value {
+  let security = open_api
+    .paths
+    .paths
+    .get(path)
+    .and_then(|pi| pi.operations.get(&OperationType::Get))
+    .map(|op| op.security.clone())
+    .expect("Missing operation");
+  serde_json::to_value(security).expect("Unable to serialize as Json")
+}
+
+#[actix_web::test]
+async fn default_and_scope_security() {
+  let open_api = App::new()
+    .document(Spec {
+      default_security: vec![ApiKey::get_default_security()],
+      ..Default::default()
+    })
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(
+      scope("/admin")
+        .service(resource("/orders").route(get().to(get_orders)))
+        .service(resource("/keyed-orders").route(get().to(get_orders_with_key)))
+        .security(OAuth::get_default_security().with_scopes(vec!["orders:read"])),
+    )
+    .service(
+      scope("/public")
+        .public()
+        .service(resource("/orders").route(get().to(get_orders))),
+    )
+    .service(
+      scope("/optional")
+        .security(ApiKey::get_default_security())
+        .public()
+        .service(resource("/orders").route(get().to(get_orders))),
+    )
+    .open_api();
+
+  assert_eq!(
+    serde_json::to_value(&open_api.security).expect("Unable to serialize as Json"),
+    json!([{ "api_key": [] }])
+  );
+  assert_eq!(operation_security(&open_api, "/orders"), json!([]));
+  assert_eq!(
+    operation_security(&open_api, "/admin/orders"),
+    json!([{ "o_auth": ["orders:read"] }])
+  );
+  assert_eq!(
+    operation_security(&open_api, "/admin/keyed-orders"),
+    json!([{ "api_key": [] }])
+  );
+  assert_eq!(operation_security(&open_api, "/public/orders"), json!([{}]));
+  assert_eq!(
+    operation_security(&open_api, "/optional/orders"),
+    json!([{ "api_key": [] }, {}])
+  );
+
+  let security_schemes = open_api.components.expect("Missing components").security_schemes;
+  assert_eq!(
+    security_schemes.keys().cloned().collect::<Vec<_>>(),
+    vec!["api_key".to_string(), "o_auth".to_string()]
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -0,0 +1,135 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::web::{Json, Path};
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::assert_spec_snapshot;
+use apistos::spec::Spec;
+use apistos::testing::UPDATE_SNAPSHOTS_ENV;
+use apistos::web::{get, post, resource, scope};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::info::Info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::panic::catch_unwind;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+pub(crate) struct Pet {
+  id: u32,
+  name: String,
+  tag: Option<String>,
+}
+
+#[api_operation(tag = "pet", summary = "Get a pet")]
+pub(crate) async fn get_pet(_id: Path<u32>) -> Result<Json<Pet>, Error> {
+  panic!()
+}
+
+#[api_operation(tag = "pet", summary = "Add a pet")]
+pub(crate) async fn add_pet(_body: Json<Pet>) -> Result<Json<Pet>, Error> {
+  panic!()
+}
+
+fn app() -> apistos::app::App<
+  impl actix_service::ServiceFactory<
+    actix_web::dev::ServiceRequest,
+    Config = (),
+    Response = actix_web::dev::ServiceResponse,
+    Error = Error,
+    InitError = (),
+  >,
+> {
+  App::new()
+    .document(Spec {
+      info: Info {
+        title: "Pets".to_string(),
+        version: "1.0.0".to_string(),
+        ..Default::default()
+      },
+      ..Default::default()
+    })
+    .service(
+      scope("/pets")
+        .service(resource("").route(post().to(add_pet)))
+        .service(resource("/{id}").route(get().to(get_pet))),
+    )
+}
+
+fn snapshot_path(name: &str) -> std::path::PathBuf {
+  std::env::temp_dir().join(format!("apistos-{}-{name}.json", std::process::id()))
+}
+
+fn panic_message(result: std::thread::Result<()>) -> String {
+  let payload = result.expect_err("Snapshot assertion should have failed");
+  payload
+    .downcast_ref::<String>()
+    .cloned()
+    .expect("Unexpected panic payload")
+}
+
+#[test]
+fn spec_matches_snapshot() {
+  assert_spec_snapshot!(app, "tests/snapshots/pets.json");
+}
+
+#[test]
+fn spec_differences_reported_by_path() {
+  if std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok() {
+    return;
+  }
+
+  let snapshot = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/pets.json"))
+    .expect("Unable to read snapshot");
+  let mut snapshot: Value = serde_json::from_str(&snapshot).expect("Unable to parse snapshot");
+  snapshot["info"]["title"] = json!("Stores");
+  snapshot["paths"]["/pets/{id}"]
+    .as_object_mut()
+    .expect("Missing path")
+    .remove("get");
+  snapshot["paths"]["/pets/{id}"]["delete"] = json!({ "responses": {} });
+  let path = snapshot_path("differences");
+  std::fs::write(&path, snapshot.to_string()).expect("Unable to write snapshot");
+
+  let message = panic_message(catch_unwind(|| assert_spec_snapshot!(app, &path)));
+  let _ = std::fs::remove_file(&path);
+
+  assert!(message.contains("openapi specification does not match snapshot"));
+  assert!(message.contains(r#"~ $.info.title: expected "Stores", got "Pets""#));
+  assert!(message.contains(r#"- $.paths["/pets/{id}"].delete: {"responses":{}}"#));
+  assert!(message.contains(r#"+ $.paths["/pets/{id}"].get: "#));
+  assert!(message.contains(&format!("run with {UPDATE_SNAPSHOTS_ENV}=1 to update it")));
+}
+
+#[test]
+fn missing_snapshot_reported() {
+  if std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok() {
+    return;
+  }
+
+  let path = snapshot_path("missing");
+  let message = panic_message(catch_unwind(|| assert_spec_snapshot!(app, &path)));
+  assert!(message.contains(&format!("run with {UPDATE_SNAPSHOTS_ENV}=1 to create it")));
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -0,0 +1,149 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::Spec;
+use apistos::validation::{BuildError, ValidationLevel};
+use apistos::web::{get, resource};
+use apistos_gen::api_operation;
+use apistos_models::tag::Tag;
+use serde_json::{json, Value};
+
+#[api_operation(tag = "orders", summary = "Get orders")]
+pub(crate) async fn get_orders() -> Result<Json<Vec<u32>>, Error> {
+  Ok(Json(vec![]))
+}
+
+#[api_operation(tag = "internal", summary = "Get metrics")]
+pub(crate) async fn get_metrics() -> Result<Json<Vec<u32>>, Error> {
+  Ok(Json(vec![]))
+}
+
+#[actix_web::test]
+async fn transformers_applied_in_order() {
+  let spec = Spec {
+    tags: vec![Tag {
+      name: "orders".to_string(),
+      ..Default::default()
+    }],
+    validation: ValidationLevel::Error,
+    ..Default::default()
+  }
+  .with_transformer(|open_api| {
+    open_api.paths.paths.retain(|path, _| !path.starts_with("/internal"));
+    Ok(())
+  })
+  .with_transformer(|open_api| {
+    for operation in open_api
+      .paths
+      .paths
+      .values_mut()
+      .flat_map(|pi| pi.operations.values_mut())
+    {
+      operation.summary = operation.summary.as_ref().map(|summary| format!("{summary} (v2)"));
+      operation.extensions.insert("x-audience".to_string(), json!("public"));
+    }
+    Ok(())
+  });
+
+  let app = App::new()
+    .document(spec)
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/internal/metrics").route(get().to(get_metrics)))
+    .try_build("/openapi.json")
+    .expect("Unable to build app");
+  let app = init_service(app).await;
+
+  let req = TestRequest::get().uri("/openapi.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let open_api: Value = try_read_body_json(resp).await.expect("Unable to read body");
+  assert_eq!(
+    open_api["paths"],
+    json!({
+      "/orders": {
+        "get": {
+          "tags": ["orders"],
+          "summary": "Get orders (v2)",
+          "operationId": open_api["paths"]["/orders"]["get"]["operationId"],
+          "responses": open_api["paths"]["/orders"]["get"]["responses"],
+          "deprecated": false,
+          "x-audience": "public"
+        }
+      }
+    })
+  );
+}
+
+#[actix_web::test]
+async fn built_open_api_is_transformed() {
+  let spec = Spec::default().with_transformer(|open_api| {
+    open_api.paths.paths.retain(|path, _| !path.starts_with("/internal"));
+    Ok(())
+  });
+
+  let app = App::new()
+    .document(spec)
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/internal/metrics").route(get().to(get_metrics)));
+
+  assert_eq!(app.open_api().paths.paths.len(), 2);
+  let built_open_api = app.built_open_api().expect("Unable to build openapi");
+  assert_eq!(
+    built_open_api.paths.paths.keys().collect::<Vec<_>>(),
+    vec![&"/orders".to_string()]
+  );
+}
+
+#[actix_web::test]
+async fn failing_transformer_aborts_build() {
+  let spec = Spec::default().with_transformer(|open_api| {
+    if open_api.paths.paths.keys().any(|path| path.starts_with("/internal")) {
+      return Err("internal paths must not be exposed".into());
+    }
+    Ok(())
+  });
+
+  let result = App::new()
+    .document(spec)
+    .service(resource("/orders").route(get().to(get_orders)))
+    .service(resource("/internal/metrics").route(get().to(get_metrics)))
+    .try_build("/openapi.json");
+
+  let Err(error) = result else {
+    panic!("Expected transformer to fail");
+  };
+  assert!(matches!(error, BuildError::Transformer { document: None, .. }));
+  assert_eq!(
+    error.to_string(),
+    "failed to transform openapi document: internal paths must not be exposed"
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use schemars as _;
+use serde as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
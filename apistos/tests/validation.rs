@@ -0,0 +1,185 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::web::{Json, Path};
+use actix_web::{App, Error};
+use apistos::app::OpenApiWrapper;
+use apistos::spec::{DefaultParameters, Spec};
+use apistos::validation::{BuildError, ValidationError, ValidationErrorKind, ValidationLevel};
+use apistos::web::{get, post, resource};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::paths::{OperationType, Parameter, ParameterDefinition, ParameterIn};
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::tag::Tag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+struct Order {
+  id: u32,
+}
+
+#[api_operation(tag = "orders", operation_id = "order")]
+pub(crate) async fn get_order(_id: Path<u32>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+#[api_operation(tag = "payments", operation_id = "order")]
+pub(crate) async fn create_order(_body: Json<Order>) -> Result<Json<Order>, Error> {
+  panic!()
+}
+
+fn spec(validation: ValidationLevel) -> Spec {
+  Spec {
+    tags: vec![Tag {
+      name: "orders".to_string(),
+      ..Default::default()
+    }],
+    default_parameters: vec![DefaultParameters {
+      parameters: vec![Parameter {
+        name: "X-Tenant".to_string(),
+        _in: ParameterIn::Header,
+        definition: Some(ParameterDefinition::Schema(ReferenceOr::Reference {
+          _ref: "#/components/schemas/Tenant".to_string(),
+        })),
+        ..Default::default()
+      }],
+      components: vec![],
+    }],
+    validation,
+    ..Default::default()
+  }
+}
+
+#[actix_web::test]
+async fn invalid_document_rejected() {
+  let result = App::new()
+    .document(spec(ValidationLevel::Error))
+    .service(
+      resource("/orders")
+        .route(get().to(get_order))
+        .route(post().to(create_order)),
+    )
+    .try_build("/openapi.json");
+
+  let Err(BuildError::Invalid { document, errors }) = result else {
+    panic!("Expected validation to fail");
+  };
+  assert_eq!(document, None);
+  assert_eq!(
+    errors,
+    vec![
+      ValidationError {
+        location: "/orders".to_string(),
+        method: Some(OperationType::Get),
+        kind: ValidationErrorKind::UnknownPathParameter(String::new()),
+      },
+      ValidationError {
+        location: "/orders".to_string(),
+        method: Some(OperationType::Post),
+        kind: ValidationErrorKind::DuplicateOperationId("order".to_string()),
+      },
+      ValidationError {
+        location: "/orders".to_string(),
+        method: Some(OperationType::Post),
+        kind: ValidationErrorKind::UndeclaredTag("payments".to_string()),
+      },
+      ValidationError {
+        location: "#/components/parameters/X-Tenant".to_string(),
+        method: None,
+        kind: ValidationErrorKind::DanglingReference("#/components/schemas/Tenant".to_string()),
+      },
+    ]
+  );
+  assert_eq!(
+    errors[1].to_string(),
+    "POST /orders: operation id order is used by multiple operations"
+  );
+}
+
+#[actix_web::test]
+async fn invalid_document_logged() {
+  let result = App::new()
+    .document(spec(ValidationLevel::Log))
+    .service(
+      resource("/orders")
+        .route(get().to(get_order))
+        .route(post().to(create_order)),
+    )
+    .try_build("/openapi.json");
+
+  assert!(result.is_ok());
+}
+
+#[actix_web::test]
+#[should_panic(expected = "openapi document is invalid")]
+async fn invalid_document_panics() {
+  App::new()
+    .document(spec(ValidationLevel::Panic))
+    .service(resource("/orders").route(get().to(get_order)))
+    .try_build("/openapi.json")
+    .ok();
+}
+
+#[actix_web::test]
+async fn valid_document_built() {
+  let result = App::new()
+    .document(Spec {
+      tags: vec![Tag {
+        name: "orders".to_string(),
+        ..Default::default()
+      }],
+      validation: ValidationLevel::Error,
+      ..Default::default()
+    })
+    .service(resource("/orders/{id}").route(get().to(get_order)))
+    .try_build("/openapi.json");
+
+  assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn undeclared_tags_rejected_without_document_tags() {
+  let result = App::new()
+    .document(Spec {
+      validation: ValidationLevel::Error,
+      ..Default::default()
+    })
+    .service(resource("/orders/{id}").route(get().to(get_order)))
+    .try_build("/openapi.json");
+
+  let Err(BuildError::Invalid { errors, .. }) = result else {
+    panic!("Expected validation to fail");
+  };
+  assert_eq!(
+    errors,
+    vec![ValidationError {
+      location: "/orders/{id}".to_string(),
+      method: Some(OperationType::Get),
+      kind: ValidationErrorKind::UndeclaredTag("orders".to_string()),
+    }]
+  );
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;
@@ -0,0 +1,98 @@
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+
+use actix_web::test::{call_service, init_service, try_read_body_json, TestRequest};
+use actix_web::web::Json;
+use actix_web::{App, Error};
+use apistos::actix::NoContent;
+use apistos::app::OpenApiWrapper;
+use apistos::spec::Spec;
+use apistos::web::{get, post, resource};
+use apistos_gen::{api_operation, ApiComponent};
+use apistos_models::paths::OperationType;
+use apistos_models::reference_or::ReferenceOr;
+use apistos_models::{OpenApi, OpenApiVersion};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[actix_web::test]
+async fn webhooks() {
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct Pet {
+    id: u32,
+  }
+
+  #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, ApiComponent)]
+  struct PetEvent {
+    pet: Pet,
+  }
+
+  #[api_operation(summary = "Get a pet")]
+  pub(crate) async fn get_pet() -> Result<Json<Pet>, Error> {
+    panic!()
+  }
+
+  #[api_operation(summary = "A new pet is available")]
+  pub(crate) async fn new_pet(_body: Json<PetEvent>) -> Result<NoContent, Error> {
+    panic!()
+  }
+
+  let spec = Spec {
+    openapi_version: OpenApiVersion::OAS3_1,
+    ..Default::default()
+  };
+  let app = App::new()
+    .document(spec)
+    .service(resource("/pet").route(get().to(get_pet)))
+    .webhook("newPet", post().to(new_pet))
+    .build("/openapi.json");
+  let app = init_service(app).await;
+
+  let req = TestRequest::get().uri("/openapi.json").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_success());
+
+  let body: OpenApi = try_read_body_json(resp).await.expect("Unable to read body");
+  assert_eq!(body.paths.paths.keys().collect::<Vec<_>>(), vec!["/pet"]);
+
+  let webhook = body.webhooks.get("newPet").cloned().and_then(ReferenceOr::get_object);
+  let operation = webhook
+    .expect("Missing webhook")
+    .operations
+    .get(&OperationType::Post)
+    .cloned()
+    .expect("Missing webhook operation");
+  assert_eq!(operation.summary, Some("A new pet is available".to_string()));
+  assert!(operation.request_body.is_some());
+
+  let schemas = body.components.expect("Missing components").schemas;
+  assert!(schemas.contains_key("Pet"));
+  assert!(schemas.contains_key("PetEvent"));
+
+  let req = TestRequest::post().uri("/newPet").to_request();
+  let resp = call_service(&app, req).await;
+  assert!(resp.status().is_client_error());
+}
+
+// Imports bellow aim at making clippy happy. Those dependencies are necessary for integration-test.
+use actix_service as _;
+use actix_web_lab as _;
+use apistos_core as _;
+use apistos_plugins as _;
+use apistos_rapidoc as _;
+use apistos_redoc as _;
+use apistos_scalar as _;
+use apistos_swagger_ui as _;
+#[cfg(feature = "compression")]
+use brotli as _;
+#[cfg(feature = "compression")]
+use flate2 as _;
+use garde_actix_web as _;
+use indexmap as _;
+use log as _;
+use md5 as _;
+use once_cell as _;
+use regex as _;
+use serde_json as _;
+#[cfg(feature = "yaml")]
+use serde_norway as _;